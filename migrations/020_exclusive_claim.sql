@@ -0,0 +1 @@
+ALTER TABLE guilds ADD COLUMN IF NOT EXISTS exclusive_claim BOOLEAN DEFAULT FALSE;
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS staff_away (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    reason TEXT,
+    away_until TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE(guild_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_staff_away_guild ON staff_away(guild_id);
+CREATE INDEX IF NOT EXISTS idx_staff_away_until ON staff_away(away_until) WHERE away_until IS NOT NULL;
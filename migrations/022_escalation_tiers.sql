@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS escalation_tiers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    guild_id BIGINT NOT NULL,
+    tier INTEGER NOT NULL,
+    role_id BIGINT NOT NULL,
+    delay_minutes INTEGER NOT NULL,
+    delivery VARCHAR(16) NOT NULL DEFAULT 'dm',
+    channel_id BIGINT,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE(guild_id, tier)
+);
+
+CREATE INDEX IF NOT EXISTS idx_escalation_tiers_guild ON escalation_tiers(guild_id);
+
+ALTER TABLE ticket_escalations ADD COLUMN IF NOT EXISTS current_tier INTEGER NOT NULL DEFAULT 0;
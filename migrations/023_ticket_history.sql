@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS ticket_history (
+    ticket_id UUID PRIMARY KEY,
+    guild_id BIGINT NOT NULL,
+    ticket_number INTEGER NOT NULL,
+    owner_id BIGINT NOT NULL,
+    category_id UUID,
+    priority VARCHAR(20),
+    claimed_by BIGINT,
+    assigned_to BIGINT,
+    rating INTEGER,
+    opened_at TIMESTAMPTZ NOT NULL,
+    closed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_ticket_history_guild_opened ON ticket_history(guild_id, opened_at);
+CREATE INDEX IF NOT EXISTS idx_ticket_history_guild_closed ON ticket_history(guild_id, closed_at);
+CREATE INDEX IF NOT EXISTS idx_tickets_guild_created ON tickets(guild_id, created_at);
@@ -0,0 +1,23 @@
+ALTER TABLE tickets ADD COLUMN IF NOT EXISTS claimed_at TIMESTAMPTZ;
+ALTER TABLE tickets ADD COLUMN IF NOT EXISTS assigned_at TIMESTAMPTZ;
+ALTER TABLE tickets ADD COLUMN IF NOT EXISTS first_response_at TIMESTAMPTZ;
+ALTER TABLE tickets ADD COLUMN IF NOT EXISTS first_responder_id BIGINT;
+
+ALTER TABLE ticket_history ADD COLUMN IF NOT EXISTS claimed_at TIMESTAMPTZ;
+ALTER TABLE ticket_history ADD COLUMN IF NOT EXISTS assigned_at TIMESTAMPTZ;
+ALTER TABLE ticket_history ADD COLUMN IF NOT EXISTS first_response_at TIMESTAMPTZ;
+ALTER TABLE ticket_history ADD COLUMN IF NOT EXISTS first_responder_id BIGINT;
+ALTER TABLE ticket_history ADD COLUMN IF NOT EXISTS closed_by BIGINT;
+
+CREATE INDEX IF NOT EXISTS idx_ticket_history_guild_closed_by ON ticket_history(guild_id, closed_by);
+
+-- Ticket messages and notes are deleted with their ticket, so per-staff
+-- activity is counted separately.
+CREATE TABLE IF NOT EXISTS staff_daily_activity (
+    guild_id BIGINT NOT NULL,
+    user_id BIGINT NOT NULL,
+    day DATE NOT NULL,
+    messages INTEGER NOT NULL DEFAULT 0,
+    notes INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (guild_id, user_id, day)
+);
@@ -0,0 +1 @@
+ALTER TABLE ticket_history ADD COLUMN IF NOT EXISTS message_count INTEGER NOT NULL DEFAULT 0;
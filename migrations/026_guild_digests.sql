@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS guild_digests (
+    guild_id BIGINT PRIMARY KEY,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    frequency VARCHAR(10) NOT NULL DEFAULT 'weekly',
+    weekday SMALLINT NOT NULL DEFAULT 0,
+    hour SMALLINT NOT NULL DEFAULT 9,
+    minute SMALLINT NOT NULL DEFAULT 0,
+    timezone VARCHAR(64) NOT NULL DEFAULT 'UTC',
+    sla_hours INTEGER NOT NULL DEFAULT 24,
+    last_sent_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_guild_digests_enabled ON guild_digests(enabled) WHERE enabled = TRUE;
@@ -0,0 +1,51 @@
+CREATE TABLE IF NOT EXISTS tag_aliases (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tag_id UUID NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    guild_id BIGINT NOT NULL,
+    alias VARCHAR(100) NOT NULL,
+    created_by BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tag_aliases_guild_alias ON tag_aliases(guild_id, LOWER(alias));
+CREATE INDEX IF NOT EXISTS idx_tag_aliases_tag ON tag_aliases(tag_id);
+
+-- Names and aliases share one namespace per guild. The unique index covers
+-- alias/alias clashes; these triggers cover name/alias clashes in both directions.
+-- Both take the same transaction-scoped advisory lock on the lowercased name, so
+-- a concurrent tag and alias with the same name can't both pass the check.
+CREATE OR REPLACE FUNCTION lock_tag_name(guild BIGINT, name TEXT) RETURNS VOID AS $$
+BEGIN
+    PERFORM pg_advisory_xact_lock(hashtextextended('tag_name:' || guild || ':' || LOWER(name), 0));
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION check_tag_alias_unique() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM lock_tag_name(NEW.guild_id, NEW.alias);
+    IF EXISTS (SELECT 1 FROM tags WHERE guild_id = NEW.guild_id AND LOWER(name) = LOWER(NEW.alias)) THEN
+        RAISE EXCEPTION 'A tag named "%" already exists', NEW.alias USING ERRCODE = 'unique_violation';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS tag_aliases_unique_name ON tag_aliases;
+CREATE TRIGGER tag_aliases_unique_name
+    BEFORE INSERT OR UPDATE OF alias ON tag_aliases
+    FOR EACH ROW EXECUTE FUNCTION check_tag_alias_unique();
+
+CREATE OR REPLACE FUNCTION check_tag_name_unique() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM lock_tag_name(NEW.guild_id, NEW.name);
+    IF EXISTS (SELECT 1 FROM tag_aliases WHERE guild_id = NEW.guild_id AND LOWER(alias) = LOWER(NEW.name)) THEN
+        RAISE EXCEPTION '"%" is already an alias of another tag', NEW.name USING ERRCODE = 'unique_violation';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS tags_unique_alias ON tags;
+CREATE TRIGGER tags_unique_alias
+    BEFORE INSERT OR UPDATE OF name ON tags
+    FOR EACH ROW EXECUTE FUNCTION check_tag_name_unique();
@@ -0,0 +1,3 @@
+-- Embed tags store their embed and link buttons as JSON; `content` keeps the
+-- description so search and the text fallback still work.
+ALTER TABLE tags ADD COLUMN IF NOT EXISTS embed JSONB;
@@ -0,0 +1,2 @@
+-- similarity() for tag autocomplete and "did you mean" suggestions.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS tag_triggers (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    guild_id BIGINT NOT NULL,
+    tag_id UUID NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    pattern TEXT NOT NULL,
+    is_regex BOOLEAN NOT NULL DEFAULT FALSE,
+    -- NULL applies the rule to tickets in every category
+    category_id UUID REFERENCES ticket_categories(id) ON DELETE CASCADE,
+    cooldown_seconds INTEGER NOT NULL DEFAULT 600,
+    first_message_only BOOLEAN NOT NULL DEFAULT FALSE,
+    created_by BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_tag_triggers_guild ON tag_triggers(guild_id);
@@ -0,0 +1,21 @@
+-- Roles allowed to create, edit or delete tags. With no roles for an action,
+-- anyone can create tags and only creators can edit or delete them.
+CREATE TABLE IF NOT EXISTS tag_permission_roles (
+    guild_id BIGINT NOT NULL,
+    action VARCHAR(10) NOT NULL CHECK (action IN ('create', 'edit', 'delete')),
+    role_id BIGINT NOT NULL,
+    PRIMARY KEY (guild_id, action, role_id)
+);
+
+-- Previous versions of a tag, saved each time its content is overwritten.
+CREATE TABLE IF NOT EXISTS tag_revisions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    tag_id UUID NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    guild_id BIGINT NOT NULL,
+    revision INTEGER NOT NULL,
+    content TEXT NOT NULL,
+    embed JSONB,
+    replaced_by BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tag_id, revision)
+);
@@ -0,0 +1,24 @@
+-- Tags curated by the bot owner that guilds can subscribe to.
+CREATE TABLE IF NOT EXISTS tag_library (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(100) NOT NULL,
+    content TEXT NOT NULL,
+    embed JSONB,
+    published_by BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tag_library_name ON tag_library(LOWER(name));
+
+CREATE TABLE IF NOT EXISTS tag_library_subscriptions (
+    guild_id BIGINT PRIMARY KEY,
+    subscribed_by BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Subscribed guilds get a copy of each library tag that follows library updates
+-- until the guild edits it.
+ALTER TABLE tags ADD COLUMN IF NOT EXISTS library_tag_id UUID REFERENCES tag_library(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_tags_library ON tags(library_tag_id);
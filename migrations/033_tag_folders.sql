@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS tag_folders (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    guild_id BIGINT NOT NULL,
+    name VARCHAR(50) NOT NULL,
+    created_by BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tag_folders_guild_name ON tag_folders(guild_id, LOWER(name));
+
+-- Deleting a folder leaves its tags unfiled.
+ALTER TABLE tags ADD COLUMN IF NOT EXISTS folder_id UUID REFERENCES tag_folders(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_tags_folder ON tags(folder_id);
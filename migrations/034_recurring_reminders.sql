@@ -0,0 +1,7 @@
+-- Repeating reminders. `recurrence` holds the schedule (see utils/reminder.rs);
+-- a recurring reminder is rescheduled after each delivery until `repeat_until`
+-- passes or it has been delivered `repeat_limit` times.
+ALTER TABLE reminders ADD COLUMN IF NOT EXISTS recurrence TEXT;
+ALTER TABLE reminders ADD COLUMN IF NOT EXISTS repeat_until TIMESTAMPTZ;
+ALTER TABLE reminders ADD COLUMN IF NOT EXISTS repeat_limit INTEGER;
+ALTER TABLE reminders ADD COLUMN IF NOT EXISTS occurrences INTEGER NOT NULL DEFAULT 0;
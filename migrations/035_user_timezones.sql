@@ -0,0 +1,7 @@
+-- Per-user preferences that aren't tied to a guild. `timezone` is an IANA name
+-- (e.g. Europe/London) used to read times such as `tomorrow 9am`.
+CREATE TABLE IF NOT EXISTS user_settings (
+    user_id BIGINT PRIMARY KEY,
+    timezone VARCHAR(64) NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,8 @@
+-- Follow-up reminders attached to a ticket. They post in the ticket channel,
+-- mentioning `mention` (owner, claimer or both), and are removed when the ticket
+-- is closed or, with `cancel_on_reply`, when the owner sends a message first.
+ALTER TABLE reminders ADD COLUMN IF NOT EXISTS ticket_id UUID REFERENCES tickets(id) ON DELETE CASCADE;
+ALTER TABLE reminders ADD COLUMN IF NOT EXISTS mention VARCHAR(16);
+ALTER TABLE reminders ADD COLUMN IF NOT EXISTS cancel_on_reply BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX IF NOT EXISTS idx_reminders_ticket ON reminders(ticket_id) WHERE ticket_id IS NOT NULL AND completed = FALSE;
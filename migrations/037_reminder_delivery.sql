@@ -0,0 +1,12 @@
+-- Delivery state for reminders. An instance claims a due reminder by pushing
+-- `next_attempt_at` forward (a lease), so other instances skip it; a failed
+-- delivery sets it to the retry time instead. `attempts` counts failures since
+-- the last successful delivery and the outcome of the latest attempt is kept
+-- for `reminder list`.
+ALTER TABLE reminders ADD COLUMN IF NOT EXISTS next_attempt_at TIMESTAMPTZ;
+ALTER TABLE reminders ADD COLUMN IF NOT EXISTS attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE reminders ADD COLUMN IF NOT EXISTS delivery_status VARCHAR(16);
+ALTER TABLE reminders ADD COLUMN IF NOT EXISTS last_error TEXT;
+ALTER TABLE reminders ADD COLUMN IF NOT EXISTS last_attempt_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_reminders_due ON reminders(remind_at) WHERE completed = FALSE;
@@ -0,0 +1,14 @@
+-- Posted panels are re-rendered in place when categories or panel settings
+-- change. `all_categories` panels pick up new categories automatically,
+-- `uses_guild_embed` panels take their embed from the guild's panel settings,
+-- and `missing_since` is set once the panel message is found to be deleted.
+ALTER TABLE ticket_panel ADD COLUMN IF NOT EXISTS all_categories BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE ticket_panel ADD COLUMN IF NOT EXISTS uses_guild_embed BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE ticket_panel ADD COLUMN IF NOT EXISTS missing_since TIMESTAMPTZ;
+
+-- Panels sent from the setup menu are the ones that track categories and use
+-- the guild embed.
+UPDATE ticket_panel SET uses_guild_embed = TRUE
+WHERE id IN (SELECT panel_id FROM panel_categories);
+
+CREATE INDEX IF NOT EXISTS idx_ticket_panel_message_id ON ticket_panel(message_id);
@@ -0,0 +1,9 @@
+-- Support role overwrites changed by an exclusive claim, so unclaiming only undoes
+-- the SEND_MESSAGES deny the claim added. `send_allowed` is whether the role had
+-- SEND_MESSAGES explicitly allowed before.
+CREATE TABLE IF NOT EXISTS ticket_claim_locks (
+    ticket_id UUID NOT NULL REFERENCES tickets(id) ON DELETE CASCADE,
+    role_id BIGINT NOT NULL,
+    send_allowed BOOLEAN NOT NULL DEFAULT FALSE,
+    PRIMARY KEY (ticket_id, role_id)
+);
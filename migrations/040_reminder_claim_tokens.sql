@@ -0,0 +1,4 @@
+-- Each claim of a reminder gets a fresh token. Recording the outcome and renewing
+-- the lease only apply while the token still matches, so an instance whose lease
+-- ran out can't overwrite the state of the instance that claimed it next.
+ALTER TABLE reminders ADD COLUMN IF NOT EXISTS claim_token UUID;
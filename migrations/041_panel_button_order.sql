@@ -0,0 +1,11 @@
+-- Buttons are shown in the order they were added to a panel. New rows take the
+-- next value of the sequence; existing ones are numbered by when they were added.
+ALTER TABLE panel_categories ADD COLUMN IF NOT EXISTS position BIGINT;
+CREATE SEQUENCE IF NOT EXISTS panel_categories_position_seq OWNED BY panel_categories.position;
+
+UPDATE panel_categories SET position = ordered.n
+FROM (SELECT id, ROW_NUMBER() OVER (ORDER BY created_at, id) AS n FROM panel_categories) ordered
+WHERE panel_categories.id = ordered.id AND panel_categories.position IS NULL;
+
+SELECT setval('panel_categories_position_seq', COALESCE((SELECT MAX(position) FROM panel_categories), 0) + 1, false);
+ALTER TABLE panel_categories ALTER COLUMN position SET DEFAULT nextval('panel_categories_position_seq');
@@ -110,9 +110,28 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database)
             return Ok(());
         }
 
+        if let Some(away) = crate::database::staff::get_away_status(&db.pool, ticket.guild_id, assigned_user_id).await? {
+            let embed = create_error_embed(
+                "Staff Away",
+                format!("<@{}> is currently away{}. Assign someone else or wait until they are back", assigned_user_id, crate::utils::format_away_until(&away)),
+            );
+
+            interaction
+                .create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+                    serenity::all::CreateInteractionResponseMessage::new()
+                        .embed(embed)
+                        .ephemeral(true)
+                ))
+                .await?;
+            return Ok(());
+        }
+
         // Assign the ticket
         crate::database::ticket::assign_ticket(&db.pool, ticket.id, assigned_user_id).await?;
 
+        let previous_assignee: Vec<i64> = ticket.assigned_to.into_iter().collect();
+        let _ = crate::utils::sync_exclusive_claim(ctx, db, ticket.id, &previous_assignee).await;
+
         // Send DM to assigned user
         if let Ok(user) = serenity::all::UserId::new(assigned_user_id as u64).to_user(&ctx.http).await {
             if let Ok(dm_channel) = user.create_dm_channel(&ctx.http).await {
@@ -0,0 +1,106 @@
+use serenity::all::{
+    CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
+    Permissions, ResolvedOption, ResolvedValue,
+};
+use crate::database::Database;
+use crate::utils::autoresponder::TriggerInput;
+use crate::utils::{create_embed, create_error_embed, create_success_embed};
+use anyhow::Result;
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database) -> Result<()> {
+    let options = &interaction.data.options();
+    let guild_id = interaction.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?.get() as i64;
+
+    let embed = if let Some(ResolvedOption {
+        value: ResolvedValue::SubCommand(sub_options),
+        name,
+        ..
+    }) = options.first()
+    {
+        let string_option = |name: &str| sub_options.iter().find_map(|o| match o.value {
+            ResolvedValue::String(value) if o.name == name => Some(value),
+            _ => None,
+        });
+        let bool_option = |name: &str| sub_options.iter().any(|o| o.name == name && matches!(o.value, ResolvedValue::Boolean(true)));
+
+        match *name {
+            "add" => {
+                let input = TriggerInput {
+                    tag: string_option("tag").unwrap_or_default(),
+                    pattern: string_option("pattern").unwrap_or_default(),
+                    is_regex: bool_option("regex"),
+                    cooldown: string_option("cooldown"),
+                    category: string_option("category"),
+                    first_message_only: bool_option("first_message_only"),
+                };
+
+                match crate::utils::autoresponder::parse_tag_trigger(db, guild_id, input).await {
+                    Ok(trigger) => {
+                        crate::utils::autoresponder::save_tag_trigger(db, guild_id, &trigger, interaction.user.id.get() as i64).await?;
+                        create_success_embed("Auto-Responder Added", crate::utils::autoresponder::describe_trigger(
+                            &trigger.tag_name,
+                            &trigger.pattern,
+                            trigger.is_regex,
+                            trigger.category_name.as_deref(),
+                            trigger.cooldown_seconds,
+                            trigger.first_message_only,
+                        ))
+                    }
+                    Err(e) => create_error_embed("Auto-Responder Not Added", e.to_string()),
+                }
+            }
+            "remove" => {
+                let number = sub_options.iter().find_map(|o| match o.value {
+                    ResolvedValue::Integer(value) if o.name == "number" => Some(value),
+                    _ => None,
+                }).unwrap_or(0);
+
+                let triggers = crate::database::tag::get_tag_triggers(&db.pool, guild_id).await?;
+                match usize::try_from(number).ok().and_then(|n| n.checked_sub(1)).and_then(|i| triggers.get(i)) {
+                    Some(trigger) => {
+                        crate::utils::autoresponder::remove_tag_trigger(db, guild_id, trigger.id).await?;
+                        create_success_embed("Auto-Responder Removed", format!("Rule #{} for tag `{}` has been removed", number, trigger.tag_name))
+                    }
+                    None => create_error_embed("Not Found", format!("There is no rule #{}. Use `/autorespond list` to see the rules.", number)),
+                }
+            }
+            "list" => {
+                let triggers = crate::database::tag::get_tag_triggers(&db.pool, guild_id).await?;
+                create_embed("Auto-Responder Rules", crate::utils::autoresponder::format_tag_triggers(&triggers))
+            }
+            _ => create_error_embed("Unknown Subcommand", "Use add, remove or list"),
+        }
+    } else {
+        create_error_embed("Unknown Subcommand", "Use add, remove or list")
+    };
+
+    interaction
+        .create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+            serenity::all::CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .ephemeral(true)
+        ))
+        .await?;
+
+    Ok(())
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("autorespond")
+        .description("Reply to ticket owners with a tag when their message matches a pattern")
+        .default_member_permissions(Permissions::ADMINISTRATOR)
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::SubCommand, "add", "Add an auto-responder rule")
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "tag", "Tag to reply with").required(true).set_autocomplete(true))
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "pattern", "Comma-separated keywords, or a regex").required(true))
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::Boolean, "regex", "Treat the pattern as a regex (default: keywords)").required(false))
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "cooldown", "Per-ticket cooldown such as 10m or 1h, or 0 for none (default: 10m)").required(false))
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "category", "Only fire in tickets of this category").required(false))
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::Boolean, "first_message_only", "Only fire on the owner's first message").required(false))
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::SubCommand, "remove", "Remove an auto-responder rule")
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::Integer, "number", "Rule number from /autorespond list").required(true).min_int_value(1))
+        )
+        .add_option(CreateCommandOption::new(CommandOptionType::SubCommand, "list", "List auto-responder rules"))
+}
@@ -0,0 +1,107 @@
+use serenity::all::{
+    CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
+    ResolvedValue,
+};
+use crate::database::Database;
+use crate::utils::{create_error_embed, create_success_embed};
+use anyhow::Result;
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database) -> Result<()> {
+    let guild_id = interaction.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?.get() as i64;
+    let user_id = interaction.user.id.get() as i64;
+
+    if !crate::utils::has_support_role_or_admin(ctx, interaction.user.id, guild_id, db).await? {
+        let embed = create_error_embed(
+            "Permission Denied",
+            "Only support staff can set an away status",
+        );
+
+        interaction
+            .create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+                serenity::all::CreateInteractionResponseMessage::new()
+                    .embed(embed)
+                    .ephemeral(true)
+            ))
+            .await?;
+        return Ok(());
+    }
+
+    let options = interaction.data.options();
+    let mut duration = None;
+    let mut reason = None;
+
+    for option in &options {
+        if let ResolvedValue::String(value) = option.value {
+            match option.name {
+                "duration" => duration = Some(value),
+                "reason" => reason = Some(value),
+                _ => {}
+            }
+        }
+    }
+
+    let away_until = match duration {
+        Some(duration) => match crate::utils::time::parse_duration(duration) {
+            Ok(d) => Some(chrono::Utc::now() + d),
+            Err(e) => {
+                let embed = create_error_embed("Invalid Duration", format!("{}\n\nExamples: `2h`, `3d`, `1w`", e));
+
+                interaction
+                    .create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+                        serenity::all::CreateInteractionResponseMessage::new()
+                            .embed(embed)
+                            .ephemeral(true)
+                    ))
+                    .await?;
+                return Ok(());
+            }
+        },
+        None => None,
+    };
+
+    crate::database::staff::set_away(&db.pool, guild_id, user_id, reason, away_until).await?;
+
+    let until_text = match away_until {
+        Some(until) => format!("until <t:{}:f>", until.timestamp()),
+        None => "until you use `/back`".to_string(),
+    };
+
+    let embed = create_success_embed(
+        "Away",
+        format!(
+            "You are now marked as away {}.\nYou won't receive escalation or handle DMs and can't be assigned tickets.",
+            until_text
+        ),
+    );
+
+    interaction
+        .create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+            serenity::all::CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .ephemeral(true)
+        ))
+        .await?;
+
+    Ok(())
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("away")
+        .description("Mark yourself as away so you don't receive ticket pings")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "duration",
+                "How long you'll be away (e.g. 2h, 3d, 1w). Leave empty to stay away until /back"
+            )
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "reason",
+                "Why you're away"
+            )
+            .required(false)
+        )
+}
@@ -0,0 +1,30 @@
+use serenity::all::{CommandInteraction, Context, CreateCommand};
+use crate::database::Database;
+use crate::utils::{create_error_embed, create_success_embed};
+use anyhow::Result;
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database) -> Result<()> {
+    let guild_id = interaction.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?.get() as i64;
+    let user_id = interaction.user.id.get() as i64;
+
+    let embed = if crate::database::staff::clear_away(&db.pool, guild_id, user_id).await? {
+        create_success_embed("Welcome Back", "You are now marked as available")
+    } else {
+        create_error_embed("Not Away", "You are not marked as away")
+    };
+
+    interaction
+        .create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+            serenity::all::CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .ephemeral(true)
+        ))
+        .await?;
+
+    Ok(())
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("back")
+        .description("Clear your away status")
+}
@@ -51,7 +51,7 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database)
                     });
 
                 let guild_id = interaction.guild_id.unwrap().get() as i64;
-                crate::database::ticket::create_ticket_category(
+                let category = crate::database::ticket::create_ticket_category(
                     &db.pool,
                     guild_id,
                     name.clone(),
@@ -59,6 +59,7 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database)
                     emoji,
                 )
                 .await?;
+                crate::database::ticket::add_category_to_panels(&db.pool, guild_id, category.id).await?;
 
                 let embed = create_success_embed(
                     "Category Created",
@@ -72,6 +73,8 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database)
                             .ephemeral(true)
                     ))
                     .await?;
+
+                crate::utils::panel::refresh_guild_panels(&ctx.http, db, guild_id).await;
             }
             "list" => {
                 let guild_id = interaction.guild_id.unwrap().get() as i64;
@@ -70,6 +70,7 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database)
         }
 
         crate::database::ticket::claim_ticket(&db.pool, ticket.id, claimer_id).await?;
+        let _ = crate::utils::sync_exclusive_claim(ctx, db, ticket.id, &[]).await;
 
         let _ = crate::database::ticket::deactivate_escalation(&db.pool, ticket.id).await;
 
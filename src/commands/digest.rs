@@ -0,0 +1,148 @@
+use serenity::all::{
+    CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
+    Permissions, ResolvedOption, ResolvedValue,
+};
+use crate::database::Database;
+use crate::utils::{create_embed, create_error_embed, create_success_embed};
+use anyhow::Result;
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database) -> Result<()> {
+    let options = &interaction.data.options();
+    let guild_id = interaction.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?.get() as i64;
+
+    let embed = if let Some(ResolvedOption {
+        value: ResolvedValue::SubCommand(sub_options),
+        name,
+        ..
+    }) = options.first()
+    {
+        match *name {
+            "set" => {
+                let string_option = |name: &str| sub_options.iter().find_map(|o| match o.value {
+                    ResolvedValue::String(value) if o.name == name => Some(value),
+                    _ => None,
+                });
+                let sla_hours = sub_options.iter().find_map(|o| match o.value {
+                    ResolvedValue::Integer(value) if o.name == "sla_hours" => Some(value as i32),
+                    _ => None,
+                });
+
+                match crate::utils::digest::parse_digest_schedule(
+                    guild_id,
+                    string_option("frequency").unwrap_or("daily"),
+                    string_option("weekday"),
+                    string_option("time").unwrap_or("09:00"),
+                    string_option("timezone"),
+                    sla_hours,
+                ) {
+                    Ok(digest) => {
+                        crate::database::digest::upsert_digest(&db.pool, &digest).await?;
+                        let guild = crate::database::ticket::get_or_create_guild(&db.pool, guild_id).await?;
+
+                        let mut description = format!(
+                            "{}\nDigests are posted to the log channel.",
+                            crate::utils::digest::describe_schedule(&digest)
+                        );
+                        if guild.log_channel_id.is_none() {
+                            description.push_str("\n\n**Warning:** no log channel is configured, so nothing will be posted until one is set.");
+                        }
+
+                        create_success_embed("Digest Scheduled", description)
+                    }
+                    Err(e) => create_error_embed("Invalid Schedule", e.to_string()),
+                }
+            }
+            "disable" => {
+                if crate::database::digest::set_digest_enabled(&db.pool, guild_id, false).await? {
+                    create_success_embed("Digest Disabled", "Scheduled digests will no longer be posted")
+                } else {
+                    create_error_embed("Not Configured", "No digest is configured for this server")
+                }
+            }
+            "show" => {
+                let digest = crate::database::digest::get_digest(&db.pool, guild_id).await?;
+                create_embed("Digest Settings", crate::utils::digest::format_digest_settings(digest.as_ref()))
+            }
+            "preview" => {
+                let digest = match crate::database::digest::get_digest(&db.pool, guild_id).await? {
+                    Some(digest) => digest,
+                    None => crate::utils::digest::parse_digest_schedule(guild_id, "daily", None, "09:00", None, None)?,
+                };
+                crate::utils::digest::build_digest_embed(db, &digest).await?
+            }
+            _ => create_error_embed("Unknown Subcommand", "Use set, disable, show or preview"),
+        }
+    } else {
+        create_error_embed("Unknown Subcommand", "Use set, disable, show or preview")
+    };
+
+    interaction
+        .create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+            serenity::all::CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .ephemeral(true)
+        ))
+        .await?;
+
+    Ok(())
+}
+
+pub fn register() -> CreateCommand {
+    let mut weekday_option = CreateCommandOption::new(
+        CommandOptionType::String,
+        "weekday",
+        "Day to post weekly digests (default: Monday)",
+    )
+    .required(false);
+
+    for day in ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"] {
+        weekday_option = weekday_option.add_string_choice(day, day.to_lowercase());
+    }
+
+    CreateCommand::new("digest")
+        .description("Configure the scheduled ticket digest")
+        .default_member_permissions(Permissions::ADMINISTRATOR)
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "set",
+                "Schedule a daily or weekly digest in the log channel",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::String, "frequency", "How often to post the digest")
+                    .add_string_choice("Daily", "daily")
+                    .add_string_choice("Weekly", "weekly")
+                    .required(true),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::String, "time", "Time to post in 24-hour HH:MM")
+                    .required(true),
+            )
+            .add_sub_option(weekday_option)
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::String, "timezone", "IANA timezone, e.g. Europe/London (default: UTC)")
+                    .required(false),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::Integer, "sla_hours", "First response target used for SLA breaches (default: 24)")
+                    .min_int_value(1)
+                    .max_int_value(720)
+                    .required(false),
+            ),
+        )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "disable",
+            "Stop posting the digest",
+        ))
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "show",
+            "Show the current digest settings",
+        ))
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "preview",
+            "Preview the digest with current data",
+        ))
+}
@@ -64,21 +64,30 @@ pub fn register() -> CreateCommand {
             .add_string_choice("claim", "claim")
             .add_string_choice("escalate", "escalate")
             .add_string_choice("handle", "handle")
+            .add_string_choice("away", "away")
+            .add_string_choice("merge", "merge")
+            .add_string_choice("stats", "stats")
+            .add_string_choice("staffstats", "staffstats")
+            .add_string_choice("export", "export")
+            .add_string_choice("digest", "digest")
+            .add_string_choice("autorespond", "autorespond")
             .add_string_choice("priority", "priority")
             .add_string_choice("note", "note")
             .add_string_choice("transcript", "transcript")
             .add_string_choice("reminder", "reminder")
+            .add_string_choice("followup", "followup")
+            .add_string_choice("timezone", "timezone")
             .add_string_choice("blacklist", "blacklist")
             .add_string_choice("unblacklist", "unblacklist")
         )
 }
 
-struct CommandDoc {
-    name: String,
-    description: String,
-    usage: String,
-    examples: String,
-    permissions: String,
+pub struct CommandDoc {
+    pub name: String,
+    pub description: String,
+    pub usage: String,
+    pub examples: String,
+    pub permissions: String,
 }
 
 fn get_command_docs() -> HashMap<String, CommandDoc> {
@@ -110,7 +119,7 @@ fn get_command_docs() -> HashMap<String, CommandDoc> {
         "panel".to_string(),
         CommandDoc {
             name: "panel".to_string(),
-            description: "Create a ticket panel in the current channel. Users can click buttons to open tickets for different categories.".to_string(),
+            description: "Create a ticket panel in the current channel. Users can click buttons to open tickets for different categories. Posted panels update in place when categories or panel settings change.".to_string(),
             usage: "`/panel` or `!panel`".to_string(),
             examples: "`/panel` - Creates a panel with all configured ticket categories".to_string(),
             permissions: "Administrator".to_string(),
@@ -143,7 +152,7 @@ fn get_command_docs() -> HashMap<String, CommandDoc> {
         "escalate".to_string(),
         CommandDoc {
             name: "escalate".to_string(),
-            description: "Escalate a ticket to notify all support staff. Only works if no support messages have been sent yet. Sends hourly DM reminders until claimed or closed.".to_string(),
+            description: "Escalate a ticket to notify all support staff. Only works if no support messages have been sent yet. Sends hourly DM reminders until claimed or closed. If escalation tiers are configured (`escalation add`), each tier notifies its role after its delay and the current tier is posted in the ticket channel.".to_string(),
             usage: "`/escalate` or `!escalate`".to_string(),
             examples: "`/escalate` - Start hourly reminders to all support staff\n`!escalate` - Prefix version".to_string(),
             permissions: "Ticket owner".to_string(),
@@ -161,6 +170,83 @@ fn get_command_docs() -> HashMap<String, CommandDoc> {
         },
     );
 
+    docs.insert(
+        "away".to_string(),
+        CommandDoc {
+            name: "away".to_string(),
+            description: "Mark yourself as away. Away staff are skipped by escalation reminders, handle notifications and ticket assignment. Use `back` to clear the status early, or `staff` to see who is available.".to_string(),
+            usage: "`/away [duration] [reason]` or `!away [duration] [reason]`\n`/back` or `!back`\n`/staff` or `!staff`".to_string(),
+            examples: "`/away duration: 3d reason: Vacation` - Away for three days\n`!away 8h Sleeping` - Away for eight hours\n`!away` - Away until `!back`\n`!staff` - List available and away staff".to_string(),
+            permissions: "Support role".to_string(),
+        },
+    );
+
+    docs.insert(
+        "merge".to_string(),
+        CommandDoc {
+            name: "merge".to_string(),
+            description: "Merge a duplicate ticket into another one. Messages and notes move to the target ticket, a summary is posted there, and this ticket is closed without its own transcript. The target's transcript will include both tickets.".to_string(),
+            usage: "`/merge <into>` or `!merge <#channel>`".to_string(),
+            examples: "`/merge into: #ticket-0042` - Merge the current ticket into #ticket-0042\n`!merge #ticket-0042` - Prefix version".to_string(),
+            permissions: "Support role".to_string(),
+        },
+    );
+
+    docs.insert(
+        "stats".to_string(),
+        CommandDoc {
+            name: "stats".to_string(),
+            description: "View ticket statistics. Without a period it shows all-time totals. With a period it shows opened and closed tickets per day, a split by category and priority, the busiest hours of the day and a comparison with the previous period of the same length. With `chart` it also attaches a PNG with a line chart of opened and closed tickets per day and a bar chart per category.".to_string(),
+            usage: "`/stats [period] [from] [to] [chart]` or `!stats [chart] [24h|7d|30d|<YYYY-MM-DD> [YYYY-MM-DD]]`".to_string(),
+            examples: "`/stats period: Last 7 days` - Weekly report\n`/stats period: Custom range from: 2025-01-01 to: 2025-01-31` - January report\n`!stats 30d` - Last 30 days\n`!stats 2025-01-01 2025-01-31` - Custom range\n`/stats period: Last 7 days chart: True` - Weekly report with a chart\n`!stats chart 30d` - Chart for the last 30 days".to_string(),
+            permissions: "Everyone".to_string(),
+        },
+    );
+
+    docs.insert(
+        "staffstats".to_string(),
+        CommandDoc {
+            name: "staffstats".to_string(),
+            description: "Show a staff member's tickets claimed, assigned and closed, messages sent in tickets, average first response time and notes written over a period. Without a member it shows a leaderboard of all support staff, sortable by any of those metrics. Defaults to the last 30 days.".to_string(),
+            usage: "`/staffstats [member] [period] [sort]` or `!staffstats [@member] [period] [sort]`".to_string(),
+            examples: "`/staffstats member: @Support1 period: Last 7 days` - Weekly report for @Support1\n`!staffstats messages 30d` - Leaderboard by messages sent\n`!staffstats @Support1 2025-01-01 2025-01-31` - Custom range".to_string(),
+            permissions: "Support role".to_string(),
+        },
+    );
+
+    docs.insert(
+        "export".to_string(),
+        CommandDoc {
+            name: "export".to_string(),
+            description: "Download the tickets created in a date range as a CSV or JSON file. Each row has the ticket number, owner, category, priority, claimer, assignee, created and closed timestamps, rating and message count.".to_string(),
+            usage: "`/export [format] [period] [from] [to]`".to_string(),
+            examples: "`/export format: CSV period: Last 30 days` - Last month as CSV\n`/export format: JSON period: Custom range from: 2025-01-01 to: 2025-03-31` - First quarter as JSON".to_string(),
+            permissions: "Administrator".to_string(),
+        },
+    );
+
+    docs.insert(
+        "autorespond".to_string(),
+        CommandDoc {
+            name: "autorespond".to_string(),
+            description: "Reply automatically with a tag when a ticket owner's message matches a rule. Patterns are comma-separated keywords matched as whole words, or a regex. Each rule has a per-ticket cooldown (10 minutes by default), can be limited to one ticket category, and can fire only on the owner's first message. At most one rule fires per message.".to_string(),
+            usage: "`/autorespond add|remove|list` or `!autorespond add <tag> <pattern> [--regex] [--first] [--cooldown <time>] [--category <name>]`, `!autorespond remove <number>`, `!autorespond list`".to_string(),
+            examples: "`!autorespond add refund refund, money back --cooldown 1h --category Billing` - Reply with the refund tag\n`/autorespond add tag: order pattern: order\\s*#?\\d+ regex: True first_message_only: True` - Regex rule for the first message only\n`!autorespond remove 2` - Remove rule #2".to_string(),
+            permissions: "Administrator".to_string(),
+        },
+    );
+
+    docs.insert(
+        "digest".to_string(),
+        CommandDoc {
+            name: "digest".to_string(),
+            description: "Post a daily or weekly digest to the log channel at a set time in the server's timezone. It covers tickets opened and closed compared with the previous period, the current backlog, the oldest unclaimed tickets, the top staff by tickets closed and the number of tickets that missed the first response SLA.".to_string(),
+            usage: "`/digest set|disable|show|preview` or `!digest set <daily|weekly> [weekday] <HH:MM> [timezone] [sla hours]`".to_string(),
+            examples: "`/digest set frequency: Weekly time: 09:00 weekday: Monday timezone: Europe/London` - Weekly Monday digest\n`!digest set daily 18:00 America/New_York 12` - Daily digest with a 12 hour SLA\n`!digest preview` - Preview with current data\n`!digest off` - Stop posting".to_string(),
+            permissions: "Administrator".to_string(),
+        },
+    );
+
     docs.insert(
         "priority".to_string(),
         CommandDoc {
@@ -209,12 +295,38 @@ fn get_command_docs() -> HashMap<String, CommandDoc> {
         "reminder".to_string(),
         CommandDoc {
             name: "reminder".to_string(),
-            description: "Set a timed reminder for yourself. Bot will ping you and send a DM when the time is up.".to_string(),
-            usage: "`!reminder <time> <reason>` or `!remind <time> <reason>`".to_string(),
+            description: "Set a timed reminder for yourself. Bot will ping you and send a DM when the time is up. The time can be a duration (`1h30m`), a time (`14:00`, `9am`) or a date (`tomorrow`, `friday`, `2026-11-01`) with an optional time. Reminders can also repeat on a schedule, optionally ending on a date (`--until`) or after a number of reminders (`--count`). Times are read in your timezone (see `timezone`), UTC if you haven't set one. The reminder DM has buttons to snooze it for 10 minutes or an hour. If the DM can't be sent it is retried a few times with increasing delays, and `reminder list` shows how your recent reminders were delivered.".to_string(),
+            usage: "`!reminder <when> <reason>` or `!remind <when> <reason>`\n\
+                `!reminder every <interval|day|weekday> [at <time>] [--until YYYY-MM-DD] [--count N] <reason>`\n\
+                `!reminder list`, `!reminder cancel <id>`, `!reminder snooze <id> [duration]`\n\
+                `!reminder edit <id> time <when>` or `!reminder edit <id> reason <text>`\n\
+                `/reminder set|list|cancel|edit|snooze`".to_string(),
             examples: "`!reminder 30m Check ticket status` - Remind in 30 minutes\n\
-                `!remind 2h Close ticket` - Remind in 2 hours\n\
-                `!remindme 1d Follow up with user` - Remind in 1 day\n\n\
-                Time formats: s (seconds), m (minutes), h (hours), d (days), w (weeks)".to_string(),
+                `!remind 1h30m Close ticket` - Remind in an hour and a half\n\
+                `!remindme tomorrow 9am Follow up with user` - Tomorrow at 09:00\n\
+                `!reminder 2026-11-01 14:00 Renew the license` - On a date and time\n\
+                `!reminder every day at 09:00 Check the queue` - Every day at 09:00\n\
+                `!reminder every monday 9am --count 4 Weekly report` - The next four Mondays\n\
+                `!reminder every 2h --until 2026-12-31 Review escalations` - Every 2 hours until the end of the year\n\
+                `!reminder snooze 1a2b3c4d 1h` - Push a reminder back an hour\n\
+                `/reminder edit reminder: Renew the license when: friday 10am` - Move a reminder\n\n\
+                Durations combine s (seconds), m (minutes), h (hours), d (days) and w (weeks)".to_string(),
+            permissions: "Everyone".to_string(),
+        },
+    );
+
+    docs.insert("followup".to_string(), followup_doc());
+
+    docs.insert(
+        "timezone".to_string(),
+        CommandDoc {
+            name: "timezone".to_string(),
+            description: "Set the timezone used to read dates and times in your reminders, such as `tomorrow 9am` or `every day at 09:00`. Without one, times are UTC. The setting is yours and follows you across servers.".to_string(),
+            usage: "`/timezone [zone]` or `!timezone [zone|reset]`".to_string(),
+            examples: "`/timezone zone: Europe/London` - Set your timezone\n\
+                `!timezone America/New_York` - Prefix version\n\
+                `!tz` - Show your timezone and local time\n\
+                `!timezone reset` - Go back to UTC".to_string(),
             permissions: "Everyone".to_string(),
         },
     );
@@ -232,3 +344,16 @@ fn get_command_docs() -> HashMap<String, CommandDoc> {
 
     docs
 }
+
+/// Shared with the prefix `doc` command so both describe follow-ups the same way.
+pub fn followup_doc() -> CommandDoc {
+    CommandDoc {
+        name: "followup".to_string(),
+        description: "Schedule a follow-up in the current ticket. When it's due, the bot posts the message in the ticket and pings the owner, the claimer or both. By default it's cancelled if the owner replies first; pass `--always` (or `cancel_on_reply: False`) to send it regardless. Pending follow-ups are removed when the ticket closes and show up in `reminder list`, where they can be edited or cancelled.".to_string(),
+        usage: "`/followup <when> [message] [mention] [cancel_on_reply]` or `!followup <when> [--mention owner|claimer|both] [--always] [message]`".to_string(),
+        examples: "`!followup 2d` - Check in with the owner in 2 days unless they reply\n\
+            `!followup tomorrow 10am --mention both Did the fix work?` - Ping owner and claimer\n\
+            `/followup when: 1w cancel_on_reply: False` - Always post in a week".to_string(),
+        permissions: "Support role".to_string(),
+    }
+}
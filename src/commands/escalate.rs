@@ -36,13 +36,14 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database)
         let support_roles = crate::database::ticket::get_support_roles(&db.pool, ticket.guild_id).await?;
 
         let guild_id = interaction.guild_id.ok_or_else(|| anyhow::anyhow!("Not in guild"))?;
+        let away_staff = crate::database::staff::get_away_user_ids(&db.pool, ticket.guild_id).await?;
 
         for role in &support_roles {
             let members = guild_id.members(&ctx.http, None, None).await?;
             let role_id = serenity::all::RoleId::new(role.role_id as u64);
 
             for member in members {
-                if member.roles.contains(&role_id) && !member.user.bot {
+                if member.roles.contains(&role_id) && !member.user.bot && !away_staff.contains(&(member.user.id.get() as i64)) {
                     let dm = member.user.create_dm_channel(&ctx.http).await?;
                     let _ = dm.send_message(
                         &ctx.http,
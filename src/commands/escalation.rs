@@ -0,0 +1,150 @@
+use serenity::all::{
+    CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
+    Permissions, ResolvedOption, ResolvedValue,
+};
+use crate::database::Database;
+use crate::utils::{create_embed, create_error_embed, create_success_embed};
+use anyhow::Result;
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database) -> Result<()> {
+    let options = &interaction.data.options();
+    let guild_id = interaction.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?.get() as i64;
+
+    let embed = if let Some(ResolvedOption {
+        value: ResolvedValue::SubCommand(sub_options),
+        name,
+        ..
+    }) = options.first()
+    {
+        match *name {
+            "add" => {
+                let mut tier = None;
+                let mut role_id = None;
+                let mut delay = None;
+                let mut channel_id = None;
+
+                for option in sub_options {
+                    match (option.name, &option.value) {
+                        ("tier", ResolvedValue::Integer(value)) => tier = Some(*value as i32),
+                        ("role", ResolvedValue::Role(role)) => role_id = Some(role.id.get() as i64),
+                        ("delay", ResolvedValue::String(value)) => delay = Some(*value),
+                        ("channel", ResolvedValue::Channel(channel)) => channel_id = Some(channel.id.get() as i64),
+                        _ => {}
+                    }
+                }
+
+                match (tier, role_id, delay) {
+                    (Some(tier), Some(role_id), Some(delay)) => {
+                        match crate::utils::time::parse_duration(delay) {
+                            Ok(duration) if duration.num_minutes() >= 1 => match i32::try_from(duration.num_minutes()) {
+                                Ok(delay_minutes) => {
+                                    crate::database::ticket::upsert_escalation_tier(
+                                        &db.pool,
+                                        guild_id,
+                                        tier,
+                                        role_id,
+                                        delay_minutes,
+                                        channel_id,
+                                    ).await?;
+
+                                    create_success_embed(
+                                        "Escalation Tier Saved",
+                                        format!(
+                                            "**Tier {}** will notify <@&{}> via {} once an escalated ticket has been unclaimed for **{}**",
+                                            tier,
+                                            role_id,
+                                            channel_id.map(|c| format!("<#{}>", c)).unwrap_or_else(|| "DM".to_string()),
+                                            crate::utils::format_minutes(delay_minutes as i64)
+                                        ),
+                                    )
+                                }
+                                Err(_) => create_error_embed("Invalid Delay", "That delay is too long"),
+                            },
+                            Ok(_) => create_error_embed("Invalid Delay", "The delay must be at least one minute"),
+                            Err(e) => create_error_embed("Invalid Delay", format!("{}\n\nExamples: `30m`, `2h`, `1d`", e)),
+                        }
+                    }
+                    _ => create_error_embed("Missing Options", "Please provide a tier, role and delay"),
+                }
+            }
+            "remove" => {
+                let tier = sub_options.iter().find_map(|o| match o.value {
+                    ResolvedValue::Integer(value) if o.name == "tier" => Some(value as i32),
+                    _ => None,
+                });
+
+                match tier {
+                    Some(tier) if crate::database::ticket::remove_escalation_tier(&db.pool, guild_id, tier).await? => {
+                        create_success_embed("Escalation Tier Removed", format!("Tier {} has been removed", tier))
+                    }
+                    Some(tier) => create_error_embed("Not Found", format!("Tier {} is not configured", tier)),
+                    None => create_error_embed("Missing Tier", "Please provide a tier number"),
+                }
+            }
+            "list" => {
+                let tiers = crate::database::ticket::get_escalation_tiers(&db.pool, guild_id).await?;
+                create_embed("Escalation Tiers", crate::utils::format_escalation_tiers(&tiers))
+            }
+            _ => create_error_embed("Unknown Subcommand", "Use add, remove or list"),
+        }
+    } else {
+        create_error_embed("Unknown Subcommand", "Use add, remove or list")
+    };
+
+    interaction
+        .create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+            serenity::all::CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .ephemeral(true)
+        ))
+        .await?;
+
+    Ok(())
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("escalation")
+        .description("Configure escalation tiers")
+        .default_member_permissions(Permissions::ADMINISTRATOR)
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "add",
+                "Add or update an escalation tier",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::Integer, "tier", "Tier number (1 is the first tier)")
+                    .min_int_value(1)
+                    .max_int_value(10)
+                    .required(true),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::Role, "role", "The role to notify at this tier")
+                    .required(true),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::String, "delay", "Time after escalation before this tier fires (e.g. 30m, 2h)")
+                    .required(true),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::Channel, "channel", "Post to this channel instead of DMing role members")
+                    .required(false),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                "remove",
+                "Remove an escalation tier",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::Integer, "tier", "Tier number to remove")
+                    .required(true),
+            ),
+        )
+        .add_option(CreateCommandOption::new(
+            CommandOptionType::SubCommand,
+            "list",
+            "List configured escalation tiers",
+        ))
+}
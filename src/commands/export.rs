@@ -0,0 +1,134 @@
+use serenity::all::{
+    CommandInteraction, CommandOptionType, Context, CreateAttachment, CreateCommand,
+    CreateCommandOption, CreateInteractionResponseFollowup, Permissions, ResolvedValue,
+};
+use crate::database::Database;
+use crate::utils::export::ExportFormat;
+use crate::utils::{create_error_embed, create_success_embed};
+use anyhow::Result;
+
+// Discord's upload limit for servers without boosts.
+const MAX_UPLOAD_BYTES: u64 = 10 * 1024 * 1024;
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database) -> Result<()> {
+    let guild_id = interaction.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?.get() as i64;
+
+    let options = interaction.data.options();
+    let string_option = |name: &str| options.iter().find_map(|o| match o.value {
+        ResolvedValue::String(value) if o.name == name => Some(value),
+        _ => None,
+    });
+
+    let format = string_option("format").and_then(ExportFormat::parse).unwrap_or(ExportFormat::Csv);
+    let period = string_option("period").unwrap_or("30d");
+
+    let period = match crate::utils::stats::parse_stats_period(period, string_option("from"), string_option("to")) {
+        Ok(period) => period,
+        Err(e) => {
+            let embed = create_error_embed("Invalid Period", e.to_string());
+
+            interaction
+                .create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+                    serenity::all::CreateInteractionResponseMessage::new()
+                        .embed(embed)
+                        .ephemeral(true)
+                ))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    interaction.defer_ephemeral(&ctx.http).await?;
+
+    let (filepath, count) = crate::utils::export::write_ticket_export(db, guild_id, &period, format).await?;
+
+    let result = send_export(ctx, interaction, &filepath, count, &period, format).await;
+    let _ = crate::utils::export::delete_export(&filepath).await;
+    result
+}
+
+async fn send_export(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    filepath: &str,
+    count: u64,
+    period: &crate::utils::stats::StatsPeriod,
+    format: ExportFormat,
+) -> Result<()> {
+    let size = std::fs::metadata(filepath).map(|m| m.len()).unwrap_or(0);
+
+    let followup = if size > MAX_UPLOAD_BYTES {
+        CreateInteractionResponseFollowup::new()
+            .embed(create_error_embed(
+                "Export Too Large",
+                format!(
+                    "The export contains {} tickets and is too large to upload. Try a shorter date range.",
+                    count
+                ),
+            ))
+            .ephemeral(true)
+    } else {
+        let file = CreateAttachment::path(filepath).await?;
+
+        CreateInteractionResponseFollowup::new()
+            .embed(create_success_embed(
+                "Ticket Export",
+                format!(
+                    "**Period:** {}\n**Tickets:** {}\n**Format:** {}",
+                    period.label,
+                    count,
+                    if format == ExportFormat::Csv { "CSV" } else { "JSON" }
+                ),
+            ))
+            .add_file(file)
+            .ephemeral(true)
+    };
+
+    interaction.create_followup(&ctx.http, followup).await?;
+
+    Ok(())
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("export")
+        .description("Export ticket data as a CSV or JSON file")
+        .default_member_permissions(Permissions::ADMINISTRATOR)
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "format",
+                "File format (default: CSV)"
+            )
+            .required(false)
+            .add_string_choice("CSV", "csv")
+            .add_string_choice("JSON", "json")
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "period",
+                "Tickets created in this window (default: last 30 days)"
+            )
+            .required(false)
+            .add_string_choice("Last 24 hours", "24h")
+            .add_string_choice("Last 7 days", "7d")
+            .add_string_choice("Last 30 days", "30d")
+            .add_string_choice("Custom range", "custom")
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "from",
+                "Start date for a custom range (YYYY-MM-DD)"
+            )
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "to",
+                "End date for a custom range, inclusive (YYYY-MM-DD, defaults to today)"
+            )
+            .required(false)
+        )
+}
@@ -0,0 +1,96 @@
+use serenity::all::{
+    CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
+    ResolvedValue,
+};
+use crate::database::Database;
+use crate::utils::create_error_embed;
+use crate::utils::reminder::{FollowUp, FollowUpMention, DEFAULT_FOLLOWUP_MESSAGE};
+use anyhow::Result;
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database) -> Result<()> {
+    let ticket = crate::database::ticket::get_ticket_by_channel(&db.pool, interaction.channel_id.get() as i64).await?;
+
+    let embed = match ticket {
+        None => create_error_embed("Not a Ticket", "This command can only be used in ticket channels"),
+        Some(ticket) if !crate::utils::has_support_role_or_admin(ctx, interaction.user.id, ticket.guild_id, db).await? => {
+            create_error_embed("Permission Denied", "Only support staff can set follow-ups")
+        }
+        Some(ticket) => {
+            let options = interaction.data.options();
+            let string_option = |name: &str| options.iter().find_map(|o| match o.value {
+                ResolvedValue::String(value) if o.name == name => Some(value),
+                _ => None,
+            });
+            let cancel_on_reply = options.iter().find_map(|o| match o.value {
+                ResolvedValue::Boolean(value) if o.name == "cancel_on_reply" => Some(value),
+                _ => None,
+            });
+
+            let when: Vec<&str> = string_option("when").unwrap_or_default().split_whitespace().collect();
+            let tz = crate::utils::time::user_timezone(db, interaction.user.id.get() as i64).await?;
+
+            match crate::utils::time::parse_when(&when, chrono::Utc::now(), tz) {
+                Ok((remind_at, _)) => {
+                    let followup = FollowUp {
+                        remind_at,
+                        mention: string_option("mention").and_then(FollowUpMention::parse).unwrap_or(FollowUpMention::Owner),
+                        cancel_on_reply: cancel_on_reply.unwrap_or(true),
+                        message: string_option("message").unwrap_or(DEFAULT_FOLLOWUP_MESSAGE).to_string(),
+                    };
+                    crate::utils::reminder::create_followup(db, &ticket, interaction.user.id.get() as i64, &followup).await?
+                }
+                Err(e) => create_error_embed("Invalid Time Format", e.to_string()),
+            }
+        }
+    };
+
+    interaction
+        .create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+            serenity::all::CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .ephemeral(true)
+        ))
+        .await?;
+
+    Ok(())
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("followup")
+        .description("Ping this ticket later if the user hasn't replied")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "when",
+                "When to follow up (e.g. 2d, tomorrow 10am)"
+            )
+            .required(true)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "message",
+                "What to post (defaults to a short check-in)"
+            )
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "mention",
+                "Who to ping (default: the ticket owner)"
+            )
+            .required(false)
+            .add_string_choice("Ticket owner", "owner")
+            .add_string_choice("Claimer", "claimer")
+            .add_string_choice("Both", "both")
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                "cancel_on_reply",
+                "Cancel if the owner replies first (default: true)"
+            )
+            .required(false)
+        )
+}
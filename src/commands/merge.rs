@@ -0,0 +1,77 @@
+use serenity::all::{
+    CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
+    ResolvedValue,
+};
+use crate::database::Database;
+use crate::utils::{create_error_embed, create_success_embed};
+use anyhow::Result;
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database) -> Result<()> {
+    let guild_id = interaction.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?.get() as i64;
+
+    let target_channel_id = interaction.data.options().iter().find_map(|o| match &o.value {
+        ResolvedValue::Channel(channel) if o.name == "into" => Some(channel.id.get() as i64),
+        _ => None,
+    });
+
+    let source = crate::database::ticket::get_ticket_by_channel(&db.pool, interaction.channel_id.get() as i64).await?;
+    let target = match target_channel_id {
+        Some(channel_id) => crate::database::ticket::get_ticket_by_channel(&db.pool, channel_id).await?,
+        None => None,
+    };
+
+    let error = if !crate::utils::has_support_role_or_admin(ctx, interaction.user.id, guild_id, db).await? {
+        Some(create_error_embed("Permission Denied", "Only support staff can merge tickets"))
+    } else if source.is_none() {
+        Some(create_error_embed("Error", "Run this command in the ticket you want to merge away"))
+    } else if target.as_ref().is_none_or(|t| t.guild_id != guild_id) {
+        Some(create_error_embed("Invalid Target", "The target channel is not an open ticket in this server"))
+    } else if target.as_ref().map(|t| t.id) == source.as_ref().map(|s| s.id) {
+        Some(create_error_embed("Invalid Target", "A ticket cannot be merged into itself"))
+    } else {
+        None
+    };
+
+    if let Some(embed) = error {
+        interaction
+            .create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+                serenity::all::CreateInteractionResponseMessage::new()
+                    .embed(embed)
+                    .ephemeral(true)
+            ))
+            .await?;
+        return Ok(());
+    }
+
+    let (source, target) = (source.unwrap(), target.unwrap());
+
+    let embed = create_success_embed(
+        "Merging Ticket",
+        format!("Merging ticket #{} into <#{}>", source.ticket_number, target.channel_id),
+    );
+
+    interaction
+        .create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+            serenity::all::CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .ephemeral(true)
+        ))
+        .await?;
+
+    crate::utils::merge_tickets(ctx, db, source, target, interaction.user.id.get()).await?;
+
+    Ok(())
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("merge")
+        .description("Merge this ticket into another ticket")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Channel,
+                "into",
+                "The ticket channel to merge this ticket into"
+            )
+            .required(true)
+        )
+}
@@ -14,3 +14,15 @@ pub mod doc;
 pub mod tag;
 pub mod assign;
 pub mod channelname;
+pub mod away;
+pub mod back;
+pub mod staff;
+pub mod escalation;
+pub mod merge;
+pub mod staffstats;
+pub mod export;
+pub mod digest;
+pub mod autorespond;
+pub mod timezone;
+pub mod reminder;
+pub mod followup;
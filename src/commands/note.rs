@@ -45,6 +45,8 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database)
                     .execute(&db.pool)
                     .await?;
 
+                    let _ = crate::database::stats::record_staff_activity(&db.pool, ticket.guild_id, author_id, 0, 1).await;
+
                     if let Some(opening_msg_id) = sqlx::query_as::<_, (Option<i64>,)>(
                         "SELECT opening_message_id FROM tickets WHERE id = $1"
                     )
@@ -395,7 +395,7 @@ async fn create_panel(
             _ => ButtonStyle::Primary,
         };
 
-        let mut button = CreateButton::new(format!("ticket_create:{}", cat_id))
+        let mut button = CreateButton::new(format!("ticket_create_cat_{}", cat_id))
             .label(name)
             .style(button_style);
 
@@ -417,7 +417,7 @@ async fn create_panel(
         .send_message(&ctx.http, CreateMessage::new().embed(embed).components(components))
         .await?;
 
-    crate::database::ticket::create_ticket_panel(
+    let panel = crate::database::ticket::create_ticket_panel(
         &db.pool,
         guild_id,
         channel.get() as i64,
@@ -427,6 +427,21 @@ async fn create_panel(
     )
     .await?;
 
+    // This panel shows every category, so it tracks them and picks up new ones.
+    crate::database::ticket::set_panel_all_categories(&db.pool, panel.id).await?;
+    for (i, (cat_id, name, emoji)) in categories.iter().enumerate() {
+        let color = custom_colors.as_ref().and_then(|c| c.get(&i)).unwrap_or(&default_color);
+        crate::database::ticket::add_panel_category(
+            &db.pool,
+            panel.id,
+            uuid::Uuid::parse_str(cat_id)?,
+            name,
+            emoji.as_deref(),
+            crate::utils::panel::normalize_button_color(color),
+        )
+        .await?;
+    }
+
     let response_embed = create_success_embed(
         "Panel Created",
         "Ticket panel has been created successfully",
@@ -0,0 +1,225 @@
+use serenity::all::{
+    CommandInteraction, CommandOptionType, ComponentInteraction, Context, CreateAutocompleteResponse,
+    CreateCommand, CreateCommandOption, CreateInteractionResponse, CreateInteractionResponseMessage,
+    ResolvedOption, ResolvedValue,
+};
+use crate::database::Database;
+use crate::utils::reminder::short_id;
+use crate::utils::{create_embed, create_error_embed, create_success_embed};
+use anyhow::Result;
+use chrono::Utc;
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database) -> Result<()> {
+    let options = &interaction.data.options();
+    let user_id = interaction.user.id.get() as i64;
+
+    let embed = if let Some(ResolvedOption {
+        value: ResolvedValue::SubCommand(sub_options),
+        name,
+        ..
+    }) = options.first()
+    {
+        let string_option = |name: &str| sub_options.iter().find_map(|o| match o.value {
+            ResolvedValue::String(value) if o.name == name => Some(value),
+            _ => None,
+        });
+        let reminders = crate::database::ticket::get_user_reminders(&db.pool, user_id).await?;
+
+        match *name {
+            "set" => {
+                let when: Vec<&str> = string_option("when").unwrap_or_default().split_whitespace().collect();
+                let reason = string_option("reason").unwrap_or_default();
+                let tz = crate::utils::time::user_timezone(db, user_id).await?;
+
+                match crate::utils::time::parse_when(&when, Utc::now(), tz) {
+                    Ok((at, _)) => {
+                        let reminder = crate::database::ticket::create_reminder(
+                            &db.pool,
+                            user_id,
+                            interaction.channel_id.get() as i64,
+                            interaction.guild_id.map(|g| g.get() as i64),
+                            None,
+                            reason.to_string(),
+                            at,
+                        )
+                        .await?;
+                        create_embed(
+                            "Reminder Set",
+                            format!("**Reason:** {}\n**Remind at:** <t:{}:F> (<t:{}:R>)", reason, at.timestamp(), at.timestamp()),
+                        )
+                        .footer(serenity::all::CreateEmbedFooter::new(format!("Reminder ID: {}", short_id(reminder.id))))
+                    }
+                    Err(e) => create_error_embed("Invalid Time Format", e.to_string()),
+                }
+            }
+            "list" => {
+                let recent = crate::database::ticket::get_recent_delivered_reminders(&db.pool, user_id, 5).await?;
+                let embed = create_embed("Your Reminders", crate::utils::reminder::format_reminder_list(&reminders));
+                match crate::utils::reminder::format_recent_deliveries(&recent) {
+                    Some(recent) => embed.field("Recently Delivered", recent, false),
+                    None => embed,
+                }
+            }
+            "cancel" => match crate::utils::reminder::find_reminder(&reminders, string_option("reminder").unwrap_or_default()) {
+                Ok(reminder) => {
+                    crate::database::ticket::delete_reminder(&db.pool, reminder.id).await?;
+                    create_success_embed("Reminder Cancelled", format!("Cancelled `{}`: {}", short_id(reminder.id), reminder.reason))
+                }
+                Err(e) => create_error_embed("Reminder Not Found", e.to_string()),
+            },
+            "snooze" => match crate::utils::reminder::find_reminder(&reminders, string_option("reminder").unwrap_or_default()) {
+                Ok(reminder) => match crate::utils::time::parse_duration(string_option("duration").unwrap_or("10m"))
+                    .and_then(|by| crate::utils::reminder::snoozed_until(reminder, by, Utc::now()))
+                {
+                    Ok(until) => {
+                        crate::database::ticket::update_reminder(&db.pool, reminder.id, None, Some(until)).await?;
+                        create_success_embed(
+                            "Reminder Snoozed",
+                            format!("`{}` will now go off <t:{}:F> (<t:{}:R>)", short_id(reminder.id), until.timestamp(), until.timestamp()),
+                        )
+                    }
+                    Err(e) => create_error_embed("Invalid Time Format", e.to_string()),
+                },
+                Err(e) => create_error_embed("Reminder Not Found", e.to_string()),
+            },
+            "edit" => match crate::utils::reminder::find_reminder(&reminders, string_option("reminder").unwrap_or_default()) {
+                Ok(reminder) => {
+                    let tz = crate::utils::time::user_timezone(db, user_id).await?;
+                    let when = string_option("when")
+                        .map(|when| crate::utils::time::parse_when(&when.split_whitespace().collect::<Vec<_>>(), Utc::now(), tz))
+                        .transpose();
+                    let reason = string_option("reason");
+
+                    match when {
+                        Ok(None) if reason.is_none() => create_error_embed("Nothing to Change", "Give a new `when`, a new `reason`, or both"),
+                        Ok(when) => {
+                            let at = when.map(|(at, _)| at);
+                            crate::database::ticket::update_reminder(&db.pool, reminder.id, reason, at).await?;
+                            create_success_embed(
+                                "Reminder Updated",
+                                format!(
+                                    "**Reason:** {}\n**Remind at:** <t:{}:F>",
+                                    reason.unwrap_or(&reminder.reason),
+                                    at.unwrap_or(reminder.remind_at).timestamp()
+                                ),
+                            )
+                            .footer(serenity::all::CreateEmbedFooter::new(format!("Reminder ID: {}", short_id(reminder.id))))
+                        }
+                        Err(e) => create_error_embed("Invalid Time Format", e.to_string()),
+                    }
+                }
+                Err(e) => create_error_embed("Reminder Not Found", e.to_string()),
+            },
+            _ => create_error_embed("Unknown Subcommand", "Use one of set, list, cancel, edit or snooze"),
+        }
+    } else {
+        create_error_embed("Unknown Subcommand", "Use one of set, list, cancel, edit or snooze")
+    };
+
+    interaction
+        .create_response(&ctx.http, CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .ephemeral(true)
+        ))
+        .await?;
+
+    Ok(())
+}
+
+/// Suggests the user's pending reminders, matching on reason or ID.
+pub async fn autocomplete(ctx: &Context, interaction: &CommandInteraction, db: &Database) -> Result<()> {
+    let Some(focused) = interaction.data.autocomplete() else {
+        return Ok(());
+    };
+    let query = focused.value.to_lowercase();
+
+    let user_id = interaction.user.id.get() as i64;
+    let reminders = crate::database::ticket::get_user_reminders(&db.pool, user_id).await?;
+    let tz = crate::utils::time::user_timezone(db, user_id).await?;
+    let response = reminders.iter()
+        .filter(|r| r.reason.to_lowercase().contains(&query) || r.id.to_string().starts_with(&query))
+        .take(25)
+        .fold(CreateAutocompleteResponse::new(), |response, r| {
+            let when = r.remind_at.with_timezone(&tz).format("%Y-%m-%d %H:%M %Z");
+            let label: String = format!("{} - {}", when, r.reason).chars().take(100).collect();
+            response.add_string_choice(label, r.id.to_string())
+        });
+
+    interaction.create_response(&ctx.http, CreateInteractionResponse::Autocomplete(response)).await?;
+    Ok(())
+}
+
+/// Snooze and Done buttons on a delivered reminder DM. Snoozing sets up a one-off
+/// copy of the reminder so a repeating reminder's own schedule is left alone.
+pub async fn handle_delivery_button(ctx: &Context, component: &ComponentInteraction, db: &Database) -> Result<()> {
+    let mut parts = component.data.custom_id.split(':');
+    let action = parts.next().unwrap_or_default();
+    let reminder = match parts.next().and_then(|id| id.parse().ok()) {
+        Some(id) => crate::database::ticket::get_reminder(&db.pool, id).await?,
+        None => None,
+    };
+
+    let Some(reminder) = reminder.filter(|r| r.user_id == component.user.id.get() as i64) else {
+        let embed = create_error_embed("Reminder Not Found", "This reminder no longer exists");
+        component.create_response(&ctx.http, CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
+        )).await?;
+        return Ok(());
+    };
+
+    let note = match action {
+        "reminder_snooze" => {
+            let minutes: i64 = parts.next().and_then(|m| m.parse().ok()).unwrap_or(10);
+            let at = Utc::now() + chrono::Duration::minutes(minutes);
+            crate::database::ticket::create_reminder(
+                &db.pool,
+                reminder.user_id,
+                reminder.channel_id,
+                reminder.guild_id,
+                reminder.message_id,
+                reminder.reason.clone(),
+                at,
+            )
+            .await?;
+            format!("Snoozed until <t:{}:t> (<t:{}:R>)", at.timestamp(), at.timestamp())
+        }
+        _ => "Marked as done".to_string(),
+    };
+
+    component.create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(
+        CreateInteractionResponseMessage::new().content(note).components(vec![])
+    )).await?;
+
+    Ok(())
+}
+
+pub fn register() -> CreateCommand {
+    let reminder_option = || CreateCommandOption::new(CommandOptionType::String, "reminder", "Which reminder")
+        .required(true)
+        .set_autocomplete(true);
+
+    CreateCommand::new("reminder")
+        .description("Set and manage your reminders")
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::SubCommand, "set", "Set a reminder")
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "when", "When to remind you (e.g. 30m, 1h30m, tomorrow 9am, 2026-11-01 14:00)").required(true))
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "reason", "What to remind you about").required(true))
+        )
+        .add_option(CreateCommandOption::new(CommandOptionType::SubCommand, "list", "List your pending reminders"))
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::SubCommand, "cancel", "Cancel a reminder")
+                .add_sub_option(reminder_option())
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::SubCommand, "edit", "Change a reminder's time or reason")
+                .add_sub_option(reminder_option())
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "when", "New time (e.g. tomorrow 9am)").required(false))
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "reason", "New reason").required(false))
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::SubCommand, "snooze", "Push a reminder back")
+                .add_sub_option(reminder_option())
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "duration", "How long to push it back (default 10m)").required(false))
+        )
+}
@@ -0,0 +1,96 @@
+use serenity::all::{
+    CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption,
+    ResolvedValue,
+};
+use crate::database::Database;
+use crate::utils::create_error_embed;
+use crate::utils::stats::StaffSort;
+use anyhow::Result;
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database) -> Result<()> {
+    let guild_id = interaction.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?.get() as i64;
+
+    let options = interaction.data.options();
+    let string_option = |name: &str| options.iter().find_map(|o| match o.value {
+        ResolvedValue::String(value) if o.name == name => Some(value),
+        _ => None,
+    });
+    let member = options.iter().find_map(|o| match &o.value {
+        ResolvedValue::User(user, _) if o.name == "member" => Some(user.id.get()),
+        _ => None,
+    });
+    let sort = string_option("sort").and_then(StaffSort::parse).unwrap_or(StaffSort::Closed);
+
+    let embed = if !crate::utils::has_support_role_or_admin(ctx, interaction.user.id, guild_id, db).await? {
+        create_error_embed("Permission Denied", "Only support staff can view staff statistics")
+    } else {
+        let period = string_option("period").unwrap_or("30d");
+        match crate::utils::stats::parse_stats_period(period, string_option("from"), string_option("to")) {
+            Ok(period) => crate::utils::stats::staff_stats_embed(ctx, db, guild_id, &period, member, sort).await?,
+            Err(e) => create_error_embed("Invalid Period", e.to_string()),
+        }
+    };
+
+    interaction
+        .create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+            serenity::all::CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .ephemeral(true)
+        ))
+        .await?;
+
+    Ok(())
+}
+
+pub fn register() -> CreateCommand {
+    let mut sort_option = CreateCommandOption::new(
+        CommandOptionType::String,
+        "sort",
+        "Leaderboard ordering (default: closed)"
+    )
+    .required(false);
+
+    for sort in StaffSort::ALL {
+        sort_option = sort_option.add_string_choice(sort.key(), sort.key());
+    }
+
+    CreateCommand::new("staffstats")
+        .description("View a staff member's performance or the staff leaderboard")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::User,
+                "member",
+                "Staff member to report on (leave empty for the leaderboard)"
+            )
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "period",
+                "Time window to report on (default: last 30 days)"
+            )
+            .required(false)
+            .add_string_choice("Last 24 hours", "24h")
+            .add_string_choice("Last 7 days", "7d")
+            .add_string_choice("Last 30 days", "30d")
+            .add_string_choice("Custom range", "custom")
+        )
+        .add_option(sort_option)
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "from",
+                "Start date for a custom range (YYYY-MM-DD)"
+            )
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "to",
+                "End date for a custom range, inclusive (YYYY-MM-DD, defaults to today)"
+            )
+            .required(false)
+        )
+}
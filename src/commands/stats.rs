@@ -1,10 +1,62 @@
-use serenity::all::{CommandInteraction, Context, CreateCommand, CreateEmbed};
+use serenity::all::{
+    CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption, CreateEmbed,
+    ResolvedValue,
+};
 use crate::database::Database;
+use crate::utils::create_error_embed;
 use anyhow::Result;
 
 pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database) -> Result<()> {
     let guild_id = interaction.guild_id.unwrap().get() as i64;
 
+    let options = interaction.data.options();
+    let string_option = |name: &str| options.iter().find_map(|o| match o.value {
+        ResolvedValue::String(value) if o.name == name => Some(value),
+        _ => None,
+    });
+
+    let chart = options.iter().any(|o| o.name == "chart" && matches!(o.value, ResolvedValue::Boolean(true)));
+
+    if chart {
+        let period = string_option("period").unwrap_or("30d");
+        match crate::utils::stats::parse_stats_period(period, string_option("from"), string_option("to")) {
+            Ok(period) => {
+                interaction.defer(&ctx.http).await?;
+
+                let (embed, attachment) = crate::utils::stats::period_stats_chart(db, guild_id, &period).await?;
+                interaction
+                    .create_followup(&ctx.http, serenity::all::CreateInteractionResponseFollowup::new()
+                        .embed(embed)
+                        .add_file(attachment))
+                    .await?;
+            }
+            Err(e) => {
+                interaction
+                    .create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+                        serenity::all::CreateInteractionResponseMessage::new()
+                            .embed(create_error_embed("Invalid Period", e.to_string()))
+                    ))
+                    .await?;
+            }
+        }
+        return Ok(());
+    }
+
+    if let Some(period) = string_option("period") {
+        let embed = match crate::utils::stats::parse_stats_period(period, string_option("from"), string_option("to")) {
+            Ok(period) => crate::utils::stats::period_stats_embed(db, guild_id, &period).await?,
+            Err(e) => create_error_embed("Invalid Period", e.to_string()),
+        };
+
+        interaction
+            .create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+                serenity::all::CreateInteractionResponseMessage::new()
+                    .embed(embed)
+            ))
+            .await?;
+        return Ok(());
+    }
+
     let open_tickets: (i64,) = sqlx::query_as(
         "SELECT COUNT(*) FROM tickets WHERE guild_id = $1 AND status = 'open'"
     )
@@ -13,7 +65,7 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database)
     .await?;
 
     let closed_tickets: (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM tickets WHERE guild_id = $1 AND status = 'closed'"
+        "SELECT COUNT(*) FROM ticket_history WHERE guild_id = $1"
     )
     .bind(guild_id)
     .fetch_one(&db.pool)
@@ -33,12 +85,12 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database)
     .fetch_one(&db.pool)
     .await?;
 
-    let avg_response_time: Option<(f64,)> = sqlx::query_as(
-        "SELECT AVG(EXTRACT(EPOCH FROM (closed_at - created_at)))
-         FROM tickets WHERE guild_id = $1 AND status = 'closed' AND closed_at IS NOT NULL"
+    let avg_response_time: (Option<f64>,) = sqlx::query_as(
+        "SELECT AVG(EXTRACT(EPOCH FROM (closed_at - opened_at)))::FLOAT8
+         FROM ticket_history WHERE guild_id = $1"
     )
     .bind(guild_id)
-    .fetch_optional(&db.pool)
+    .fetch_one(&db.pool)
     .await?;
 
     let total_messages: (i64,) = sqlx::query_as(
@@ -52,8 +104,8 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database)
 
     let top_support: Vec<(i64, i64)> = sqlx::query_as(
         "SELECT claimed_by, COUNT(*) as count
-         FROM tickets
-         WHERE guild_id = $1 AND claimed_by IS NOT NULL AND status = 'closed'
+         FROM ticket_history
+         WHERE guild_id = $1 AND claimed_by IS NOT NULL
          GROUP BY claimed_by
          ORDER BY count DESC
          LIMIT 5"
@@ -62,13 +114,9 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database)
     .fetch_all(&db.pool)
     .await?;
 
-    let avg_time_str = if let Some((avg_seconds,)) = avg_response_time {
-        let hours = (avg_seconds / 3600.0).floor();
-        let minutes = ((avg_seconds % 3600.0) / 60.0).floor();
-        format!("{}h {}m", hours, minutes)
-    } else {
-        "N/A".to_string()
-    };
+    let avg_time_str = avg_response_time.0
+        .map(crate::utils::stats::format_duration_seconds)
+        .unwrap_or_else(|| "N/A".to_string());
 
     let top_support_str = if top_support.is_empty() {
         "No data yet".to_string()
@@ -103,5 +151,42 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database)
 }
 
 pub fn register() -> CreateCommand {
-    CreateCommand::new("stats").description("View ticket statistics")
+    CreateCommand::new("stats")
+        .description("View ticket statistics")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "period",
+                "Time window to report on (leave empty for all-time totals)"
+            )
+            .required(false)
+            .add_string_choice("Last 24 hours", "24h")
+            .add_string_choice("Last 7 days", "7d")
+            .add_string_choice("Last 30 days", "30d")
+            .add_string_choice("Custom range", "custom")
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "from",
+                "Start date for a custom range (YYYY-MM-DD)"
+            )
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "to",
+                "End date for a custom range, inclusive (YYYY-MM-DD, defaults to today)"
+            )
+            .required(false)
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::Boolean,
+                "chart",
+                "Attach a chart of tickets per day and per category (default period: last 30 days)"
+            )
+            .required(false)
+        )
 }
@@ -1,5 +1,12 @@
-use serenity::all::{CommandInteraction, CommandOptionType, Context, CreateCommand, CreateCommandOption, ResolvedOption, ResolvedValue};
+use serenity::all::{
+    ActionRowComponent, ButtonStyle, CommandInteraction, CommandOptionType, ComponentInteraction, Context, CreateActionRow,
+    CreateAutocompleteResponse, CreateButton, CreateCommand, CreateCommandOption, CreateInputText, CreateInteractionResponse,
+    CreateInteractionResponseMessage, CreateModal, InputTextStyle, ModalInteraction, ResolvedOption, ResolvedValue,
+};
+use serde::{Deserialize, Serialize};
 use crate::database::Database;
+use crate::models::{TagButton, TagEmbed, TagEmbedField};
+use crate::utils::tag::{is_http_url, BrowserFolder, ConflictStrategy, TagAction, MAX_EMBED_FIELDS, MAX_LINK_BUTTONS};
 use crate::utils::{create_error_embed, create_success_embed, create_embed};
 use anyhow::Result;
 
@@ -18,6 +25,15 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database)
             "rename" => handle_rename(ctx, interaction, db, value).await?,
             "popular" => handle_popular(ctx, interaction, db).await?,
             "use" => handle_use(ctx, interaction, db, value).await?,
+            "alias" => handle_alias(ctx, interaction, db, value).await?,
+            "embed" => handle_embed(ctx, interaction, db, value).await?,
+            "history" => handle_history(ctx, interaction, db, value).await?,
+            "rollback" => handle_rollback(ctx, interaction, db, value).await?,
+            "permissions" => handle_permissions(ctx, interaction, db, value).await?,
+            "export" => handle_export(ctx, interaction, db).await?,
+            "import" => handle_import(ctx, interaction, db, value).await?,
+            "library" => handle_library(ctx, interaction, db, value).await?,
+            "folder" => handle_folder(ctx, interaction, db, value).await?,
             _ => {}
         }
     }
@@ -25,6 +41,34 @@ pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database)
     Ok(())
 }
 
+/// Suggests folder names for folder options and tag names for every other option,
+/// closest matches first.
+pub async fn autocomplete(ctx: &Context, interaction: &CommandInteraction, db: &Database) -> Result<()> {
+    let Some(guild_id) = interaction.guild_id else {
+        return Ok(());
+    };
+    let Some(focused) = interaction.data.autocomplete() else {
+        return Ok(());
+    };
+
+    let names = if focused.name == "folder" {
+        let query = focused.value.to_lowercase();
+        crate::database::tag::get_tag_folders(&db.pool, guild_id.get() as i64).await?
+            .into_iter()
+            .map(|f| f.name)
+            .filter(|name| name.to_lowercase().contains(&query))
+            .take(25)
+            .collect()
+    } else {
+        crate::database::tag::fuzzy_tag_names(&db.pool, guild_id.get() as i64, focused.value, 25).await?
+    };
+    let response = names.into_iter()
+        .fold(CreateAutocompleteResponse::new(), |response, name| response.add_string_choice(name.clone(), name));
+
+    interaction.create_response(&ctx.http, CreateInteractionResponse::Autocomplete(response)).await?;
+    Ok(())
+}
+
 async fn handle_create(ctx: &Context, interaction: &CommandInteraction, db: &Database, options: &ResolvedValue<'_>) -> Result<()> {
     let guild_id = interaction.guild_id.unwrap().get() as i64;
 
@@ -47,6 +91,14 @@ async fn handle_create(ctx: &Context, interaction: &CommandInteraction, db: &Dat
             return Ok(());
         }
 
+        if !crate::utils::tag::can_manage_tag(ctx, db, interaction.guild_id.unwrap(), interaction.user.id, TagAction::Create, None).await? {
+            let embed = create_error_embed("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Create));
+            interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+                serenity::all::CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
+            )).await?;
+            return Ok(());
+        }
+
         if crate::database::tag::get_tag(&db.pool, guild_id, name).await?.is_some() {
             let embed = create_error_embed("Tag Exists", format!("Tag `{}` already exists", name));
             interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
@@ -83,7 +135,7 @@ async fn handle_edit(ctx: &Context, interaction: &CommandInteraction, db: &Datab
         let tag = crate::database::tag::get_tag(&db.pool, guild_id, name).await?;
 
         if tag.is_none() {
-            let embed = create_error_embed("Tag Not Found", format!("Tag `{}` does not exist", name));
+            let embed = crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?;
             interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
                 serenity::all::CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
             )).await?;
@@ -91,15 +143,23 @@ async fn handle_edit(ctx: &Context, interaction: &CommandInteraction, db: &Datab
         }
 
         let tag = tag.unwrap();
-        if tag.creator_id != interaction.user.id.get() as i64 {
-            let embed = create_error_embed("Permission Denied", "You can only edit tags you created");
+        if !crate::utils::tag::can_manage_tag(ctx, db, interaction.guild_id.unwrap(), interaction.user.id, TagAction::Edit, Some(tag.creator_id)).await? {
+            let embed = create_error_embed("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Edit));
             interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
                 serenity::all::CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
             )).await?;
             return Ok(());
         }
 
-        crate::database::tag::update_tag(&db.pool, guild_id, name, content).await?;
+        if tag.embed.is_some() {
+            let embed = create_error_embed("Embed Tag", format!("Tag `{}` is an embed tag. Use `/tag embed` to edit it.", tag.name));
+            interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+                serenity::all::CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
+            )).await?;
+            return Ok(());
+        }
+
+        crate::database::tag::update_tag(&db.pool, guild_id, &tag.name, content, interaction.user.id.get() as i64).await?;
 
         let embed = create_success_embed("Tag Updated", format!("Tag `{}` has been updated successfully", name));
         interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
@@ -122,7 +182,7 @@ async fn handle_delete(ctx: &Context, interaction: &CommandInteraction, db: &Dat
         let tag = crate::database::tag::get_tag(&db.pool, guild_id, name).await?;
 
         if tag.is_none() {
-            let embed = create_error_embed("Tag Not Found", format!("Tag `{}` does not exist", name));
+            let embed = crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?;
             interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
                 serenity::all::CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
             )).await?;
@@ -130,15 +190,15 @@ async fn handle_delete(ctx: &Context, interaction: &CommandInteraction, db: &Dat
         }
 
         let tag = tag.unwrap();
-        if tag.creator_id != interaction.user.id.get() as i64 {
-            let embed = create_error_embed("Permission Denied", "You can only delete tags you created");
+        if !crate::utils::tag::can_manage_tag(ctx, db, interaction.guild_id.unwrap(), interaction.user.id, TagAction::Delete, Some(tag.creator_id)).await? {
+            let embed = create_error_embed("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Delete));
             interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
                 serenity::all::CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
             )).await?;
             return Ok(());
         }
 
-        crate::database::tag::delete_tag(&db.pool, guild_id, name).await?;
+        crate::database::tag::delete_tag(&db.pool, guild_id, &tag.name).await?;
 
         let embed = create_success_embed("Tag Deleted", format!("Tag `{}` has been deleted", name));
         interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
@@ -161,12 +221,14 @@ async fn handle_info(ctx: &Context, interaction: &CommandInteraction, db: &Datab
         let tag = crate::database::tag::get_tag(&db.pool, guild_id, name).await?;
 
         if let Some(tag) = tag {
+            let aliases = crate::database::tag::get_tag_aliases(&db.pool, tag.id).await?;
             let embed = create_embed(
                 format!("Tag: {}", tag.name),
                 format!(
-                    "**Creator:** <@{}>\n**Uses:** {}\n**Created:** <t:{}:R>\n**Updated:** <t:{}:R>",
+                    "**Creator:** <@{}>\n**Uses:** {}\n**Aliases:** {}\n**Created:** <t:{}:R>\n**Updated:** <t:{}:R>",
                     tag.creator_id,
                     tag.uses,
+                    crate::utils::tag::format_tag_aliases(&aliases),
                     tag.created_at.timestamp(),
                     tag.updated_at.timestamp()
                 )
@@ -176,7 +238,7 @@ async fn handle_info(ctx: &Context, interaction: &CommandInteraction, db: &Datab
                 serenity::all::CreateInteractionResponseMessage::new().embed(embed)
             )).await?;
         } else {
-            let embed = create_error_embed("Tag Not Found", format!("Tag `{}` does not exist", name));
+            let embed = crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?;
             interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
                 serenity::all::CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
             )).await?;
@@ -188,28 +250,125 @@ async fn handle_info(ctx: &Context, interaction: &CommandInteraction, db: &Datab
 
 async fn handle_list(ctx: &Context, interaction: &CommandInteraction, db: &Database) -> Result<()> {
     let guild_id = interaction.guild_id.unwrap().get() as i64;
-    let tags = crate::database::tag::list_tags(&db.pool, guild_id).await?;
+    let (embed, components) = crate::utils::tag::tag_browser(db, guild_id, BrowserFolder::All, 0, None).await?;
 
-    if tags.is_empty() {
-        let embed = create_error_embed("No Tags", "There are no tags in this server");
-        interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
-            serenity::all::CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
-        )).await?;
+    interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+        serenity::all::CreateInteractionResponseMessage::new().embed(embed).components(components)
+    )).await?;
+
+    Ok(())
+}
+
+/// Folder picker, tag preview picker and page buttons on the tag browser.
+pub async fn handle_browser(ctx: &Context, interaction: &ComponentInteraction, db: &Database) -> Result<()> {
+    let guild_id = interaction.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?.get() as i64;
+    let selected = match &interaction.data.kind {
+        serenity::all::ComponentInteractionDataKind::StringSelect { values } => values.first().cloned(),
+        _ => None,
+    };
+
+    let (folder, page, folder_window) = match interaction.data.custom_id.as_str() {
+        "tag_browser_preview" => return preview_tag(ctx, interaction, db, selected.as_deref().unwrap_or_default()).await,
+        "tag_browser_folder" => match selected.as_deref().and_then(|v| v.strip_prefix("more:")) {
+            // "More folders" carries the next slice of folders plus the current folder and page.
+            Some(more) => {
+                let mut parts = more.splitn(3, ':');
+                let window = parts.next().and_then(|w| w.parse().ok());
+                let folder = BrowserFolder::parse(parts.next().unwrap_or("all"));
+                (folder, parts.next().and_then(|p| p.parse().ok()).unwrap_or(0), window)
+            }
+            None => (BrowserFolder::parse(selected.as_deref().unwrap_or("all")), 0, None),
+        },
+        id => {
+            let mut parts = id.trim_start_matches("tag_browser:").splitn(2, ':');
+            let folder = BrowserFolder::parse(parts.next().unwrap_or("all"));
+            (folder, parts.next().and_then(|p| p.parse().ok()).unwrap_or(0), None)
+        }
+    };
+
+    let (embed, components) = crate::utils::tag::tag_browser(db, guild_id, folder, page, folder_window).await?;
+    interaction.create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(
+        CreateInteractionResponseMessage::new().embed(embed).components(components)
+    )).await?;
+
+    Ok(())
+}
+
+/// Shows a tag only to the person browsing. Previews don't count as uses.
+async fn preview_tag(ctx: &Context, interaction: &ComponentInteraction, db: &Database, name: &str) -> Result<()> {
+    let guild_id = interaction.guild_id.unwrap().get() as i64;
+
+    let response = match crate::database::tag::get_tag(&db.pool, guild_id, name).await? {
+        Some(tag) => {
+            let vars = crate::utils::tag::tag_variables(ctx, db, interaction.guild_id, interaction.channel_id, &interaction.user).await?;
+            let (embed, components) = crate::utils::tag::tag_message(&tag, &vars, &[]);
+            CreateInteractionResponseMessage::new().embed(embed).components(components).ephemeral(true)
+        }
+        None => CreateInteractionResponseMessage::new()
+            .embed(crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?)
+            .ephemeral(true),
+    };
+
+    interaction.create_response(&ctx.http, CreateInteractionResponse::Message(response)).await?;
+    Ok(())
+}
+
+async fn handle_folder(ctx: &Context, interaction: &CommandInteraction, db: &Database, options: &ResolvedValue<'_>) -> Result<()> {
+    let guild_id = interaction.guild_id.unwrap().get() as i64;
+
+    let ResolvedValue::SubCommandGroup(group) = options else {
         return Ok(());
-    }
+    };
+    let Some(ResolvedOption { name: sub, value: ResolvedValue::SubCommand(options), .. }) = group.first() else {
+        return Ok(());
+    };
 
-    let tag_list: Vec<String> = tags.iter()
-        .take(25)
-        .map(|t| format!("`{}` (uses: {})", t.name, t.uses))
-        .collect();
+    let string_option = |name: &str| options.iter()
+        .find(|opt| opt.name == name)
+        .and_then(|opt| if let ResolvedValue::String(s) = opt.value { Some(s) } else { None });
 
-    let embed = create_embed(
-        format!("Tags ({} total)", tags.len()),
-        tag_list.join("\n")
-    );
+    let embed = match *sub {
+        "list" => {
+            let folders = crate::database::tag::get_tag_folders(&db.pool, guild_id).await?;
+            create_embed("Tag Folders", crate::utils::tag::format_tag_folders(&folders))
+        }
+        "move" => {
+            let name = string_option("name").unwrap_or_default();
+            let folder = string_option("folder");
+            match crate::database::tag::get_tag(&db.pool, guild_id, name).await? {
+                None => crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?,
+                Some(tag) if !crate::utils::tag::can_manage_tag(ctx, db, interaction.guild_id.unwrap(), interaction.user.id, TagAction::Edit, Some(tag.creator_id)).await? => {
+                    create_error_embed("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Edit))
+                }
+                Some(tag) => crate::utils::tag::move_tag_to_folder(db, guild_id, &tag, folder).await?,
+            }
+        }
+        _ if !crate::utils::tag::can_manage_tag(ctx, db, interaction.guild_id.unwrap(), interaction.user.id, TagAction::Edit, None).await? => {
+            create_error_embed("Permission Denied", "You need Administrator or a tag edit role to manage folders")
+        }
+        "create" => {
+            let name = string_option("folder").unwrap_or_default();
+            if !crate::utils::tag::valid_folder_name(name) {
+                create_error_embed("Invalid Name", "Folder names must be one word of 50 characters or less, and can't be `none`")
+            } else if crate::database::tag::create_tag_folder(&db.pool, guild_id, name, interaction.user.id.get() as i64).await?.is_some() {
+                create_success_embed("Folder Created", format!("Folder `{}` has been created. Add tags with `/tag folder move`.", name))
+            } else {
+                create_error_embed("Folder Exists", format!("Folder `{}` already exists", name))
+            }
+        }
+        "delete" => {
+            let name = string_option("folder").unwrap_or_default();
+            if crate::database::tag::delete_tag_folder(&db.pool, guild_id, name).await? {
+                create_success_embed("Folder Deleted", format!("Folder `{}` has been deleted. Its tags are now unfiled.", name))
+            } else {
+                create_error_embed("Folder Not Found", format!("Folder `{}` does not exist", name))
+            }
+        }
+        _ => return Ok(()),
+    };
 
     interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
-        serenity::all::CreateInteractionResponseMessage::new().embed(embed)
+        serenity::all::CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
     )).await?;
 
     Ok(())
@@ -263,13 +422,23 @@ async fn handle_raw(ctx: &Context, interaction: &CommandInteraction, db: &Databa
         let tag = crate::database::tag::get_tag(&db.pool, guild_id, name).await?;
 
         if let Some(tag) = tag {
+            if let Some(embed) = &tag.embed {
+                let (content, file) = crate::utils::tag::export_tag_embed(&tag.name, embed)?;
+                let mut message = serenity::all::CreateInteractionResponseMessage::new().content(content);
+                if let Some(file) = file {
+                    message = message.add_file(file);
+                }
+                interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(message)).await?;
+                return Ok(());
+            }
+
             let content = format!("```\n{}\n```", tag.content.replace("```", "\\`\\`\\`"));
 
             interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
                 serenity::all::CreateInteractionResponseMessage::new().content(content)
             )).await?;
         } else {
-            let embed = create_error_embed("Tag Not Found", format!("Tag `{}` does not exist", name));
+            let embed = crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?;
             interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
                 serenity::all::CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
             )).await?;
@@ -304,7 +473,7 @@ async fn handle_rename(ctx: &Context, interaction: &CommandInteraction, db: &Dat
         let tag = crate::database::tag::get_tag(&db.pool, guild_id, old_name).await?;
 
         if tag.is_none() {
-            let embed = create_error_embed("Tag Not Found", format!("Tag `{}` does not exist", old_name));
+            let embed = crate::utils::tag::tag_not_found_embed(db, guild_id, old_name).await?;
             interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
                 serenity::all::CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
             )).await?;
@@ -312,8 +481,8 @@ async fn handle_rename(ctx: &Context, interaction: &CommandInteraction, db: &Dat
         }
 
         let tag = tag.unwrap();
-        if tag.creator_id != interaction.user.id.get() as i64 {
-            let embed = create_error_embed("Permission Denied", "You can only rename tags you created");
+        if !crate::utils::tag::can_manage_tag(ctx, db, interaction.guild_id.unwrap(), interaction.user.id, TagAction::Edit, Some(tag.creator_id)).await? {
+            let embed = create_error_embed("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Edit));
             interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
                 serenity::all::CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
             )).await?;
@@ -328,7 +497,7 @@ async fn handle_rename(ctx: &Context, interaction: &CommandInteraction, db: &Dat
             return Ok(());
         }
 
-        crate::database::tag::rename_tag(&db.pool, guild_id, old_name, new_name).await?;
+        crate::database::tag::rename_tag(&db.pool, guild_id, &tag.name, new_name).await?;
 
         let embed = create_success_embed("Tag Renamed", format!("Tag `{}` has been renamed to `{}`", old_name, new_name));
         interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
@@ -372,19 +541,24 @@ async fn handle_use(ctx: &Context, interaction: &CommandInteraction, db: &Databa
             .find(|opt| opt.name == "name")
             .and_then(|opt| if let ResolvedValue::String(s) = opt.value { Some(s) } else { None })
             .unwrap();
+        let args: Vec<&str> = options.iter()
+            .find(|opt| opt.name == "args")
+            .and_then(|opt| if let ResolvedValue::String(s) = opt.value { Some(s.split_whitespace().collect()) } else { None })
+            .unwrap_or_default();
 
         let tag = crate::database::tag::get_tag(&db.pool, guild_id, name).await?;
 
         if let Some(tag) = tag {
-            crate::database::tag::increment_tag_uses(&db.pool, guild_id, name).await?;
+            crate::database::tag::increment_tag_uses(&db.pool, guild_id, &tag.name).await?;
 
-            let embed = create_embed(&tag.name, &tag.content);
+            let vars = crate::utils::tag::tag_variables(ctx, db, interaction.guild_id, interaction.channel_id, &interaction.user).await?;
+            let (embed, components) = crate::utils::tag::tag_message(&tag, &vars, &args);
 
             interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
-                serenity::all::CreateInteractionResponseMessage::new().embed(embed)
+                serenity::all::CreateInteractionResponseMessage::new().embed(embed).components(components)
             )).await?;
         } else {
-            let embed = create_error_embed("Tag Not Found", format!("Tag `{}` does not exist", name));
+            let embed = crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?;
             interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
                 serenity::all::CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
             )).await?;
@@ -394,6 +568,611 @@ async fn handle_use(ctx: &Context, interaction: &CommandInteraction, db: &Databa
     Ok(())
 }
 
+async fn handle_alias(ctx: &Context, interaction: &CommandInteraction, db: &Database, options: &ResolvedValue<'_>) -> Result<()> {
+    let guild_id = interaction.guild_id.unwrap().get() as i64;
+    let user_id = interaction.user.id.get() as i64;
+
+    let ResolvedValue::SubCommandGroup(group) = options else {
+        return Ok(());
+    };
+    let Some(ResolvedOption { name: action, value: ResolvedValue::SubCommand(options), .. }) = group.first() else {
+        return Ok(());
+    };
+
+    let string_option = |name: &str| options.iter()
+        .find(|opt| opt.name == name)
+        .and_then(|opt| if let ResolvedValue::String(s) = opt.value { Some(s) } else { None })
+        .unwrap_or_default();
+
+    let embed = match *action {
+        "add" => {
+            let (name, alias) = (string_option("name"), string_option("alias"));
+            match crate::database::tag::get_tag(&db.pool, guild_id, name).await? {
+                None => crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?,
+                Some(tag) if !crate::utils::tag::can_manage_tag(ctx, db, interaction.guild_id.unwrap(), interaction.user.id, TagAction::Edit, Some(tag.creator_id)).await? => {
+                    create_error_embed("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Edit))
+                }
+                Some(tag) => match crate::utils::tag::check_tag_alias(db, guild_id, alias).await? {
+                    Some(reason) => create_error_embed("Alias Not Added", reason),
+                    None => {
+                        crate::database::tag::add_tag_alias(&db.pool, guild_id, tag.id, alias, user_id).await?;
+                        create_success_embed("Alias Added", format!("`{}` now points to tag `{}`", alias, tag.name))
+                    }
+                },
+            }
+        }
+        "remove" => {
+            let alias = string_option("alias");
+            match crate::database::tag::get_tag(&db.pool, guild_id, alias).await? {
+                Some(tag) if !tag.name.eq_ignore_ascii_case(alias) => {
+                    if !crate::utils::tag::can_manage_tag(ctx, db, interaction.guild_id.unwrap(), interaction.user.id, TagAction::Edit, Some(tag.creator_id)).await? {
+                        create_error_embed("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Edit))
+                    } else {
+                        crate::database::tag::remove_tag_alias(&db.pool, guild_id, alias).await?;
+                        create_success_embed("Alias Removed", format!("`{}` no longer points to tag `{}`", alias, tag.name))
+                    }
+                }
+                _ => create_error_embed("Alias Not Found", format!("`{}` is not a tag alias", alias)),
+            }
+        }
+        _ => return Ok(()),
+    };
+
+    interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+        serenity::all::CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
+    )).await?;
+
+    Ok(())
+}
+
+async fn handle_history(ctx: &Context, interaction: &CommandInteraction, db: &Database, options: &ResolvedValue<'_>) -> Result<()> {
+    let guild_id = interaction.guild_id.unwrap().get() as i64;
+
+    let ResolvedValue::SubCommand(options) = options else {
+        return Ok(());
+    };
+    let name = options.iter()
+        .find(|opt| opt.name == "name")
+        .and_then(|opt| if let ResolvedValue::String(s) = opt.value { Some(s) } else { None })
+        .unwrap_or_default();
+
+    let embed = match crate::database::tag::get_tag(&db.pool, guild_id, name).await? {
+        None => crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?,
+        Some(tag) if !crate::utils::tag::can_manage_tag(ctx, db, interaction.guild_id.unwrap(), interaction.user.id, TagAction::Edit, Some(tag.creator_id)).await? => {
+            create_error_embed("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Edit))
+        }
+        Some(tag) => {
+            let revisions = crate::database::tag::get_tag_revisions(&db.pool, tag.id, 10).await?;
+            create_embed(format!("History: {}", tag.name), crate::utils::tag::format_tag_history(&tag, &revisions))
+                .footer(serenity::all::CreateEmbedFooter::new(format!("Restore a version with /tag rollback name:{} revision:<number>", tag.name)))
+        }
+    };
+
+    interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+        serenity::all::CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
+    )).await?;
+
+    Ok(())
+}
+
+async fn handle_rollback(ctx: &Context, interaction: &CommandInteraction, db: &Database, options: &ResolvedValue<'_>) -> Result<()> {
+    let guild_id = interaction.guild_id.unwrap().get() as i64;
+
+    let ResolvedValue::SubCommand(options) = options else {
+        return Ok(());
+    };
+    let name = options.iter()
+        .find(|opt| opt.name == "name")
+        .and_then(|opt| if let ResolvedValue::String(s) = opt.value { Some(s) } else { None })
+        .unwrap_or_default();
+    let number = options.iter()
+        .find(|opt| opt.name == "revision")
+        .and_then(|opt| if let ResolvedValue::Integer(n) = opt.value { Some(n as i32) } else { None })
+        .unwrap_or_default();
+
+    let embed = match crate::database::tag::get_tag(&db.pool, guild_id, name).await? {
+        None => crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?,
+        Some(tag) if !crate::utils::tag::can_manage_tag(ctx, db, interaction.guild_id.unwrap(), interaction.user.id, TagAction::Edit, Some(tag.creator_id)).await? => {
+            create_error_embed("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Edit))
+        }
+        Some(tag) => match crate::database::tag::get_tag_revision(&db.pool, tag.id, number).await? {
+            Some(revision) => {
+                crate::database::tag::restore_tag_revision(&db.pool, guild_id, &tag.name, &revision, interaction.user.id.get() as i64).await?;
+                create_success_embed("Tag Restored", format!("Tag `{}` has been restored to revision #{}", tag.name, number))
+            }
+            None => create_error_embed("Revision Not Found", format!("Tag `{}` has no revision #{}. Use `/tag history` to see its versions.", tag.name, number)),
+        },
+    };
+
+    interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+        serenity::all::CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
+    )).await?;
+
+    Ok(())
+}
+
+async fn handle_permissions(ctx: &Context, interaction: &CommandInteraction, db: &Database, options: &ResolvedValue<'_>) -> Result<()> {
+    let guild_id = interaction.guild_id.unwrap().get() as i64;
+
+    let ResolvedValue::SubCommandGroup(group) = options else {
+        return Ok(());
+    };
+    let Some(ResolvedOption { name: sub, value: ResolvedValue::SubCommand(options), .. }) = group.first() else {
+        return Ok(());
+    };
+
+    let is_admin = interaction.member.as_ref()
+        .and_then(|m| m.permissions)
+        .is_some_and(|p| p.administrator());
+    let action = options.iter()
+        .find(|opt| opt.name == "action")
+        .and_then(|opt| if let ResolvedValue::String(s) = opt.value { TagAction::parse(s) } else { None });
+    let role_id = options.iter()
+        .find(|opt| opt.name == "role")
+        .and_then(|opt| if let ResolvedValue::Role(role) = opt.value { Some(role.id.get() as i64) } else { None });
+
+    let embed = match (*sub, action, role_id) {
+        ("show", _, _) => {
+            let roles = crate::database::tag::get_tag_permission_roles(&db.pool, guild_id).await?;
+            create_embed("Tag Permissions", crate::utils::tag::format_tag_permissions(&roles))
+        }
+        _ if !is_admin => create_error_embed("Permission Denied", "You need Administrator permission to change tag permissions"),
+        ("add", Some(action), Some(role_id)) => {
+            crate::database::tag::add_tag_permission_role(&db.pool, guild_id, action.as_str(), role_id).await?;
+            create_success_embed("Tag Permissions Updated", format!("<@&{}> can now {} tags", role_id, action.as_str()))
+        }
+        ("remove", Some(action), Some(role_id)) => {
+            if crate::database::tag::remove_tag_permission_role(&db.pool, guild_id, action.as_str(), role_id).await? {
+                create_success_embed("Tag Permissions Updated", format!("<@&{}> can no longer {} tags", role_id, action.as_str()))
+            } else {
+                create_error_embed("Not Found", format!("<@&{}> is not a tag {} role", role_id, action.as_str()))
+            }
+        }
+        _ => return Ok(()),
+    };
+
+    interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+        serenity::all::CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
+    )).await?;
+
+    Ok(())
+}
+
+async fn handle_export(ctx: &Context, interaction: &CommandInteraction, db: &Database) -> Result<()> {
+    let guild_id = interaction.guild_id.unwrap().get() as i64;
+
+    let (count, file) = crate::utils::tag::export_tags(db, guild_id).await?;
+    let embed = create_success_embed("Tags Exported", format!("Exported {} tags. Load them into another server with `/tag import`.", count));
+
+    interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+        serenity::all::CreateInteractionResponseMessage::new().embed(embed).add_file(file).ephemeral(true)
+    )).await?;
+
+    Ok(())
+}
+
+async fn handle_import(ctx: &Context, interaction: &CommandInteraction, db: &Database, options: &ResolvedValue<'_>) -> Result<()> {
+    let guild_id = interaction.guild_id.unwrap().get() as i64;
+
+    let ResolvedValue::SubCommand(options) = options else {
+        return Ok(());
+    };
+    let attachment = options.iter()
+        .find(|opt| opt.name == "file")
+        .and_then(|opt| if let ResolvedValue::Attachment(a) = opt.value { Some(a) } else { None });
+    let strategy = options.iter()
+        .find(|opt| opt.name == "conflicts")
+        .and_then(|opt| if let ResolvedValue::String(s) = opt.value { ConflictStrategy::parse(s) } else { None })
+        .unwrap_or(ConflictStrategy::Skip);
+
+    let is_admin = interaction.member.as_ref()
+        .and_then(|m| m.permissions)
+        .is_some_and(|p| p.administrator());
+
+    let error = match attachment {
+        _ if !is_admin => Some("You need Administrator permission to import tags".to_string()),
+        None => return Ok(()),
+        Some(a) if a.size > crate::utils::tag::MAX_IMPORT_BYTES => Some("Import files can be at most 1 MB".to_string()),
+        Some(_) => None,
+    };
+    if let Some(error) = error {
+        interaction.create_response(&ctx.http, ephemeral_error("Import Failed", error)).await?;
+        return Ok(());
+    }
+
+    interaction.defer_ephemeral(&ctx.http).await?;
+
+    let data = attachment.unwrap().download().await?;
+    let embed = match crate::utils::tag::import_tags(db, guild_id, &data, strategy, interaction.user.id.get() as i64).await {
+        Ok(summary) => create_success_embed("Tags Imported", crate::utils::tag::format_import_summary(&summary)),
+        Err(e) => create_error_embed("Import Failed", e.to_string()),
+    };
+
+    interaction.create_followup(&ctx.http, serenity::all::CreateInteractionResponseFollowup::new().embed(embed).ephemeral(true)).await?;
+
+    Ok(())
+}
+
+async fn handle_library(ctx: &Context, interaction: &CommandInteraction, db: &Database, options: &ResolvedValue<'_>) -> Result<()> {
+    let guild_id = interaction.guild_id.unwrap().get() as i64;
+
+    let ResolvedValue::SubCommandGroup(group) = options else {
+        return Ok(());
+    };
+    let Some(ResolvedOption { name: sub, .. }) = group.first() else {
+        return Ok(());
+    };
+
+    let is_admin = interaction.member.as_ref()
+        .and_then(|m| m.permissions)
+        .is_some_and(|p| p.administrator());
+
+    let embed = match *sub {
+        "browse" => {
+            let entries = crate::database::tag::list_library_tags(&db.pool).await?;
+            let subscribed = crate::database::tag::is_tag_library_subscribed(&db.pool, guild_id).await?;
+            create_embed(format!("Tag Library ({})", entries.len()), crate::utils::tag::format_library_tags(&entries))
+                .footer(serenity::all::CreateEmbedFooter::new(if subscribed {
+                    "This server is subscribed"
+                } else {
+                    "Subscribe with /tag library subscribe"
+                }))
+        }
+        _ if !is_admin => create_error_embed("Permission Denied", "You need Administrator permission to manage the library subscription"),
+        "subscribe" => match crate::database::tag::subscribe_tag_library(&db.pool, guild_id, interaction.user.id.get() as i64).await? {
+            Some((added, skipped)) => create_success_embed("Subscribed", crate::utils::tag::library_subscribed_message(added, skipped)),
+            None => create_error_embed("Already Subscribed", "This server is already subscribed to the tag library"),
+        },
+        "unsubscribe" => match crate::database::tag::unsubscribe_tag_library(&db.pool, guild_id).await? {
+            Some(removed) => create_success_embed("Unsubscribed", format!("Removed {} library tags. Tags you edited have been kept.", removed)),
+            None => create_error_embed("Not Subscribed", "This server is not subscribed to the tag library"),
+        },
+        _ => return Ok(()),
+    };
+
+    interaction.create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+        serenity::all::CreateInteractionResponseMessage::new().embed(embed).ephemeral(true)
+    )).await?;
+
+    Ok(())
+}
+
+const BUILDER_SESSION_TTL: u64 = 900;
+
+/// An embed tag being built. Kept in Redis between button presses and modals.
+#[derive(Serialize, Deserialize)]
+struct TagBuilderSession {
+    guild_id: i64,
+    name: String,
+    existing: bool,
+    embed: TagEmbed,
+}
+
+fn builder_session_key(user_id: u64) -> String {
+    format!("tag_builder_session:{}", user_id)
+}
+
+async fn load_builder_session(db: &Database, user_id: u64) -> Result<Option<TagBuilderSession>> {
+    let mut redis_conn = db.redis.clone();
+    let raw: Option<String> = redis::cmd("GET")
+        .arg(builder_session_key(user_id))
+        .query_async(&mut redis_conn)
+        .await?;
+
+    Ok(raw.and_then(|raw| serde_json::from_str(&raw).ok()))
+}
+
+async fn save_builder_session(db: &Database, user_id: u64, session: &TagBuilderSession) -> Result<()> {
+    let mut redis_conn = db.redis.clone();
+    redis::cmd("SETEX")
+        .arg(builder_session_key(user_id))
+        .arg(BUILDER_SESSION_TTL)
+        .arg(serde_json::to_string(session)?)
+        .query_async::<()>(&mut redis_conn)
+        .await?;
+    Ok(())
+}
+
+async fn clear_builder_session(db: &Database, user_id: u64) -> Result<()> {
+    let mut redis_conn = db.redis.clone();
+    redis::cmd("DEL")
+        .arg(builder_session_key(user_id))
+        .query_async::<()>(&mut redis_conn)
+        .await?;
+    Ok(())
+}
+
+fn ephemeral_error(title: &str, description: impl Into<String>) -> CreateInteractionResponse {
+    CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new().embed(create_error_embed(title, description)).ephemeral(true)
+    )
+}
+
+fn builder_message(session: &TagBuilderSession) -> CreateInteractionResponseMessage {
+    let embed = &session.embed;
+    let (preview, mut components) = if embed.title.is_none() && embed.description.is_none() && embed.fields.is_empty() {
+        (create_embed("Empty Embed", "Use **Edit Content** to add a title, description, color, image and footer."), Vec::new())
+    } else {
+        crate::utils::tag::embed_tag_message(embed, |text| text.to_string())
+    };
+
+    components.push(CreateActionRow::Buttons(vec![
+        CreateButton::new("tag_builder:content").label("Edit Content").style(ButtonStyle::Primary),
+        CreateButton::new("tag_builder:field").label("Add Field").style(ButtonStyle::Secondary),
+        CreateButton::new("tag_builder:button").label("Add Button").style(ButtonStyle::Secondary),
+        CreateButton::new("tag_builder:clear").label("Clear Fields & Buttons").style(ButtonStyle::Secondary),
+    ]));
+    components.push(CreateActionRow::Buttons(vec![
+        CreateButton::new("tag_builder:save").label("Save").style(ButtonStyle::Success),
+        CreateButton::new("tag_builder:cancel").label("Cancel").style(ButtonStyle::Danger),
+    ]));
+
+    CreateInteractionResponseMessage::new()
+        .content(format!(
+            "Building embed tag `{}` ({}/{} fields, {}/{} buttons). Nothing is saved until you press **Save**.",
+            session.name, embed.fields.len(), MAX_EMBED_FIELDS, embed.buttons.len(), MAX_LINK_BUTTONS
+        ))
+        .embed(preview)
+        .components(components)
+        .ephemeral(true)
+}
+
+// Longest value a modal text input can hold.
+const MODAL_TEXT_LIMIT: usize = 4000;
+
+fn builder_input(style: InputTextStyle, label: &str, custom_id: &str, value: Option<&str>, max_length: u16, required: bool) -> CreateActionRow {
+    let mut input = CreateInputText::new(style, label, custom_id)
+        .max_length(max_length)
+        .required(required);
+    if let Some(value) = value.filter(|v| !v.is_empty()) {
+        input = input.value(value);
+    }
+    CreateActionRow::InputText(input)
+}
+
+fn modal_value(interaction: &ModalInteraction, custom_id: &str) -> Option<String> {
+    interaction.data.components.iter()
+        .flat_map(|row| row.components.iter())
+        .find_map(|component| match component {
+            ActionRowComponent::InputText(input) if input.custom_id == custom_id => input.value.clone(),
+            _ => None,
+        })
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+async fn handle_embed(ctx: &Context, interaction: &CommandInteraction, db: &Database, options: &ResolvedValue<'_>) -> Result<()> {
+    let guild_id = interaction.guild_id.unwrap().get() as i64;
+
+    let ResolvedValue::SubCommand(options) = options else {
+        return Ok(());
+    };
+    let name = options.iter()
+        .find(|opt| opt.name == "name")
+        .and_then(|opt| if let ResolvedValue::String(s) = opt.value { Some(s) } else { None })
+        .unwrap();
+
+    let session = match crate::database::tag::get_tag(&db.pool, guild_id, name).await? {
+        Some(tag) => {
+            if !crate::utils::tag::can_manage_tag(ctx, db, interaction.guild_id.unwrap(), interaction.user.id, TagAction::Edit, Some(tag.creator_id)).await? {
+                interaction.create_response(&ctx.http, ephemeral_error("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Edit))).await?;
+                return Ok(());
+            }
+
+            // Text tags open with their content as the description, so saving converts them.
+            let content_length = tag.content.chars().count();
+            if tag.embed.is_none() && content_length > MODAL_TEXT_LIMIT {
+                interaction.create_response(&ctx.http, ephemeral_error(
+                    "Tag Too Long",
+                    format!("`{}` is {} characters, but the builder can only edit up to {}. Shorten it with `/tag edit` first.", tag.name, content_length, MODAL_TEXT_LIMIT),
+                )).await?;
+                return Ok(());
+            }
+            let embed = tag.embed.map(|embed| embed.0).unwrap_or_else(|| TagEmbed {
+                description: Some(tag.content.clone()),
+                ..Default::default()
+            });
+            TagBuilderSession { guild_id, name: tag.name, existing: true, embed }
+        }
+        None => {
+            if name.len() > 100 {
+                interaction.create_response(&ctx.http, ephemeral_error("Invalid Name", "Tag name must be 100 characters or less")).await?;
+                return Ok(());
+            }
+            if !crate::utils::tag::can_manage_tag(ctx, db, interaction.guild_id.unwrap(), interaction.user.id, TagAction::Create, None).await? {
+                interaction.create_response(&ctx.http, ephemeral_error("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Create))).await?;
+                return Ok(());
+            }
+            TagBuilderSession { guild_id, name: name.to_string(), existing: false, embed: TagEmbed::default() }
+        }
+    };
+
+    save_builder_session(db, interaction.user.id.get(), &session).await?;
+    interaction.create_response(&ctx.http, CreateInteractionResponse::Message(builder_message(&session))).await?;
+
+    Ok(())
+}
+
+pub async fn handle_builder_button(ctx: &Context, interaction: &ComponentInteraction, db: &Database) -> Result<()> {
+    let user_id = interaction.user.id.get();
+
+    let Some(mut session) = load_builder_session(db, user_id).await? else {
+        interaction.create_response(&ctx.http, ephemeral_error("Session Expired", "This tag builder has expired. Run `/tag embed` again.")).await?;
+        return Ok(());
+    };
+
+    match interaction.data.custom_id.trim_start_matches("tag_builder:") {
+        "content" => {
+            let embed = &session.embed;
+            // Discord rejects a modal whose pre-filled value is longer than the input allows.
+            let description_length = embed.description.as_deref().map_or(0, |d| d.chars().count());
+            if description_length > MODAL_TEXT_LIMIT {
+                interaction.create_response(&ctx.http, ephemeral_error(
+                    "Description Too Long",
+                    format!("The description is {} characters, but the form can only hold {}.", description_length, MODAL_TEXT_LIMIT),
+                )).await?;
+                return Ok(());
+            }
+            let color = embed.color.map(|c| format!("#{:06X}", c));
+            let modal = CreateModal::new("tag_builder_modal:content", "Embed Content").components(vec![
+                builder_input(InputTextStyle::Short, "Title", "title", embed.title.as_deref(), 256, false),
+                builder_input(InputTextStyle::Paragraph, "Description", "description", embed.description.as_deref(), MODAL_TEXT_LIMIT as u16, false),
+                builder_input(InputTextStyle::Short, "Color (hex, e.g. #5865F2)", "color", color.as_deref(), 7, false),
+                builder_input(InputTextStyle::Short, "Image URL", "image", embed.image.as_deref(), 1000, false),
+                builder_input(InputTextStyle::Short, "Footer", "footer", embed.footer.as_deref(), 2048, false),
+            ]);
+            interaction.create_response(&ctx.http, CreateInteractionResponse::Modal(modal)).await?;
+        }
+        "field" => {
+            if session.embed.fields.len() >= MAX_EMBED_FIELDS {
+                interaction.create_response(&ctx.http, ephemeral_error("Too Many Fields", format!("Embeds can have at most {} fields", MAX_EMBED_FIELDS))).await?;
+                return Ok(());
+            }
+            let modal = CreateModal::new("tag_builder_modal:field", "Add Field").components(vec![
+                builder_input(InputTextStyle::Short, "Name", "name", None, 256, true),
+                builder_input(InputTextStyle::Paragraph, "Value", "value", None, 1024, true),
+                builder_input(InputTextStyle::Short, "Inline? (yes/no)", "inline", None, 3, false),
+            ]);
+            interaction.create_response(&ctx.http, CreateInteractionResponse::Modal(modal)).await?;
+        }
+        "button" => {
+            if session.embed.buttons.len() >= MAX_LINK_BUTTONS {
+                interaction.create_response(&ctx.http, ephemeral_error("Too Many Buttons", format!("Tags can have at most {} buttons", MAX_LINK_BUTTONS))).await?;
+                return Ok(());
+            }
+            let modal = CreateModal::new("tag_builder_modal:button", "Add Link Button").components(vec![
+                builder_input(InputTextStyle::Short, "Label", "label", None, 80, true),
+                builder_input(InputTextStyle::Short, "URL", "url", None, 512, true),
+            ]);
+            interaction.create_response(&ctx.http, CreateInteractionResponse::Modal(modal)).await?;
+        }
+        "clear" => {
+            session.embed.fields.clear();
+            session.embed.buttons.clear();
+            save_builder_session(db, user_id, &session).await?;
+            interaction.create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(builder_message(&session))).await?;
+        }
+        "save" => save_builder(ctx, interaction, db, &session).await?,
+        "cancel" => {
+            clear_builder_session(db, user_id).await?;
+            interaction.create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content(format!("Stopped building `{}`. Nothing was saved.", session.name))
+                    .embeds(Vec::new())
+                    .components(Vec::new())
+            )).await?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+pub async fn handle_builder_modal(ctx: &Context, interaction: &ModalInteraction, db: &Database) -> Result<()> {
+    let user_id = interaction.user.id.get();
+
+    let Some(mut session) = load_builder_session(db, user_id).await? else {
+        interaction.create_response(&ctx.http, ephemeral_error("Session Expired", "This tag builder has expired. Run `/tag embed` again.")).await?;
+        return Ok(());
+    };
+
+    match interaction.data.custom_id.trim_start_matches("tag_builder_modal:") {
+        "content" => {
+            let color = match modal_value(interaction, "color") {
+                Some(input) => match crate::utils::tag::parse_hex_color(&input) {
+                    Some(color) => Some(color),
+                    None => {
+                        interaction.create_response(&ctx.http, ephemeral_error("Invalid Color", "Use a hex color such as `#5865F2`")).await?;
+                        return Ok(());
+                    }
+                },
+                None => None,
+            };
+
+            let image = modal_value(interaction, "image");
+            if image.as_deref().is_some_and(|url| !is_http_url(url)) {
+                interaction.create_response(&ctx.http, ephemeral_error("Invalid Image", "The image must be an http(s) URL")).await?;
+                return Ok(());
+            }
+
+            session.embed.title = modal_value(interaction, "title");
+            session.embed.description = modal_value(interaction, "description");
+            session.embed.color = color;
+            session.embed.image = image;
+            session.embed.footer = modal_value(interaction, "footer");
+        }
+        "field" => {
+            let inline = modal_value(interaction, "inline")
+                .is_some_and(|v| matches!(v.to_lowercase().as_str(), "yes" | "y" | "true"));
+
+            session.embed.fields.push(TagEmbedField {
+                name: modal_value(interaction, "name").unwrap_or_default(),
+                value: modal_value(interaction, "value").unwrap_or_default(),
+                inline,
+            });
+        }
+        "button" => {
+            let url = modal_value(interaction, "url").unwrap_or_default();
+            if !is_http_url(&url) {
+                interaction.create_response(&ctx.http, ephemeral_error("Invalid URL", "Button links must be http(s) URLs")).await?;
+                return Ok(());
+            }
+
+            session.embed.buttons.push(TagButton {
+                label: modal_value(interaction, "label").unwrap_or_default(),
+                url,
+            });
+        }
+        _ => return Ok(()),
+    }
+
+    save_builder_session(db, user_id, &session).await?;
+    interaction.create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(builder_message(&session))).await?;
+
+    Ok(())
+}
+
+async fn save_builder(ctx: &Context, interaction: &ComponentInteraction, db: &Database, session: &TagBuilderSession) -> Result<()> {
+    let embed = &session.embed;
+    if let Err(problem) = crate::utils::tag::validate_tag_embed(embed) {
+        interaction.create_response(&ctx.http, ephemeral_error("Can't Save Embed", problem)).await?;
+        return Ok(());
+    }
+
+    let user_id = interaction.user.id.get() as i64;
+    let name = match crate::database::tag::get_tag(&db.pool, session.guild_id, &session.name).await? {
+        Some(tag) if !session.existing => {
+            interaction.create_response(&ctx.http, ephemeral_error("Tag Exists", format!("Tag `{}` was created while you were building this one", tag.name))).await?;
+            return Ok(());
+        }
+        Some(tag) if !crate::utils::tag::can_manage_tag(ctx, db, interaction.guild_id.unwrap(), interaction.user.id, TagAction::Edit, Some(tag.creator_id)).await? => {
+            interaction.create_response(&ctx.http, ephemeral_error("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Edit))).await?;
+            return Ok(());
+        }
+        Some(tag) => {
+            crate::database::tag::set_tag_embed(&db.pool, session.guild_id, &tag.name, embed, user_id).await?;
+            tag.name
+        }
+        None if session.existing => {
+            interaction.create_response(&ctx.http, ephemeral_error("Tag Not Found", format!("Tag `{}` was deleted while you were editing it", session.name))).await?;
+            return Ok(());
+        }
+        None => crate::database::tag::create_embed_tag(&db.pool, session.guild_id, &session.name, embed, user_id).await?.name,
+    };
+
+    clear_builder_session(db, interaction.user.id.get()).await?;
+
+    let verb = if session.existing { "updated" } else { "created" };
+    interaction.create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(
+        CreateInteractionResponseMessage::new()
+            .content("")
+            .embed(create_success_embed("Tag Saved", format!("Embed tag `{}` has been {} successfully", name, verb)))
+            .components(Vec::new())
+    )).await?;
+
+    Ok(())
+}
+
 pub fn register() -> CreateCommand {
     CreateCommand::new("tag")
         .description("Manage server tags")
@@ -404,16 +1183,16 @@ pub fn register() -> CreateCommand {
         )
         .add_option(
             CreateCommandOption::new(CommandOptionType::SubCommand, "edit", "Edit an existing tag")
-                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "name", "Tag name").required(true))
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "name", "Tag name").required(true).set_autocomplete(true))
                 .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "content", "New content").required(true))
         )
         .add_option(
             CreateCommandOption::new(CommandOptionType::SubCommand, "delete", "Delete a tag")
-                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "name", "Tag name").required(true))
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "name", "Tag name").required(true).set_autocomplete(true))
         )
         .add_option(
             CreateCommandOption::new(CommandOptionType::SubCommand, "info", "Get information about a tag")
-                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "name", "Tag name").required(true))
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "name", "Tag name").required(true).set_autocomplete(true))
         )
         .add_option(CreateCommandOption::new(CommandOptionType::SubCommand, "list", "List all tags"))
         .add_option(
@@ -422,16 +1201,98 @@ pub fn register() -> CreateCommand {
         )
         .add_option(
             CreateCommandOption::new(CommandOptionType::SubCommand, "raw", "Get raw tag content")
-                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "name", "Tag name").required(true))
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "name", "Tag name").required(true).set_autocomplete(true))
         )
         .add_option(
             CreateCommandOption::new(CommandOptionType::SubCommand, "rename", "Rename a tag")
-                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "old_name", "Current name").required(true))
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "old_name", "Current name").required(true).set_autocomplete(true))
                 .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "new_name", "New name").required(true))
         )
         .add_option(CreateCommandOption::new(CommandOptionType::SubCommand, "popular", "Show most popular tags"))
         .add_option(
             CreateCommandOption::new(CommandOptionType::SubCommand, "use", "Use a tag")
-                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "name", "Tag name").required(true))
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "name", "Tag name").required(true).set_autocomplete(true))
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "args", "Values for {1}, {2}, ... separated by spaces").required(false))
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::SubCommand, "embed", "Create or edit an embed tag with the builder")
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "name", "Tag name").required(true).set_autocomplete(true))
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::SubCommandGroup, "alias", "Manage tag aliases")
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "add", "Add another name for a tag")
+                        .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "name", "Tag name").required(true).set_autocomplete(true))
+                        .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "alias", "Alias to add").required(true))
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "remove", "Remove a tag alias")
+                        .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "alias", "Alias to remove").required(true))
+                )
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::SubCommand, "history", "Show previous versions of a tag")
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "name", "Tag name").required(true).set_autocomplete(true))
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::SubCommand, "rollback", "Restore a previous version of a tag")
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "name", "Tag name").required(true).set_autocomplete(true))
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::Integer, "revision", "Revision number from /tag history").required(true).min_int_value(1))
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::SubCommandGroup, "permissions", "Manage which roles can create, edit and delete tags")
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::SubCommand, "show", "Show the configured tag roles"))
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "add", "Allow a role to manage tags")
+                        .add_sub_option(tag_action_option())
+                        .add_sub_option(CreateCommandOption::new(CommandOptionType::Role, "role", "Role to allow").required(true))
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "remove", "Stop a role from managing tags")
+                        .add_sub_option(tag_action_option())
+                        .add_sub_option(CreateCommandOption::new(CommandOptionType::Role, "role", "Role to remove").required(true))
+                )
+        )
+        .add_option(CreateCommandOption::new(CommandOptionType::SubCommand, "export", "Download every tag in this server as a JSON file"))
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::SubCommand, "import", "Import tags from a /tag export file")
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::Attachment, "file", "JSON file from /tag export").required(true))
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::String, "conflicts", "What to do when a tag name is taken (default: skip)")
+                        .add_string_choice("Skip", "skip")
+                        .add_string_choice("Overwrite", "overwrite")
+                        .add_string_choice("Rename", "rename")
+                )
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::SubCommandGroup, "library", "The shared tag library")
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::SubCommand, "browse", "List the tags in the library"))
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::SubCommand, "subscribe", "Add the library tags to this server and keep them updated"))
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::SubCommand, "unsubscribe", "Remove the library tags this server hasn't edited"))
         )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::SubCommandGroup, "folder", "Group tags into folders")
+                .add_sub_option(CreateCommandOption::new(CommandOptionType::SubCommand, "list", "List the tag folders"))
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "create", "Create a folder")
+                        .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "folder", "Folder name (one word)").required(true).max_length(50))
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "delete", "Delete a folder, leaving its tags unfiled")
+                        .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "folder", "Folder name").required(true).set_autocomplete(true))
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "move", "Move a tag into a folder")
+                        .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "name", "Tag name").required(true).set_autocomplete(true))
+                        .add_sub_option(CreateCommandOption::new(CommandOptionType::String, "folder", "Folder name; leave empty to unfile the tag").set_autocomplete(true))
+                )
+        )
+}
+
+fn tag_action_option() -> CreateCommandOption {
+    CreateCommandOption::new(CommandOptionType::String, "action", "What the role may do")
+        .required(true)
+        .add_string_choice("Create", "create")
+        .add_string_choice("Edit", "edit")
+        .add_string_choice("Delete", "delete")
 }
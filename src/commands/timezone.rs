@@ -0,0 +1,80 @@
+use serenity::all::{
+    CommandInteraction, CommandOptionType, Context, CreateAutocompleteResponse, CreateCommand,
+    CreateCommandOption, CreateInteractionResponse, ResolvedValue,
+};
+use crate::database::Database;
+use crate::utils::{create_embed, create_error_embed, create_success_embed};
+use anyhow::Result;
+
+pub async fn run(ctx: &Context, interaction: &CommandInteraction, db: &Database) -> Result<()> {
+    let user_id = interaction.user.id.get() as i64;
+    let now = chrono::Utc::now();
+
+    let options = interaction.data.options();
+    let zone = options.iter()
+        .find(|opt| opt.name == "zone")
+        .and_then(|opt| if let ResolvedValue::String(s) = opt.value { Some(s) } else { None });
+
+    let embed = match zone {
+        None => {
+            let tz = crate::utils::time::user_timezone(db, user_id).await?;
+            create_embed(
+                "Your Timezone",
+                format!(
+                    "Your timezone is {}.\n\nPick one with `/timezone zone:<name>`. Reminders like `tomorrow 9am` are read in this timezone.",
+                    crate::utils::time::describe_timezone(tz, now)
+                ),
+            )
+        }
+        Some(zone) if zone.eq_ignore_ascii_case("reset") => {
+            crate::database::ticket::clear_user_timezone(&db.pool, user_id).await?;
+            create_success_embed("Timezone Reset", "Your timezone is back to **UTC**")
+        }
+        Some(zone) => match crate::utils::time::parse_timezone(zone) {
+            Some(tz) => {
+                crate::database::ticket::set_user_timezone(&db.pool, user_id, tz.name()).await?;
+                create_success_embed("Timezone Set", format!("Your timezone is now {}", crate::utils::time::describe_timezone(tz, now)))
+            }
+            None => create_error_embed(
+                "Unknown Timezone",
+                format!("`{}` isn't a timezone I know. Pick one of the suggestions, e.g. `Europe/London`", zone),
+            ),
+        },
+    };
+
+    interaction
+        .create_response(&ctx.http, serenity::all::CreateInteractionResponse::Message(
+            serenity::all::CreateInteractionResponseMessage::new()
+                .embed(embed)
+                .ephemeral(true)
+        ))
+        .await?;
+
+    Ok(())
+}
+
+pub async fn autocomplete(ctx: &Context, interaction: &CommandInteraction) -> Result<()> {
+    let Some(focused) = interaction.data.autocomplete() else {
+        return Ok(());
+    };
+
+    let response = crate::utils::time::search_timezones(focused.value, 25).into_iter()
+        .fold(CreateAutocompleteResponse::new(), |response, name| response.add_string_choice(name, name));
+
+    interaction.create_response(&ctx.http, CreateInteractionResponse::Autocomplete(response)).await?;
+    Ok(())
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("timezone")
+        .description("Set the timezone used to read times in your reminders")
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "zone",
+                "Timezone name (e.g. Europe/London), or 'reset' for UTC. Leave empty to see your current one"
+            )
+            .required(false)
+            .set_autocomplete(true)
+        )
+}
@@ -17,6 +17,7 @@ pub struct Config {
     pub interaction_webhook: Option<String>,
     pub postgres_webhook: Option<String>,
     pub redis_webhook: Option<String>,
+    pub metrics_addr: Option<String>,
 }
 
 impl Config {
@@ -44,6 +45,7 @@ impl Config {
             interaction_webhook: env::var("INTERACTION_WEBHOOK").ok(),
             postgres_webhook: env::var("POSTGRES_WEBHOOK").ok(),
             redis_webhook: env::var("REDIS_WEBHOOK").ok(),
+            metrics_addr: env::var("METRICS_ADDR").ok(),
         })
     }
 }
@@ -0,0 +1,69 @@
+use anyhow::Result;
+use sqlx::PgPool;
+use crate::models::GuildDigest;
+
+pub async fn get_digest(pool: &PgPool, guild_id: i64) -> Result<Option<GuildDigest>> {
+    let digest = sqlx::query_as::<_, GuildDigest>(
+        "SELECT guild_id, enabled, frequency, weekday, hour, minute, timezone, sla_hours, last_sent_at
+         FROM guild_digests WHERE guild_id = $1"
+    )
+    .bind(guild_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(digest)
+}
+
+pub async fn get_enabled_digests(pool: &PgPool) -> Result<Vec<GuildDigest>> {
+    let digests = sqlx::query_as::<_, GuildDigest>(
+        "SELECT guild_id, enabled, frequency, weekday, hour, minute, timezone, sla_hours, last_sent_at
+         FROM guild_digests WHERE enabled = TRUE"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(digests)
+}
+
+pub async fn upsert_digest(pool: &PgPool, digest: &GuildDigest) -> Result<()> {
+    // last_sent_at is reset so a schedule that has already passed today doesn't fire
+    // straight away; the first digest goes out at the next scheduled time.
+    sqlx::query(
+        "INSERT INTO guild_digests (guild_id, enabled, frequency, weekday, hour, minute, timezone, sla_hours, last_sent_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NOW())
+         ON CONFLICT (guild_id) DO UPDATE
+         SET enabled = $2, frequency = $3, weekday = $4, hour = $5, minute = $6, timezone = $7, sla_hours = $8,
+             last_sent_at = NOW()"
+    )
+    .bind(digest.guild_id)
+    .bind(digest.enabled)
+    .bind(&digest.frequency)
+    .bind(digest.weekday)
+    .bind(digest.hour)
+    .bind(digest.minute)
+    .bind(&digest.timezone)
+    .bind(digest.sla_hours)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn set_digest_enabled(pool: &PgPool, guild_id: i64, enabled: bool) -> Result<bool> {
+    let result = sqlx::query("UPDATE guild_digests SET enabled = $1 WHERE guild_id = $2")
+        .bind(enabled)
+        .bind(guild_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn mark_digest_sent(pool: &PgPool, guild_id: i64) -> Result<()> {
+    sqlx::query("UPDATE guild_digests SET last_sent_at = NOW() WHERE guild_id = $1")
+        .bind(guild_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
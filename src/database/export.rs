@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct TicketExportRow {
+    pub ticket_number: i32,
+    pub owner_id: i64,
+    pub category: Option<String>,
+    pub priority: Option<String>,
+    pub claimed_by: Option<i64>,
+    pub assigned_to: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub rating: Option<i32>,
+    pub message_count: i64,
+}
+
+/// Streams open and closed tickets created in `[start, end)`, oldest first.
+pub fn stream_ticket_export(
+    pool: &PgPool,
+    guild_id: i64,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> BoxStream<'_, Result<TicketExportRow, sqlx::Error>> {
+    sqlx::query_as::<_, TicketExportRow>(
+        "SELECT t.ticket_number, t.owner_id, c.name AS category, t.priority, t.claimed_by, t.assigned_to,
+                t.created_at, t.closed_at, t.rating, t.message_count
+         FROM (
+            SELECT ticket_number, owner_id, category_id, priority, claimed_by, assigned_to,
+                   created_at, NULL::TIMESTAMPTZ AS closed_at, rating,
+                   (SELECT COUNT(*) FROM ticket_messages m WHERE m.ticket_id = tickets.id) AS message_count
+            FROM tickets WHERE guild_id = $1
+            UNION ALL
+            SELECT ticket_number, owner_id, category_id, priority, claimed_by, assigned_to,
+                   opened_at, closed_at, rating, message_count::BIGINT
+            FROM ticket_history WHERE guild_id = $1
+         ) t
+         LEFT JOIN ticket_categories c ON c.id = t.category_id
+         WHERE t.created_at >= $2 AND t.created_at < $3
+         ORDER BY t.created_at ASC"
+    )
+    .bind(guild_id)
+    .bind(start)
+    .bind(end)
+    .fetch(pool)
+}
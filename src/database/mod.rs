@@ -5,6 +5,10 @@ use sqlx::{postgres::PgPoolOptions, PgPool};
 pub mod ticket;
 pub mod tag;
 pub mod ignore;
+pub mod staff;
+pub mod stats;
+pub mod export;
+pub mod digest;
 
 #[derive(Clone)]
 pub struct Database {
@@ -32,4 +36,20 @@ impl Database {
 
         Ok(Self { pool, redis })
     }
+
+    /// Takes a Redis lock for `ttl` so that only one bot instance runs a periodic job
+    /// per tick. If Redis can't be reached the job runs anyway, as a lone instance would.
+    pub async fn try_job_lock(&self, job: &str, ttl: std::time::Duration) -> bool {
+        let mut redis = self.redis.clone();
+        let reply: redis::RedisResult<Option<String>> = redis::cmd("SET")
+            .arg(format!("job_lock:{}", job))
+            .arg(std::process::id())
+            .arg("NX")
+            .arg("PX")
+            .arg(ttl.as_millis() as u64)
+            .query_async(&mut redis)
+            .await;
+
+        reply.map(|r| r.is_some()).unwrap_or(true)
+    }
 }
@@ -0,0 +1,93 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use std::collections::HashSet;
+use crate::models::StaffAway;
+
+pub async fn set_away(
+    pool: &PgPool,
+    guild_id: i64,
+    user_id: i64,
+    reason: Option<&str>,
+    away_until: Option<DateTime<Utc>>,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO staff_away (guild_id, user_id, reason, away_until)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (guild_id, user_id)
+         DO UPDATE SET reason = $3, away_until = $4, created_at = NOW()"
+    )
+    .bind(guild_id)
+    .bind(user_id)
+    .bind(reason)
+    .bind(away_until)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn clear_away(pool: &PgPool, guild_id: i64, user_id: i64) -> Result<bool> {
+    let result = sqlx::query(
+        "DELETE FROM staff_away WHERE guild_id = $1 AND user_id = $2"
+    )
+    .bind(guild_id)
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get_away_status(pool: &PgPool, guild_id: i64, user_id: i64) -> Result<Option<StaffAway>> {
+    let status = sqlx::query_as::<_, StaffAway>(
+        "SELECT id, guild_id, user_id, reason, away_until, created_at
+         FROM staff_away
+         WHERE guild_id = $1 AND user_id = $2
+         AND (away_until IS NULL OR away_until > NOW())"
+    )
+    .bind(guild_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(status)
+}
+
+pub async fn get_away_staff(pool: &PgPool, guild_id: i64) -> Result<Vec<StaffAway>> {
+    let staff = sqlx::query_as::<_, StaffAway>(
+        "SELECT id, guild_id, user_id, reason, away_until, created_at
+         FROM staff_away
+         WHERE guild_id = $1
+         AND (away_until IS NULL OR away_until > NOW())
+         ORDER BY created_at ASC"
+    )
+    .bind(guild_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(staff)
+}
+
+pub async fn get_away_user_ids(pool: &PgPool, guild_id: i64) -> Result<HashSet<i64>> {
+    let users: Vec<(i64,)> = sqlx::query_as(
+        "SELECT user_id FROM staff_away
+         WHERE guild_id = $1
+         AND (away_until IS NULL OR away_until > NOW())"
+    )
+    .bind(guild_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(users.into_iter().map(|u| u.0).collect())
+}
+
+pub async fn clear_expired_away(pool: &PgPool) -> Result<u64> {
+    let result = sqlx::query(
+        "DELETE FROM staff_away WHERE away_until IS NOT NULL AND away_until <= NOW()"
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
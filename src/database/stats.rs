@@ -0,0 +1,407 @@
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{FromRow, PgPool};
+
+// Open tickets live in `tickets`, closed ones only in `ticket_history`, so every
+// windowed query reads from both.
+const ALL_TICKETS: &str = "
+    WITH all_tickets AS (
+        SELECT category_id, priority, created_at AS opened_at, NULL::TIMESTAMPTZ AS closed_at
+        FROM tickets WHERE guild_id = $1
+        UNION ALL
+        SELECT category_id, priority, opened_at, closed_at
+        FROM ticket_history WHERE guild_id = $1
+    )";
+
+#[derive(Debug, Clone)]
+pub struct DailyCount {
+    pub day: NaiveDate,
+    pub opened: i64,
+    pub closed: i64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PeriodStats {
+    pub opened: i64,
+    pub closed: i64,
+    pub avg_resolution_seconds: Option<f64>,
+    pub daily: Vec<DailyCount>,
+    pub by_category: Vec<(String, i64)>,
+    pub by_priority: Vec<(String, i64)>,
+    pub busiest_hours: Vec<(i32, i64)>,
+}
+
+pub async fn count_opened_closed(
+    pool: &PgPool,
+    guild_id: i64,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<(i64, i64)> {
+    let counts: (i64, i64) = sqlx::query_as(&format!(
+        "{ALL_TICKETS}
+         SELECT
+            COUNT(*) FILTER (WHERE opened_at >= $2 AND opened_at < $3),
+            COUNT(*) FILTER (WHERE closed_at >= $2 AND closed_at < $3)
+         FROM all_tickets"
+    ))
+    .bind(guild_id)
+    .bind(start)
+    .bind(end)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(counts)
+}
+
+pub async fn get_period_stats(
+    pool: &PgPool,
+    guild_id: i64,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<PeriodStats> {
+    let (opened, closed) = count_opened_closed(pool, guild_id, start, end).await?;
+
+    let avg_resolution: (Option<f64>,) = sqlx::query_as(
+        "SELECT AVG(EXTRACT(EPOCH FROM (closed_at - opened_at)))::FLOAT8
+         FROM ticket_history
+         WHERE guild_id = $1 AND closed_at >= $2 AND closed_at < $3"
+    )
+    .bind(guild_id)
+    .bind(start)
+    .bind(end)
+    .fetch_one(pool)
+    .await?;
+
+    let daily: Vec<(NaiveDate, i64, i64)> = sqlx::query_as(&format!(
+        "{ALL_TICKETS}
+         SELECT day::DATE,
+                COUNT(*) FILTER (WHERE opened_at >= GREATEST(day, $2) AND opened_at < LEAST(day + INTERVAL '1 day', $3)),
+                COUNT(*) FILTER (WHERE closed_at >= GREATEST(day, $2) AND closed_at < LEAST(day + INTERVAL '1 day', $3))
+         FROM generate_series(date_trunc('day', $2::TIMESTAMPTZ), $3::TIMESTAMPTZ - INTERVAL '1 microsecond', INTERVAL '1 day') AS day
+         LEFT JOIN all_tickets
+            ON (opened_at >= day AND opened_at < day + INTERVAL '1 day')
+            OR (closed_at >= day AND closed_at < day + INTERVAL '1 day')
+         GROUP BY day
+         ORDER BY day"
+    ))
+    .bind(guild_id)
+    .bind(start)
+    .bind(end)
+    .fetch_all(pool)
+    .await?;
+
+    let by_category: Vec<(String, i64)> = sqlx::query_as(&format!(
+        "{ALL_TICKETS}
+         SELECT COALESCE(c.name, 'Uncategorized'), COUNT(*)
+         FROM all_tickets t
+         LEFT JOIN ticket_categories c ON c.id = t.category_id
+         WHERE t.opened_at >= $2 AND t.opened_at < $3
+         GROUP BY 1
+         ORDER BY 2 DESC"
+    ))
+    .bind(guild_id)
+    .bind(start)
+    .bind(end)
+    .fetch_all(pool)
+    .await?;
+
+    let by_priority: Vec<(String, i64)> = sqlx::query_as(&format!(
+        "{ALL_TICKETS}
+         SELECT COALESCE(priority, 'normal'), COUNT(*)
+         FROM all_tickets
+         WHERE opened_at >= $2 AND opened_at < $3
+         GROUP BY 1
+         ORDER BY 2 DESC"
+    ))
+    .bind(guild_id)
+    .bind(start)
+    .bind(end)
+    .fetch_all(pool)
+    .await?;
+
+    let busiest_hours: Vec<(i32, i64)> = sqlx::query_as(&format!(
+        "{ALL_TICKETS}
+         SELECT EXTRACT(HOUR FROM opened_at AT TIME ZONE 'UTC')::INT, COUNT(*)
+         FROM all_tickets
+         WHERE opened_at >= $2 AND opened_at < $3
+         GROUP BY 1
+         ORDER BY 2 DESC, 1 ASC
+         LIMIT 3"
+    ))
+    .bind(guild_id)
+    .bind(start)
+    .bind(end)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(PeriodStats {
+        opened,
+        closed,
+        avg_resolution_seconds: avg_resolution.0,
+        daily: daily
+            .into_iter()
+            .map(|(day, opened, closed)| DailyCount { day, opened, closed })
+            .collect(),
+        by_category,
+        by_priority,
+        busiest_hours,
+    })
+}
+
+pub async fn record_staff_activity(pool: &PgPool, guild_id: i64, user_id: i64, messages: i32, notes: i32) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO staff_daily_activity (guild_id, user_id, day, messages, notes)
+         VALUES ($1, $2, CURRENT_DATE, $3, $4)
+         ON CONFLICT (guild_id, user_id, day) DO UPDATE
+         SET messages = staff_daily_activity.messages + $3,
+             notes = staff_daily_activity.notes + $4"
+    )
+    .bind(guild_id)
+    .bind(user_id)
+    .bind(messages)
+    .bind(notes)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StaffStats {
+    pub user_id: i64,
+    pub claimed: i64,
+    pub assigned: i64,
+    pub closed: i64,
+    pub messages: i64,
+    pub notes: i64,
+    pub avg_first_response_seconds: Option<f64>,
+}
+
+pub async fn get_staff_stats(
+    pool: &PgPool,
+    guild_id: i64,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<StaffStats>> {
+    let stats = sqlx::query_as::<_, StaffStats>(
+        "WITH all_tickets AS (
+            SELECT created_at AS opened_at, NULL::TIMESTAMPTZ AS closed_at, NULL::BIGINT AS closed_by,
+                   claimed_by, claimed_at, assigned_to, assigned_at, first_responder_id, first_response_at
+            FROM tickets WHERE guild_id = $1
+            UNION ALL
+            SELECT opened_at, closed_at, closed_by,
+                   claimed_by, claimed_at, assigned_to, assigned_at, first_responder_id, first_response_at
+            FROM ticket_history WHERE guild_id = $1
+        ),
+        claimed AS (
+            SELECT claimed_by AS user_id, COUNT(*) AS n FROM all_tickets
+            WHERE claimed_by IS NOT NULL AND claimed_at >= $2 AND claimed_at < $3 GROUP BY 1
+        ),
+        assigned AS (
+            SELECT assigned_to AS user_id, COUNT(*) AS n FROM all_tickets
+            WHERE assigned_to IS NOT NULL AND assigned_at >= $2 AND assigned_at < $3 GROUP BY 1
+        ),
+        closed AS (
+            SELECT closed_by AS user_id, COUNT(*) AS n FROM all_tickets
+            WHERE closed_by IS NOT NULL AND closed_at >= $2 AND closed_at < $3 GROUP BY 1
+        ),
+        responses AS (
+            SELECT first_responder_id AS user_id,
+                   AVG(EXTRACT(EPOCH FROM (first_response_at - opened_at)))::FLOAT8 AS seconds
+            FROM all_tickets
+            WHERE first_responder_id IS NOT NULL AND first_response_at >= $2 AND first_response_at < $3 GROUP BY 1
+        ),
+        activity AS (
+            SELECT user_id, SUM(messages)::BIGINT AS messages, SUM(notes)::BIGINT AS notes
+            FROM staff_daily_activity
+            WHERE guild_id = $1 AND day >= $2::DATE AND day <= ($3 - INTERVAL '1 microsecond')::DATE GROUP BY 1
+        ),
+        users AS (
+            SELECT user_id FROM claimed UNION SELECT user_id FROM assigned UNION SELECT user_id FROM closed
+            UNION SELECT user_id FROM responses UNION SELECT user_id FROM activity
+        )
+        SELECT u.user_id,
+               COALESCE(c.n, 0) AS claimed,
+               COALESCE(a.n, 0) AS assigned,
+               COALESCE(cl.n, 0) AS closed,
+               COALESCE(act.messages, 0) AS messages,
+               COALESCE(act.notes, 0) AS notes,
+               r.seconds AS avg_first_response_seconds
+        FROM users u
+        LEFT JOIN claimed c ON c.user_id = u.user_id
+        LEFT JOIN assigned a ON a.user_id = u.user_id
+        LEFT JOIN closed cl ON cl.user_id = u.user_id
+        LEFT JOIN responses r ON r.user_id = u.user_id
+        LEFT JOIN activity act ON act.user_id = u.user_id"
+    )
+    .bind(guild_id)
+    .bind(start)
+    .bind(end)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(stats)
+}
+
+/// Returns `(open, unclaimed)` counts for tickets that are currently open.
+pub async fn get_backlog(pool: &PgPool, guild_id: i64) -> Result<(i64, i64)> {
+    let backlog: (i64, i64) = sqlx::query_as(
+        "SELECT COUNT(*), COUNT(*) FILTER (WHERE claimed_by IS NULL)
+         FROM tickets WHERE guild_id = $1 AND status = 'open'"
+    )
+    .bind(guild_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(backlog)
+}
+
+/// Returns `(ticket_number, channel_id, created_at)` for the oldest unclaimed open tickets.
+pub async fn get_oldest_unclaimed(pool: &PgPool, guild_id: i64, limit: i64) -> Result<Vec<(i32, i64, DateTime<Utc>)>> {
+    let tickets: Vec<(i32, i64, DateTime<Utc>)> = sqlx::query_as(
+        "SELECT ticket_number, channel_id, created_at
+         FROM tickets
+         WHERE guild_id = $1 AND status = 'open' AND claimed_by IS NULL
+         ORDER BY created_at ASC
+         LIMIT $2"
+    )
+    .bind(guild_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tickets)
+}
+
+/// Counts tickets opened in `[start, end)` whose first staff response took longer
+/// than `sla_hours`, including ones that are still waiting past the deadline.
+pub async fn count_sla_breaches(
+    pool: &PgPool,
+    guild_id: i64,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    sla_hours: i32,
+) -> Result<i64> {
+    let breaches: (i64,) = sqlx::query_as(
+        "WITH all_tickets AS (
+            SELECT created_at AS opened_at, first_response_at, TRUE AS is_open
+            FROM tickets WHERE guild_id = $1
+            UNION ALL
+            SELECT opened_at, first_response_at, FALSE
+            FROM ticket_history WHERE guild_id = $1
+        )
+        SELECT COUNT(*) FROM all_tickets
+        WHERE opened_at >= $2 AND opened_at < $3
+        AND (
+            first_response_at - opened_at > make_interval(hours => $4)
+            OR (first_response_at IS NULL AND is_open AND NOW() - opened_at > make_interval(hours => $4))
+        )"
+    )
+    .bind(guild_id)
+    .bind(start)
+    .bind(end)
+    .bind(sla_hours)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(breaches.0)
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct GlobalTotals {
+    pub open: i64,
+    pub closed: i64,
+    pub messages: i64,
+    pub avg_resolution_seconds: Option<f64>,
+}
+
+pub async fn get_global_totals(pool: &PgPool) -> Result<GlobalTotals> {
+    // Messages of closed tickets are deleted with them, so their archived count is added back.
+    let totals = sqlx::query_as::<_, GlobalTotals>(
+        "SELECT
+            (SELECT COUNT(*) FROM tickets) AS open,
+            (SELECT COUNT(*) FROM ticket_history) AS closed,
+            (SELECT COUNT(*) FROM ticket_messages)
+                + (SELECT COALESCE(SUM(message_count), 0) FROM ticket_history)::BIGINT AS messages,
+            (SELECT AVG(EXTRACT(EPOCH FROM (closed_at - opened_at)))::FLOAT8 FROM ticket_history) AS avg_resolution_seconds"
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(totals)
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct GuildActivity {
+    pub guild_id: i64,
+    pub total: i64,
+    pub open: i64,
+    pub last_30d: i64,
+    pub prev_30d: i64,
+    pub last_ticket_at: Option<DateTime<Utc>>,
+    pub premium: bool,
+    pub has_category: bool,
+    pub has_support_role: bool,
+}
+
+/// Per-guild ticket volume and setup state for every guild with a settings row,
+/// busiest first.
+pub async fn get_guild_activity(pool: &PgPool) -> Result<Vec<GuildActivity>> {
+    let guilds = sqlx::query_as::<_, GuildActivity>(
+        "WITH all_tickets AS (
+            SELECT guild_id, created_at AS opened_at FROM tickets
+            UNION ALL
+            SELECT guild_id, opened_at FROM ticket_history
+        ),
+        volume AS (
+            SELECT guild_id,
+                   COUNT(*) AS total,
+                   COUNT(*) FILTER (WHERE opened_at >= NOW() - INTERVAL '30 days') AS last_30d,
+                   COUNT(*) FILTER (WHERE opened_at >= NOW() - INTERVAL '60 days' AND opened_at < NOW() - INTERVAL '30 days') AS prev_30d,
+                   MAX(opened_at) AS last_ticket_at
+            FROM all_tickets
+            GROUP BY guild_id
+        )
+        SELECT g.guild_id,
+               COALESCE(v.total, 0) AS total,
+               (SELECT COUNT(*) FROM tickets t WHERE t.guild_id = g.guild_id) AS open,
+               COALESCE(v.last_30d, 0) AS last_30d,
+               COALESCE(v.prev_30d, 0) AS prev_30d,
+               v.last_ticket_at,
+               EXISTS (SELECT 1 FROM premium p WHERE p.guild_id = g.guild_id AND p.expires_at > NOW()) AS premium,
+               g.ticket_category_id IS NOT NULL AS has_category,
+               EXISTS (SELECT 1 FROM support_roles r WHERE r.guild_id = g.guild_id) AS has_support_role
+        FROM guilds g
+        LEFT JOIN volume v ON v.guild_id = g.guild_id
+        ORDER BY total DESC, g.guild_id"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(guilds)
+}
+
+/// Tickets opened across all guilds per week, oldest first, including empty weeks.
+pub async fn get_weekly_ticket_counts(pool: &PgPool, weeks: i32) -> Result<Vec<(NaiveDate, i64)>> {
+    let counts: Vec<(NaiveDate, i64)> = sqlx::query_as(
+        "WITH all_tickets AS (
+            SELECT created_at AS opened_at FROM tickets
+            UNION ALL
+            SELECT opened_at FROM ticket_history
+        )
+        SELECT week::DATE, COUNT(t.opened_at)
+        FROM generate_series(
+            date_trunc('week', NOW()) - make_interval(weeks => $1 - 1),
+            date_trunc('week', NOW()),
+            INTERVAL '1 week'
+        ) AS week
+        LEFT JOIN all_tickets t ON t.opened_at >= week AND t.opened_at < week + INTERVAL '1 week'
+        GROUP BY week
+        ORDER BY week"
+    )
+    .bind(weeks)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(counts)
+}
@@ -1,43 +1,166 @@
 use anyhow::Result;
-use sqlx::PgPool;
-use crate::models::Tag;
+use sqlx::types::Json;
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+use crate::models::{NewTagTrigger, Tag, TagEmbed, TagFolder, TagLibraryEntry, TagRevision, TagTrigger};
 
-pub async fn create_tag(pool: &PgPool, guild_id: i64, name: &str, content: &str, creator_id: i64) -> Result<Tag> {
+pub async fn create_tag<'e>(executor: impl PgExecutor<'e>, guild_id: i64, name: &str, content: &str, creator_id: i64) -> Result<Tag> {
     let tag = sqlx::query_as::<_, Tag>(
-        "INSERT INTO tags (guild_id, name, content, creator_id) VALUES ($1, $2, $3, $4) RETURNING id, guild_id, name, content, creator_id, created_at, updated_at, uses"
+        "INSERT INTO tags (guild_id, name, content, creator_id) VALUES ($1, $2, $3, $4) RETURNING id, guild_id, name, content, creator_id, created_at, updated_at, uses, embed"
     )
     .bind(guild_id)
     .bind(name)
     .bind(content)
     .bind(creator_id)
-    .fetch_one(pool)
+    .fetch_one(executor)
     .await?;
     Ok(tag)
 }
 
-pub async fn get_tag(pool: &PgPool, guild_id: i64, name: &str) -> Result<Option<Tag>> {
+/// Looks a tag up by name or alias.
+pub async fn get_tag<'e>(executor: impl PgExecutor<'e>, guild_id: i64, name: &str) -> Result<Option<Tag>> {
     let tag = sqlx::query_as::<_, Tag>(
-        "SELECT id, guild_id, name, content, creator_id, created_at, updated_at, uses FROM tags WHERE guild_id = $1 AND LOWER(name) = LOWER($2)"
+        "SELECT id, guild_id, name, content, creator_id, created_at, updated_at, uses, embed FROM tags
+         WHERE guild_id = $1 AND (
+            LOWER(name) = LOWER($2)
+            OR id = (SELECT tag_id FROM tag_aliases WHERE guild_id = $1 AND LOWER(alias) = LOWER($2))
+         )"
     )
     .bind(guild_id)
     .bind(name)
-    .fetch_optional(pool)
+    .fetch_optional(executor)
     .await?;
     Ok(tag)
 }
 
-pub async fn update_tag(pool: &PgPool, guild_id: i64, name: &str, content: &str) -> Result<()> {
+/// Copies a tag's current content into `tag_revisions` before it is overwritten.
+async fn save_revision(tx: &mut Transaction<'_, Postgres>, guild_id: i64, name: &str, replaced_by: i64) -> Result<()> {
+    // Lock the tag row so concurrent edits number their revisions one after the other.
+    sqlx::query("SELECT id FROM tags WHERE guild_id = $1 AND LOWER(name) = LOWER($2) FOR UPDATE")
+        .bind(guild_id)
+        .bind(name)
+        .fetch_optional(&mut **tx)
+        .await?;
+
+    sqlx::query(
+        "INSERT INTO tag_revisions (tag_id, guild_id, revision, content, embed, replaced_by)
+         SELECT t.id, t.guild_id, COALESCE((SELECT MAX(r.revision) FROM tag_revisions r WHERE r.tag_id = t.id), 0) + 1,
+                t.content, t.embed, $3
+         FROM tags t WHERE t.guild_id = $1 AND LOWER(t.name) = LOWER($2)"
+    )
+    .bind(guild_id)
+    .bind(name)
+    .bind(replaced_by)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+pub async fn update_tag(pool: &PgPool, guild_id: i64, name: &str, content: &str, edited_by: i64) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    save_revision(&mut tx, guild_id, name, edited_by).await?;
+
     sqlx::query(
-        "UPDATE tags SET content = $1, updated_at = NOW() WHERE guild_id = $2 AND LOWER(name) = LOWER($3)"
+        "UPDATE tags SET content = $1, library_tag_id = NULL, updated_at = NOW() WHERE guild_id = $2 AND LOWER(name) = LOWER($3)"
     )
     .bind(content)
     .bind(guild_id)
     .bind(name)
-    .execute(pool)
+    .execute(&mut *tx)
     .await?;
+
+    tx.commit().await?;
     Ok(())
 }
 
+pub async fn create_embed_tag<'e>(executor: impl PgExecutor<'e>, guild_id: i64, name: &str, embed: &TagEmbed, creator_id: i64) -> Result<Tag> {
+    let tag = sqlx::query_as::<_, Tag>(
+        "INSERT INTO tags (guild_id, name, content, embed, creator_id) VALUES ($1, $2, $3, $4, $5) RETURNING id, guild_id, name, content, creator_id, created_at, updated_at, uses, embed"
+    )
+    .bind(guild_id)
+    .bind(name)
+    .bind(embed.description.as_deref().unwrap_or_default())
+    .bind(Json(embed))
+    .bind(creator_id)
+    .fetch_one(executor)
+    .await?;
+    Ok(tag)
+}
+
+/// Stores an embed tag. `content` mirrors the description so search keeps working.
+pub async fn set_tag_embed(pool: &PgPool, guild_id: i64, name: &str, embed: &TagEmbed, edited_by: i64) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    save_revision(&mut tx, guild_id, name, edited_by).await?;
+
+    sqlx::query(
+        "UPDATE tags SET content = $1, embed = $2, library_tag_id = NULL, updated_at = NOW() WHERE guild_id = $3 AND LOWER(name) = LOWER($4)"
+    )
+    .bind(embed.description.as_deref().unwrap_or_default())
+    .bind(Json(embed))
+    .bind(guild_id)
+    .bind(name)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Puts an earlier revision back. The content being replaced becomes a new revision,
+/// so a rollback can itself be undone.
+pub async fn restore_tag_revision(pool: &PgPool, guild_id: i64, name: &str, revision: &TagRevision, edited_by: i64) -> Result<()> {
+    overwrite_tag(pool, guild_id, name, &revision.content, revision.embed.as_deref(), edited_by).await
+}
+
+/// Replaces both the text and the embed of a tag, saving the old version as a revision.
+pub async fn overwrite_tag(pool: &PgPool, guild_id: i64, name: &str, content: &str, embed: Option<&TagEmbed>, edited_by: i64) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    overwrite_tag_in(&mut tx, guild_id, name, content, embed, edited_by).await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// `overwrite_tag` inside a transaction the caller commits.
+pub async fn overwrite_tag_in(tx: &mut Transaction<'_, Postgres>, guild_id: i64, name: &str, content: &str, embed: Option<&TagEmbed>, edited_by: i64) -> Result<()> {
+    save_revision(tx, guild_id, name, edited_by).await?;
+
+    sqlx::query(
+        "UPDATE tags SET content = $1, embed = $2, library_tag_id = NULL, updated_at = NOW() WHERE guild_id = $3 AND LOWER(name) = LOWER($4)"
+    )
+    .bind(content)
+    .bind(embed.map(Json))
+    .bind(guild_id)
+    .bind(name)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Most recent revisions first.
+pub async fn get_tag_revisions(pool: &PgPool, tag_id: Uuid, limit: i64) -> Result<Vec<TagRevision>> {
+    let revisions = sqlx::query_as::<_, TagRevision>(
+        "SELECT revision, content, embed, replaced_by, created_at FROM tag_revisions
+         WHERE tag_id = $1 ORDER BY revision DESC LIMIT $2"
+    )
+    .bind(tag_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(revisions)
+}
+
+pub async fn get_tag_revision(pool: &PgPool, tag_id: Uuid, revision: i32) -> Result<Option<TagRevision>> {
+    let revision = sqlx::query_as::<_, TagRevision>(
+        "SELECT revision, content, embed, replaced_by, created_at FROM tag_revisions
+         WHERE tag_id = $1 AND revision = $2"
+    )
+    .bind(tag_id)
+    .bind(revision)
+    .fetch_optional(pool)
+    .await?;
+    Ok(revision)
+}
+
 pub async fn delete_tag(pool: &PgPool, guild_id: i64, name: &str) -> Result<bool> {
     let result = sqlx::query(
         "DELETE FROM tags WHERE guild_id = $1 AND LOWER(name) = LOWER($2)"
@@ -62,7 +185,7 @@ pub async fn increment_tag_uses(pool: &PgPool, guild_id: i64, name: &str) -> Res
 
 pub async fn list_tags(pool: &PgPool, guild_id: i64) -> Result<Vec<Tag>> {
     let tags = sqlx::query_as::<_, Tag>(
-        "SELECT id, guild_id, name, content, creator_id, created_at, updated_at, uses FROM tags WHERE guild_id = $1 ORDER BY name ASC"
+        "SELECT id, guild_id, name, content, creator_id, created_at, updated_at, uses, embed FROM tags WHERE guild_id = $1 ORDER BY name ASC"
     )
     .bind(guild_id)
     .fetch_all(pool)
@@ -70,32 +193,87 @@ pub async fn list_tags(pool: &PgPool, guild_id: i64) -> Result<Vec<Tag>> {
     Ok(tags)
 }
 
-pub async fn get_tag_info(pool: &PgPool, guild_id: i64, name: &str) -> Result<Option<Tag>> {
-    let tag = sqlx::query_as::<_, Tag>(
-        "SELECT id, guild_id, name, content, creator_id, created_at, updated_at, uses FROM tags WHERE guild_id = $1 AND LOWER(name) = LOWER($2)"
-    )
+/// One page of tags ordered by name, plus the total. `folder_id` limits it to one
+/// folder and `unfiled` to tags without a folder.
+pub async fn list_tags_page(
+    pool: &PgPool,
+    guild_id: i64,
+    folder_id: Option<Uuid>,
+    unfiled: bool,
+    offset: i64,
+    limit: i64,
+) -> Result<(Vec<Tag>, i64)> {
+    let filter = "guild_id = $1 AND ($2::uuid IS NULL OR folder_id = $2) AND (NOT $3 OR folder_id IS NULL)";
+
+    let tags = sqlx::query_as::<_, Tag>(&format!(
+        "SELECT id, guild_id, name, content, creator_id, created_at, updated_at, uses, embed FROM tags
+         WHERE {} ORDER BY LOWER(name) ASC LIMIT $4 OFFSET $5",
+        filter
+    ))
     .bind(guild_id)
-    .bind(name)
-    .fetch_optional(pool)
+    .bind(folder_id)
+    .bind(unfiled)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
     .await?;
-    Ok(tag)
+
+    let (total,): (i64,) = sqlx::query_as(&format!("SELECT COUNT(*) FROM tags WHERE {}", filter))
+        .bind(guild_id)
+        .bind(folder_id)
+        .bind(unfiled)
+        .fetch_one(pool)
+        .await?;
+
+    Ok((tags, total))
+}
+
+pub async fn get_tag_info(pool: &PgPool, guild_id: i64, name: &str) -> Result<Option<Tag>> {
+    get_tag(pool, guild_id, name).await
 }
 
 pub async fn search_tags(pool: &PgPool, guild_id: i64, query: &str) -> Result<Vec<Tag>> {
     let pattern = format!("%{}%", query);
     let tags = sqlx::query_as::<_, Tag>(
-        "SELECT id, guild_id, name, content, creator_id, created_at, updated_at, uses FROM tags WHERE guild_id = $1 AND (LOWER(name) LIKE LOWER($2) OR LOWER(content) LIKE LOWER($2)) ORDER BY name ASC LIMIT 25"
+        "SELECT id, guild_id, name, content, creator_id, created_at, updated_at, uses, embed FROM tags
+         WHERE guild_id = $1 AND (LOWER(name) LIKE LOWER($2) OR LOWER(content) LIKE LOWER($2) OR similarity(LOWER(name), LOWER($3)) > 0.3)
+         ORDER BY similarity(LOWER(name), LOWER($3)) DESC, uses DESC, name ASC LIMIT 25"
     )
     .bind(guild_id)
     .bind(pattern)
+    .bind(query)
     .fetch_all(pool)
     .await?;
     Ok(tags)
 }
 
+/// Tag names ranked by how closely the name or one of its aliases matches `query`,
+/// then by uses. Names starting with the query rank first; an empty query returns
+/// the most used tags.
+pub async fn fuzzy_tag_names(pool: &PgPool, guild_id: i64, query: &str, limit: i64) -> Result<Vec<String>> {
+    let names: Vec<(String,)> = sqlx::query_as(
+        "WITH terms AS (
+            SELECT t.name, t.uses, LOWER(t.name) AS term FROM tags t WHERE t.guild_id = $1
+            UNION ALL
+            SELECT t.name, t.uses, LOWER(a.alias) FROM tag_aliases a JOIN tags t ON t.id = a.tag_id WHERE a.guild_id = $1
+         )
+         SELECT name FROM terms
+         WHERE $2 = '' OR strpos(term, $2) > 0 OR similarity(term, $2) > 0.2
+         GROUP BY name, uses
+         ORDER BY MAX(similarity(term, $2) + CASE WHEN strpos(term, $2) = 1 THEN 1 ELSE 0 END) DESC, uses DESC, name ASC
+         LIMIT $3"
+    )
+    .bind(guild_id)
+    .bind(query.to_lowercase())
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(names.into_iter().map(|(name,)| name).collect())
+}
+
 pub async fn get_popular_tags(pool: &PgPool, guild_id: i64, limit: i64) -> Result<Vec<Tag>> {
     let tags = sqlx::query_as::<_, Tag>(
-        "SELECT id, guild_id, name, content, creator_id, created_at, updated_at, uses FROM tags WHERE guild_id = $1 ORDER BY uses DESC LIMIT $2"
+        "SELECT id, guild_id, name, content, creator_id, created_at, updated_at, uses, embed FROM tags WHERE guild_id = $1 ORDER BY uses DESC LIMIT $2"
     )
     .bind(guild_id)
     .bind(limit)
@@ -104,9 +282,11 @@ pub async fn get_popular_tags(pool: &PgPool, guild_id: i64, limit: i64) -> Resul
     Ok(tags)
 }
 
+/// A renamed library tag becomes the guild's own, so it no longer follows library
+/// updates or gets removed on unsubscribe.
 pub async fn rename_tag(pool: &PgPool, guild_id: i64, old_name: &str, new_name: &str) -> Result<bool> {
     let result = sqlx::query(
-        "UPDATE tags SET name = $1, updated_at = NOW() WHERE guild_id = $2 AND LOWER(name) = LOWER($3)"
+        "UPDATE tags SET name = $1, library_tag_id = NULL, updated_at = NOW() WHERE guild_id = $2 AND LOWER(name) = LOWER($3)"
     )
     .bind(new_name)
     .bind(guild_id)
@@ -115,3 +295,357 @@ pub async fn rename_tag(pool: &PgPool, guild_id: i64, old_name: &str, new_name:
     .await?;
     Ok(result.rows_affected() > 0)
 }
+
+pub async fn add_tag_alias<'e>(executor: impl PgExecutor<'e>, guild_id: i64, tag_id: Uuid, alias: &str, created_by: i64) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO tag_aliases (tag_id, guild_id, alias, created_by) VALUES ($1, $2, $3, $4)"
+    )
+    .bind(tag_id)
+    .bind(guild_id)
+    .bind(alias)
+    .bind(created_by)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+pub async fn remove_tag_alias(pool: &PgPool, guild_id: i64, alias: &str) -> Result<bool> {
+    let result = sqlx::query(
+        "DELETE FROM tag_aliases WHERE guild_id = $1 AND LOWER(alias) = LOWER($2)"
+    )
+    .bind(guild_id)
+    .bind(alias)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get_tag_aliases(pool: &PgPool, tag_id: Uuid) -> Result<Vec<String>> {
+    let aliases: Vec<(String,)> = sqlx::query_as(
+        "SELECT alias FROM tag_aliases WHERE tag_id = $1 ORDER BY alias ASC"
+    )
+    .bind(tag_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(aliases.into_iter().map(|(alias,)| alias).collect())
+}
+
+/// Every alias in the guild as `(tag_id, alias)`.
+pub async fn get_guild_tag_aliases(pool: &PgPool, guild_id: i64) -> Result<Vec<(Uuid, String)>> {
+    let aliases = sqlx::query_as(
+        "SELECT tag_id, alias FROM tag_aliases WHERE guild_id = $1 ORDER BY alias ASC"
+    )
+    .bind(guild_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(aliases)
+}
+
+pub async fn add_tag_trigger(pool: &PgPool, guild_id: i64, trigger: &NewTagTrigger, created_by: i64) -> Result<Uuid> {
+    let id = sqlx::query_scalar(
+        "INSERT INTO tag_triggers (guild_id, tag_id, pattern, is_regex, category_id, cooldown_seconds, first_message_only, created_by)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8) RETURNING id"
+    )
+    .bind(guild_id)
+    .bind(trigger.tag_id)
+    .bind(&trigger.pattern)
+    .bind(trigger.is_regex)
+    .bind(trigger.category_id)
+    .bind(trigger.cooldown_seconds)
+    .bind(trigger.first_message_only)
+    .bind(created_by)
+    .fetch_one(pool)
+    .await?;
+    Ok(id)
+}
+
+/// Auto-responder rules for a guild, oldest first. List positions are what users
+/// pass to remove a rule.
+pub async fn get_tag_triggers(pool: &PgPool, guild_id: i64) -> Result<Vec<TagTrigger>> {
+    let triggers = sqlx::query_as::<_, TagTrigger>(
+        "SELECT tr.id, tr.guild_id, t.name AS tag_name, tr.pattern, tr.is_regex, tr.category_id,
+                c.name AS category_name, tr.cooldown_seconds, tr.first_message_only
+         FROM tag_triggers tr
+         JOIN tags t ON t.id = tr.tag_id
+         LEFT JOIN ticket_categories c ON c.id = tr.category_id
+         WHERE tr.guild_id = $1
+         ORDER BY tr.created_at ASC"
+    )
+    .bind(guild_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(triggers)
+}
+
+pub async fn remove_tag_trigger(pool: &PgPool, guild_id: i64, trigger_id: Uuid) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM tag_triggers WHERE guild_id = $1 AND id = $2")
+        .bind(guild_id)
+        .bind(trigger_id)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get_tag_permission_roles(pool: &PgPool, guild_id: i64) -> Result<Vec<(String, i64)>> {
+    let roles = sqlx::query_as(
+        "SELECT action, role_id FROM tag_permission_roles WHERE guild_id = $1 ORDER BY action, role_id"
+    )
+    .bind(guild_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(roles)
+}
+
+pub async fn add_tag_permission_role(pool: &PgPool, guild_id: i64, action: &str, role_id: i64) -> Result<bool> {
+    let result = sqlx::query(
+        "INSERT INTO tag_permission_roles (guild_id, action, role_id) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING"
+    )
+    .bind(guild_id)
+    .bind(action)
+    .bind(role_id)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn remove_tag_permission_role(pool: &PgPool, guild_id: i64, action: &str, role_id: i64) -> Result<bool> {
+    let result = sqlx::query(
+        "DELETE FROM tag_permission_roles WHERE guild_id = $1 AND action = $2 AND role_id = $3"
+    )
+    .bind(guild_id)
+    .bind(action)
+    .bind(role_id)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn list_library_tags(pool: &PgPool) -> Result<Vec<TagLibraryEntry>> {
+    let entries = sqlx::query_as::<_, TagLibraryEntry>(
+        "SELECT name, content, embed FROM tag_library ORDER BY name ASC"
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(entries)
+}
+
+/// Adds or updates a library tag and pushes it to subscribed guilds. Guild copies
+/// follow the update; a new tag is added to every subscribed guild where the name
+/// is free. Returns whether the tag was new.
+pub async fn publish_library_tag(pool: &PgPool, name: &str, content: &str, embed: Option<&TagEmbed>, published_by: i64) -> Result<bool> {
+    let mut tx = pool.begin().await?;
+
+    let (id, inserted): (Uuid, bool) = sqlx::query_as(
+        "INSERT INTO tag_library (name, content, embed, published_by) VALUES ($1, $2, $3, $4)
+         ON CONFLICT ((LOWER(name))) DO UPDATE
+            SET content = EXCLUDED.content, embed = EXCLUDED.embed, published_by = EXCLUDED.published_by, updated_at = NOW()
+         RETURNING id, (xmax = 0)"
+    )
+    .bind(name)
+    .bind(content)
+    .bind(embed.map(Json))
+    .bind(published_by)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if inserted {
+        sqlx::query(
+            "INSERT INTO tags (guild_id, name, content, embed, creator_id, library_tag_id)
+             SELECT s.guild_id, l.name, l.content, l.embed, l.published_by, l.id
+             FROM tag_library_subscriptions s CROSS JOIN tag_library l
+             WHERE l.id = $1
+               AND NOT EXISTS (SELECT 1 FROM tags t WHERE t.guild_id = s.guild_id AND LOWER(t.name) = LOWER(l.name))
+               AND NOT EXISTS (SELECT 1 FROM tag_aliases a WHERE a.guild_id = s.guild_id AND LOWER(a.alias) = LOWER(l.name))"
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    } else {
+        // Same as save_revision, for every guild copy the update is about to replace.
+        sqlx::query("SELECT id FROM tags WHERE library_tag_id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_all(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            "INSERT INTO tag_revisions (tag_id, guild_id, revision, content, embed, replaced_by)
+             SELECT t.id, t.guild_id, COALESCE((SELECT MAX(r.revision) FROM tag_revisions r WHERE r.tag_id = t.id), 0) + 1,
+                    t.content, t.embed, $2
+             FROM tags t WHERE t.library_tag_id = $1"
+        )
+        .bind(id)
+        .bind(published_by)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "UPDATE tags SET content = $1, embed = $2, updated_at = NOW() WHERE library_tag_id = $3"
+        )
+        .bind(content)
+        .bind(embed.map(Json))
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(inserted)
+}
+
+/// Removes a library tag along with the unedited copies in subscribed guilds.
+pub async fn unpublish_library_tag(pool: &PgPool, name: &str) -> Result<bool> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        "DELETE FROM tags WHERE library_tag_id = (SELECT id FROM tag_library WHERE LOWER(name) = LOWER($1))"
+    )
+    .bind(name)
+    .execute(&mut *tx)
+    .await?;
+
+    let result = sqlx::query("DELETE FROM tag_library WHERE LOWER(name) = LOWER($1)")
+        .bind(name)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn is_tag_library_subscribed(pool: &PgPool, guild_id: i64) -> Result<bool> {
+    let row: Option<(i64,)> = sqlx::query_as("SELECT guild_id FROM tag_library_subscriptions WHERE guild_id = $1")
+        .bind(guild_id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.is_some())
+}
+
+/// Subscribes a guild and copies in every library tag whose name is free. Returns
+/// `(added, skipped)`, or `None` if the guild was already subscribed.
+pub async fn subscribe_tag_library(pool: &PgPool, guild_id: i64, subscribed_by: i64) -> Result<Option<(u64, u64)>> {
+    let mut tx = pool.begin().await?;
+
+    let result = sqlx::query(
+        "INSERT INTO tag_library_subscriptions (guild_id, subscribed_by) VALUES ($1, $2) ON CONFLICT DO NOTHING"
+    )
+    .bind(guild_id)
+    .bind(subscribed_by)
+    .execute(&mut *tx)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Ok(None);
+    }
+
+    let added = sqlx::query(
+        "INSERT INTO tags (guild_id, name, content, embed, creator_id, library_tag_id)
+         SELECT $1, l.name, l.content, l.embed, l.published_by, l.id
+         FROM tag_library l
+         WHERE NOT EXISTS (SELECT 1 FROM tags t WHERE t.guild_id = $1 AND LOWER(t.name) = LOWER(l.name))
+           AND NOT EXISTS (SELECT 1 FROM tag_aliases a WHERE a.guild_id = $1 AND LOWER(a.alias) = LOWER(l.name))"
+    )
+    .bind(guild_id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let (total,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM tag_library")
+        .fetch_one(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(Some((added, (total as u64).saturating_sub(added))))
+}
+
+/// Unsubscribes a guild and removes the library tags it hasn't edited. Returns how
+/// many tags were removed, or `None` if the guild wasn't subscribed.
+pub async fn unsubscribe_tag_library(pool: &PgPool, guild_id: i64) -> Result<Option<u64>> {
+    let mut tx = pool.begin().await?;
+
+    let result = sqlx::query("DELETE FROM tag_library_subscriptions WHERE guild_id = $1")
+        .bind(guild_id)
+        .execute(&mut *tx)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Ok(None);
+    }
+
+    let removed = sqlx::query("DELETE FROM tags WHERE guild_id = $1 AND library_tag_id IS NOT NULL")
+        .bind(guild_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+    tx.commit().await?;
+    Ok(Some(removed))
+}
+
+pub async fn get_tag_folders(pool: &PgPool, guild_id: i64) -> Result<Vec<TagFolder>> {
+    let folders = sqlx::query_as::<_, TagFolder>(
+        "SELECT f.id, f.name, COUNT(t.id) AS tag_count FROM tag_folders f
+         LEFT JOIN tags t ON t.folder_id = f.id
+         WHERE f.guild_id = $1
+         GROUP BY f.id, f.name
+         ORDER BY LOWER(f.name) ASC"
+    )
+    .bind(guild_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(folders)
+}
+
+pub async fn get_tag_folder<'e>(executor: impl PgExecutor<'e>, guild_id: i64, name: &str) -> Result<Option<TagFolder>> {
+    let folder = sqlx::query_as::<_, TagFolder>(
+        "SELECT f.id, f.name, (SELECT COUNT(*) FROM tags t WHERE t.folder_id = f.id) AS tag_count
+         FROM tag_folders f WHERE f.guild_id = $1 AND LOWER(f.name) = LOWER($2)"
+    )
+    .bind(guild_id)
+    .bind(name)
+    .fetch_optional(executor)
+    .await?;
+    Ok(folder)
+}
+
+/// Returns `None` if a folder with that name already exists.
+pub async fn create_tag_folder<'e>(executor: impl PgExecutor<'e>, guild_id: i64, name: &str, created_by: i64) -> Result<Option<Uuid>> {
+    let row: Option<(Uuid,)> = sqlx::query_as(
+        "INSERT INTO tag_folders (guild_id, name, created_by) VALUES ($1, $2, $3)
+         ON CONFLICT (guild_id, LOWER(name)) DO NOTHING RETURNING id"
+    )
+    .bind(guild_id)
+    .bind(name)
+    .bind(created_by)
+    .fetch_optional(executor)
+    .await?;
+    Ok(row.map(|(id,)| id))
+}
+
+pub async fn delete_tag_folder(pool: &PgPool, guild_id: i64, name: &str) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM tag_folders WHERE guild_id = $1 AND LOWER(name) = LOWER($2)")
+        .bind(guild_id)
+        .bind(name)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn set_tag_folder<'e>(executor: impl PgExecutor<'e>, tag_id: Uuid, folder_id: Option<Uuid>) -> Result<()> {
+    sqlx::query("UPDATE tags SET folder_id = $1 WHERE id = $2")
+        .bind(folder_id)
+        .bind(tag_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// Folder name of every filed tag in the guild, as `(tag_id, folder)`.
+pub async fn get_tag_folder_names(pool: &PgPool, guild_id: i64) -> Result<Vec<(Uuid, String)>> {
+    let rows = sqlx::query_as(
+        "SELECT t.id, f.name FROM tags t JOIN tag_folders f ON f.id = t.folder_id WHERE t.guild_id = $1"
+    )
+    .bind(guild_id)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows)
+}
@@ -1,4 +1,4 @@
-use crate::models::{Guild, SupportRole, Ticket, TicketCategory, TicketMessage, TicketPanel, Reminder};
+use crate::models::{Guild, SupportRole, Ticket, TicketCategory, TicketMessage, TicketPanel, PostedPanel, PanelButton, Reminder, NewReminder, EscalationTier, ActiveEscalation};
 use anyhow::Result;
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -7,7 +7,7 @@ pub async fn get_or_create_guild(pool: &PgPool, guild_id: i64) -> Result<Guild>
     let guild = sqlx::query_as::<_, Guild>(
         "INSERT INTO guilds (guild_id) VALUES ($1)
          ON CONFLICT (guild_id) DO UPDATE SET guild_id = guilds.guild_id
-         RETURNING guild_id, ticket_category_id, log_channel_id, transcript_channel_id, prefix, claim_buttons_enabled, auto_close_hours, ticket_limit_per_user, ticket_cooldown_seconds, dm_on_create, embed_color, embed_title, embed_description, embed_footer, channel_name_template, created_at, updated_at"
+         RETURNING guild_id, ticket_category_id, log_channel_id, transcript_channel_id, prefix, claim_buttons_enabled, auto_close_hours, ticket_limit_per_user, ticket_cooldown_seconds, dm_on_create, embed_color, embed_title, embed_description, embed_footer, channel_name_template, exclusive_claim, created_at, updated_at"
     )
     .bind(guild_id)
     .fetch_one(pool)
@@ -99,6 +99,44 @@ pub async fn get_ticket_categories(pool: &PgPool, guild_id: i64) -> Result<Vec<T
     Ok(categories)
 }
 
+pub async fn update_ticket_category(
+    pool: &PgPool,
+    guild_id: i64,
+    category_id: Uuid,
+    name: &str,
+    description: &str,
+    emoji: Option<&str>,
+) -> Result<bool> {
+    let mut tx = pool.begin().await?;
+
+    // Panel buttons still labelled with the old name follow the rename; custom labels are kept.
+    sqlx::query(
+        "UPDATE panel_categories pc SET button_label = LEFT($1, 80), button_emoji = $2
+         FROM ticket_categories tc
+         WHERE tc.id = pc.category_id AND tc.id = $3 AND tc.guild_id = $4 AND pc.button_label = LEFT(tc.name, 80)"
+    )
+    .bind(name)
+    .bind(emoji)
+    .bind(category_id)
+    .bind(guild_id)
+    .execute(&mut *tx)
+    .await?;
+
+    let result = sqlx::query(
+        "UPDATE ticket_categories SET name = $1, description = $2, emoji = $3 WHERE id = $4 AND guild_id = $5"
+    )
+    .bind(name)
+    .bind(description)
+    .bind(emoji)
+    .bind(category_id)
+    .bind(guild_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(result.rows_affected() > 0)
+}
+
 #[allow(dead_code)]
 pub async fn delete_ticket_category(pool: &PgPool, guild_id: i64, category_id: Uuid) -> Result<()> {
     sqlx::query("DELETE FROM ticket_categories WHERE id = $1 AND guild_id = $2")
@@ -166,6 +204,8 @@ pub async fn create_ticket(
     .fetch_one(pool)
     .await?;
 
+    crate::metrics::record_ticket_opened(guild_id);
+
     Ok(ticket)
 }
 
@@ -194,7 +234,6 @@ pub async fn get_ticket_by_channel(pool: &PgPool, channel_id: i64) -> Result<Opt
     Ok(ticket)
 }
 
-#[allow(dead_code)]
 pub async fn get_ticket_by_id(pool: &PgPool, ticket_id: Uuid) -> Result<Option<Ticket>> {
     let ticket = sqlx::query_as::<_, Ticket>(
         "SELECT id, guild_id, channel_id, ticket_number, owner_id, category_id, claimed_by, assigned_to,
@@ -224,8 +263,49 @@ pub async fn get_user_tickets(pool: &PgPool, guild_id: i64, user_id: i64) -> Res
     Ok(tickets)
 }
 
+pub async fn get_claimed_open_tickets(pool: &PgPool, guild_id: i64) -> Result<Vec<Ticket>> {
+    let tickets = sqlx::query_as::<_, Ticket>(
+        "SELECT id, guild_id, channel_id, ticket_number, owner_id, category_id, claimed_by, assigned_to,
+                status, created_at, closed_at, priority, rating, last_activity, opening_message_id,
+                has_messages, last_message_at
+         FROM tickets WHERE guild_id = $1 AND status = 'open' AND claimed_by IS NOT NULL"
+    )
+    .bind(guild_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tickets)
+}
+
+/// Remembers that an exclusive claim denied SEND_MESSAGES to a support role.
+pub async fn record_claim_lock(pool: &PgPool, ticket_id: Uuid, role_id: i64, send_allowed: bool) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO ticket_claim_locks (ticket_id, role_id, send_allowed) VALUES ($1, $2, $3)
+         ON CONFLICT (ticket_id, role_id) DO NOTHING"
+    )
+    .bind(ticket_id)
+    .bind(role_id)
+    .bind(send_allowed)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Removes and returns the role locks a claim added, as `(role_id, send_allowed)`.
+pub async fn take_claim_locks(pool: &PgPool, ticket_id: Uuid) -> Result<Vec<(i64, bool)>> {
+    let locks = sqlx::query_as(
+        "DELETE FROM ticket_claim_locks WHERE ticket_id = $1 RETURNING role_id, send_allowed"
+    )
+    .bind(ticket_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(locks)
+}
+
 pub async fn claim_ticket(pool: &PgPool, ticket_id: Uuid, claimer_id: i64) -> Result<()> {
-    sqlx::query("UPDATE tickets SET claimed_by = $1 WHERE id = $2")
+    sqlx::query("UPDATE tickets SET claimed_by = $1, claimed_at = NOW() WHERE id = $2")
         .bind(claimer_id)
         .bind(ticket_id)
         .execute(pool)
@@ -236,7 +316,7 @@ pub async fn claim_ticket(pool: &PgPool, ticket_id: Uuid, claimer_id: i64) -> Re
 
 #[allow(dead_code)]
 pub async fn unclaim_ticket(pool: &PgPool, ticket_id: Uuid) -> Result<()> {
-    sqlx::query("UPDATE tickets SET claimed_by = NULL WHERE id = $1")
+    sqlx::query("UPDATE tickets SET claimed_by = NULL, claimed_at = NULL WHERE id = $1")
         .bind(ticket_id)
         .execute(pool)
         .await?;
@@ -245,7 +325,7 @@ pub async fn unclaim_ticket(pool: &PgPool, ticket_id: Uuid) -> Result<()> {
 }
 
 pub async fn assign_ticket(pool: &PgPool, ticket_id: Uuid, assignee_id: i64) -> Result<()> {
-    sqlx::query("UPDATE tickets SET assigned_to = $1 WHERE id = $2")
+    sqlx::query("UPDATE tickets SET assigned_to = $1, assigned_at = NOW() WHERE id = $2")
         .bind(assignee_id)
         .bind(ticket_id)
         .execute(pool)
@@ -263,15 +343,62 @@ pub async fn update_ticket_last_message(pool: &PgPool, ticket_id: Uuid) -> Resul
     Ok(())
 }
 
-pub async fn close_ticket(pool: &PgPool, ticket_id: Uuid) -> Result<()> {
-    sqlx::query("DELETE FROM tickets WHERE id = $1")
+pub async fn close_ticket(pool: &PgPool, ticket_id: Uuid, closed_by: i64) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    // Closed tickets are deleted, so keep a summary row around for analytics.
+    // Their logged messages go with them via ON DELETE CASCADE.
+    sqlx::query(
+        "INSERT INTO ticket_history
+         (ticket_id, guild_id, ticket_number, owner_id, category_id, priority, claimed_by, assigned_to, rating, opened_at, closed_at,
+          claimed_at, assigned_at, first_response_at, first_responder_id, closed_by, message_count)
+         SELECT id, guild_id, ticket_number, owner_id, category_id, priority, claimed_by, assigned_to, rating, created_at, NOW(),
+                claimed_at, assigned_at, first_response_at, first_responder_id, $2,
+                (SELECT COUNT(*) FROM ticket_messages WHERE ticket_id = $1)
+         FROM tickets WHERE id = $1
+         ON CONFLICT (ticket_id) DO NOTHING"
+    )
+    .bind(ticket_id)
+    .bind(closed_by)
+    .execute(&mut *tx)
+    .await?;
+
+    let guild_id: Option<i64> = sqlx::query_scalar("DELETE FROM tickets WHERE id = $1 RETURNING guild_id")
         .bind(ticket_id)
-        .execute(pool)
+        .fetch_optional(&mut *tx)
         .await?;
 
+    tx.commit().await?;
+
+    if let Some(guild_id) = guild_id {
+        crate::metrics::record_ticket_closed(guild_id);
+    }
+
     Ok(())
 }
 
+pub async fn merge_ticket_records(pool: &PgPool, source_id: Uuid, target_id: Uuid) -> Result<(u64, u64)> {
+    let mut tx = pool.begin().await?;
+
+    let messages = sqlx::query("UPDATE ticket_messages SET ticket_id = $1 WHERE ticket_id = $2")
+        .bind(target_id)
+        .bind(source_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+    let notes = sqlx::query("UPDATE ticket_notes SET ticket_id = $1 WHERE ticket_id = $2")
+        .bind(target_id)
+        .bind(source_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+    tx.commit().await?;
+
+    Ok((messages, notes))
+}
+
 pub async fn cleanup_priority_ping(redis: &mut redis::aio::ConnectionManager, ticket_id: Uuid) -> Result<()> {
     let redis_key = format!("priority_ping:{}", ticket_id);
     let _: () = redis::cmd("DEL")
@@ -371,6 +498,121 @@ pub async fn get_panel_count(pool: &PgPool, guild_id: i64) -> Result<i64> {
     Ok(count.0)
 }
 
+/// Panels in a guild whose message is still posted and which have something to
+/// re-render, either tracked categories or the guild embed.
+pub async fn get_posted_panels(pool: &PgPool, guild_id: i64) -> Result<Vec<PostedPanel>> {
+    let panels = sqlx::query_as::<_, PostedPanel>(
+        "SELECT p.id, p.guild_id, p.channel_id, p.message_id, p.selection_type, p.uses_guild_embed,
+                p.embed_image_url, p.embed_thumbnail_url, p.embed_footer_text, p.embed_footer_icon_url,
+                (p.all_categories OR EXISTS (SELECT 1 FROM panel_categories pc WHERE pc.panel_id = p.id)) AS tracks_categories
+         FROM ticket_panel p
+         WHERE p.guild_id = $1 AND p.missing_since IS NULL
+         ORDER BY p.created_at"
+    )
+    .bind(guild_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(panels.into_iter().filter(|p| p.uses_guild_embed || p.tracks_categories).collect())
+}
+
+/// The categories on a panel with their configured labels, in the order they were added.
+pub async fn get_panel_buttons(pool: &PgPool, panel_id: Uuid) -> Result<Vec<PanelButton>> {
+    let buttons = sqlx::query_as::<_, PanelButton>(
+        "SELECT pc.category_id, pc.button_label AS name, pc.button_emoji AS emoji, pc.button_style, pc.button_color
+         FROM panel_categories pc
+         JOIN ticket_categories tc ON tc.id = pc.category_id
+         WHERE pc.panel_id = $1
+         ORDER BY pc.position, pc.created_at"
+    )
+    .bind(panel_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(buttons)
+}
+
+pub async fn add_panel_category(
+    pool: &PgPool,
+    panel_id: Uuid,
+    category_id: Uuid,
+    label: &str,
+    emoji: Option<&str>,
+    button_color: &str,
+) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO panel_categories (panel_id, category_id, button_label, button_emoji, button_color)
+         VALUES ($1, $2, $3, $4, $5) ON CONFLICT (panel_id, category_id) DO NOTHING"
+    )
+    .bind(panel_id)
+    .bind(category_id)
+    .bind(label)
+    .bind(emoji)
+    .bind(button_color)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn set_panel_all_categories(pool: &PgPool, panel_id: Uuid) -> Result<()> {
+    sqlx::query("UPDATE ticket_panel SET all_categories = TRUE WHERE id = $1")
+        .bind(panel_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Adds a new category to every panel that shows all of the guild's categories.
+/// The button takes the colour of the panel's first button.
+pub async fn add_category_to_panels(pool: &PgPool, guild_id: i64, category_id: Uuid) -> Result<u64> {
+    let result = sqlx::query(
+        "INSERT INTO panel_categories (panel_id, category_id, button_label, button_emoji, button_style, button_color)
+         SELECT p.id, tc.id, LEFT(tc.name, 80), tc.emoji,
+                COALESCE(first.button_style, 'primary'), COALESCE(first.button_color, 'primary')
+         FROM ticket_panel p
+         JOIN ticket_categories tc ON tc.id = $2
+         LEFT JOIN LATERAL (
+             SELECT button_style, button_color FROM panel_categories
+             WHERE panel_id = p.id ORDER BY created_at LIMIT 1
+         ) first ON TRUE
+         WHERE p.guild_id = $1 AND p.all_categories AND p.missing_since IS NULL
+         ON CONFLICT (panel_id, category_id) DO NOTHING"
+    )
+    .bind(guild_id)
+    .bind(category_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Flags a panel whose message is gone. Returns false if the message was not a
+/// panel or was already flagged.
+pub async fn flag_missing_panel(pool: &PgPool, message_id: i64) -> Result<bool> {
+    let result = sqlx::query(
+        "UPDATE ticket_panel SET missing_since = NOW() WHERE message_id = $1 AND missing_since IS NULL"
+    )
+    .bind(message_id)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// `flag_missing_panel` for a bulk delete. Returns how many panels were flagged.
+pub async fn flag_missing_panels(pool: &PgPool, message_ids: &[i64]) -> Result<u64> {
+    let result = sqlx::query(
+        "UPDATE ticket_panel SET missing_since = NOW() WHERE message_id = ANY($1) AND missing_since IS NULL"
+    )
+    .bind(message_ids)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
 pub async fn add_premium(
     pool: &PgPool,
     guild_id: i64,
@@ -635,6 +877,39 @@ pub async fn set_category_welcome_message(
     Ok(())
 }
 
+pub async fn get_category_name(pool: &PgPool, category_id: Uuid) -> Result<Option<String>> {
+    let name: Option<(String,)> = sqlx::query_as("SELECT name FROM ticket_categories WHERE id = $1")
+        .bind(category_id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(name.map(|(name,)| name))
+}
+
+pub async fn find_category_by_name(pool: &PgPool, guild_id: i64, name: &str) -> Result<Option<Uuid>> {
+    let id: Option<(Uuid,)> = sqlx::query_as(
+        "SELECT id FROM ticket_categories WHERE guild_id = $1 AND LOWER(name) = LOWER($2)"
+    )
+    .bind(guild_id)
+    .bind(name)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(id.map(|(id,)| id))
+}
+
+pub async fn count_ticket_messages_by(pool: &PgPool, ticket_id: Uuid, author_id: i64) -> Result<i64> {
+    let (count,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM ticket_messages WHERE ticket_id = $1 AND author_id = $2"
+    )
+    .bind(ticket_id)
+    .bind(author_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(count)
+}
+
 pub async fn get_category_welcome_message(
     pool: &PgPool,
     category_id: Uuid,
@@ -659,7 +934,7 @@ pub async fn create_escalation(
         "INSERT INTO ticket_escalations (ticket_id, escalated_by)
          VALUES ($1, $2)
          ON CONFLICT (ticket_id) DO UPDATE
-         SET is_active = TRUE, last_ping_at = NOW()"
+         SET is_active = TRUE, last_ping_at = NOW(), escalated_at = NOW(), current_tier = 0"
     )
     .bind(ticket_id)
     .bind(escalated_by)
@@ -678,9 +953,11 @@ pub async fn deactivate_escalation(pool: &PgPool, ticket_id: Uuid) -> Result<()>
     Ok(())
 }
 
-pub async fn get_active_escalations(pool: &PgPool) -> Result<Vec<(Uuid, i64)>> {
-    let escalations: Vec<(Uuid, i64)> = sqlx::query_as(
-        "SELECT ticket_id, EXTRACT(EPOCH FROM (NOW() - last_ping_at))::BIGINT
+pub async fn get_active_escalations(pool: &PgPool) -> Result<Vec<ActiveEscalation>> {
+    let escalations = sqlx::query_as::<_, ActiveEscalation>(
+        "SELECT ticket_id, current_tier,
+                EXTRACT(EPOCH FROM (NOW() - escalated_at))::BIGINT AS seconds_since_escalated,
+                EXTRACT(EPOCH FROM (NOW() - last_ping_at))::BIGINT AS seconds_since_last_ping
          FROM ticket_escalations
          WHERE is_active = TRUE"
     )
@@ -690,8 +967,9 @@ pub async fn get_active_escalations(pool: &PgPool) -> Result<Vec<(Uuid, i64)>> {
     Ok(escalations)
 }
 
-pub async fn update_escalation_ping_time(pool: &PgPool, ticket_id: Uuid) -> Result<()> {
-    sqlx::query("UPDATE ticket_escalations SET last_ping_at = NOW() WHERE ticket_id = $1")
+pub async fn set_escalation_tier(pool: &PgPool, ticket_id: Uuid, tier: i32) -> Result<()> {
+    sqlx::query("UPDATE ticket_escalations SET current_tier = $1, last_ping_at = NOW() WHERE ticket_id = $2")
+        .bind(tier)
         .bind(ticket_id)
         .execute(pool)
         .await?;
@@ -699,8 +977,60 @@ pub async fn update_escalation_ping_time(pool: &PgPool, ticket_id: Uuid) -> Resu
     Ok(())
 }
 
-pub async fn mark_ticket_has_messages(pool: &PgPool, ticket_id: Uuid) -> Result<()> {
-    sqlx::query("UPDATE tickets SET has_messages = TRUE WHERE id = $1")
+pub async fn get_escalation_tiers(pool: &PgPool, guild_id: i64) -> Result<Vec<EscalationTier>> {
+    let tiers = sqlx::query_as::<_, EscalationTier>(
+        "SELECT id, guild_id, tier, role_id, delay_minutes, delivery, channel_id, created_at
+         FROM escalation_tiers
+         WHERE guild_id = $1
+         ORDER BY tier ASC"
+    )
+    .bind(guild_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tiers)
+}
+
+pub async fn upsert_escalation_tier(
+    pool: &PgPool,
+    guild_id: i64,
+    tier: i32,
+    role_id: i64,
+    delay_minutes: i32,
+    channel_id: Option<i64>,
+) -> Result<()> {
+    let delivery = if channel_id.is_some() { "channel" } else { "dm" };
+
+    sqlx::query(
+        "INSERT INTO escalation_tiers (guild_id, tier, role_id, delay_minutes, delivery, channel_id)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         ON CONFLICT (guild_id, tier) DO UPDATE
+         SET role_id = $3, delay_minutes = $4, delivery = $5, channel_id = $6"
+    )
+    .bind(guild_id)
+    .bind(tier)
+    .bind(role_id)
+    .bind(delay_minutes)
+    .bind(delivery)
+    .bind(channel_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn remove_escalation_tier(pool: &PgPool, guild_id: i64, tier: i32) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM escalation_tiers WHERE guild_id = $1 AND tier = $2")
+        .bind(guild_id)
+        .bind(tier)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn update_escalation_ping_time(pool: &PgPool, ticket_id: Uuid) -> Result<()> {
+    sqlx::query("UPDATE ticket_escalations SET last_ping_at = NOW() WHERE ticket_id = $1")
         .bind(ticket_id)
         .execute(pool)
         .await?;
@@ -708,6 +1038,24 @@ pub async fn mark_ticket_has_messages(pool: &PgPool, ticket_id: Uuid) -> Result<
     Ok(())
 }
 
+/// Marks that someone other than the owner has written in the ticket. A staff
+/// `responder_id` is also recorded as the first response if there isn't one yet.
+pub async fn mark_ticket_has_messages(pool: &PgPool, ticket_id: Uuid, responder_id: Option<i64>) -> Result<()> {
+    sqlx::query(
+        "UPDATE tickets
+         SET has_messages = TRUE,
+             first_response_at = CASE WHEN $2::BIGINT IS NULL THEN first_response_at ELSE COALESCE(first_response_at, NOW()) END,
+             first_responder_id = COALESCE(first_responder_id, $2)
+         WHERE id = $1"
+    )
+    .bind(ticket_id)
+    .bind(responder_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
 pub async fn ticket_has_messages(pool: &PgPool, ticket_id: Uuid) -> Result<bool> {
     let result: Option<(bool,)> = sqlx::query_as(
         "SELECT COALESCE(has_messages, FALSE) FROM tickets WHERE id = $1"
@@ -730,7 +1078,7 @@ pub async fn create_reminder(
 ) -> Result<Reminder> {
     let reminder = sqlx::query_as::<_, Reminder>(
         "INSERT INTO reminders (user_id, channel_id, guild_id, message_id, reason, remind_at)
-         VALUES ($1, $2, $3, $4, $5, $6) RETURNING id, user_id, channel_id, guild_id, message_id, reason, remind_at, created_at, completed"
+         VALUES ($1, $2, $3, $4, $5, $6) RETURNING id, user_id, channel_id, guild_id, message_id, reason, remind_at, created_at, completed, recurrence, repeat_until, repeat_limit, occurrences, ticket_id, mention, cancel_on_reply, attempts, delivery_status, last_error, last_attempt_at"
     )
     .bind(user_id)
     .bind(channel_id)
@@ -744,18 +1092,189 @@ pub async fn create_reminder(
     Ok(reminder)
 }
 
-pub async fn get_pending_reminders(pool: &PgPool) -> Result<Vec<Reminder>> {
+/// Claims up to `limit` due reminders for this instance by leasing them for
+/// `lease_seconds` under `claim`. Rows locked or leased by another instance are
+/// skipped, so each reminder is delivered by one instance only.
+pub async fn claim_due_reminders(pool: &PgPool, claim: Uuid, lease_seconds: i64, limit: i64) -> Result<Vec<Reminder>> {
+    let reminders = sqlx::query_as::<_, Reminder>(
+        "UPDATE reminders SET next_attempt_at = NOW() + make_interval(secs => $1), claim_token = $3
+         WHERE id IN (
+             SELECT id FROM reminders
+             WHERE completed = FALSE AND remind_at <= NOW()
+               AND (next_attempt_at IS NULL OR next_attempt_at <= NOW())
+             ORDER BY remind_at ASC
+             LIMIT $2
+             FOR UPDATE SKIP LOCKED
+         )
+         RETURNING id, user_id, channel_id, guild_id, message_id, reason, remind_at, created_at, completed, recurrence, repeat_until, repeat_limit, occurrences, ticket_id, mention, cancel_on_reply, attempts, delivery_status, last_error, last_attempt_at"
+    )
+    .bind(lease_seconds as f64)
+    .bind(limit)
+    .bind(claim)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(reminders)
+}
+
+/// Extends the lease on a claimed reminder right before it is delivered. Returns
+/// false if the claim has been lost to another instance or the reminder was
+/// rescheduled, in which case it must not be delivered.
+pub async fn renew_reminder_claim(pool: &PgPool, reminder_id: Uuid, claim: Uuid, lease_seconds: i64) -> Result<bool> {
+    let result = sqlx::query(
+        "UPDATE reminders SET next_attempt_at = NOW() + make_interval(secs => $1)
+         WHERE id = $2 AND claim_token = $3 AND completed = FALSE"
+    )
+    .bind(lease_seconds as f64)
+    .bind(reminder_id)
+    .bind(claim)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Records a finished delivery (`status` is `delivered` or `failed` once retries run
+/// out) and either moves a repeating reminder to `next` or completes it. Does
+/// nothing if `claim` no longer holds the reminder.
+pub async fn finish_reminder_delivery(
+    pool: &PgPool,
+    reminder_id: Uuid,
+    claim: Uuid,
+    next: Option<chrono::DateTime<chrono::Utc>>,
+    status: &str,
+    error: Option<&str>,
+) -> Result<()> {
+    sqlx::query(
+        "UPDATE reminders SET
+             remind_at = COALESCE($1, remind_at),
+             completed = ($1 IS NULL),
+             occurrences = occurrences + 1,
+             attempts = 0,
+             next_attempt_at = NULL,
+             delivery_status = $2,
+             last_error = $3,
+             last_attempt_at = NOW(),
+             claim_token = NULL
+         WHERE id = $4 AND claim_token = $5"
+    )
+    .bind(next)
+    .bind(status)
+    .bind(error)
+    .bind(reminder_id)
+    .bind(claim)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Records a failed delivery that will be tried again at `retry_at`, if `claim`
+/// still holds the reminder.
+pub async fn schedule_reminder_retry(pool: &PgPool, reminder_id: Uuid, claim: Uuid, retry_at: chrono::DateTime<chrono::Utc>, error: &str) -> Result<()> {
+    sqlx::query(
+        "UPDATE reminders SET attempts = attempts + 1, next_attempt_at = $1, delivery_status = 'retrying', last_error = $2, last_attempt_at = NOW(),
+             claim_token = NULL
+         WHERE id = $3 AND claim_token = $4"
+    )
+    .bind(retry_at)
+    .bind(error)
+    .bind(reminder_id)
+    .bind(claim)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// The user's reminders that finished in the last week, newest first.
+pub async fn get_recent_delivered_reminders(pool: &PgPool, user_id: i64, limit: i64) -> Result<Vec<Reminder>> {
     let reminders = sqlx::query_as::<_, Reminder>(
-        "SELECT id, user_id, channel_id, guild_id, message_id, reason, remind_at, created_at, completed FROM reminders WHERE completed = FALSE AND remind_at <= NOW() ORDER BY remind_at ASC"
+        "SELECT id, user_id, channel_id, guild_id, message_id, reason, remind_at, created_at, completed, recurrence, repeat_until, repeat_limit, occurrences, ticket_id, mention, cancel_on_reply, attempts, delivery_status, last_error, last_attempt_at FROM reminders
+         WHERE user_id = $1 AND completed = TRUE AND last_attempt_at > NOW() - INTERVAL '7 days'
+         ORDER BY last_attempt_at DESC LIMIT $2"
     )
+    .bind(user_id)
+    .bind(limit)
     .fetch_all(pool)
     .await?;
 
     Ok(reminders)
 }
 
-pub async fn mark_reminder_completed(pool: &PgPool, reminder_id: Uuid) -> Result<()> {
-    sqlx::query("UPDATE reminders SET completed = TRUE WHERE id = $1")
+/// Inserts a reminder together with its repeat schedule or ticket follow-up settings.
+pub async fn create_scheduled_reminder(pool: &PgPool, reminder: &NewReminder) -> Result<Reminder> {
+    let reminder = sqlx::query_as::<_, Reminder>(
+        "INSERT INTO reminders (user_id, channel_id, guild_id, message_id, reason, remind_at, recurrence, repeat_until, repeat_limit,
+                                ticket_id, mention, cancel_on_reply)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12) RETURNING id, user_id, channel_id, guild_id, message_id, reason, remind_at, created_at, completed, recurrence, repeat_until, repeat_limit, occurrences, ticket_id, mention, cancel_on_reply, attempts, delivery_status, last_error, last_attempt_at"
+    )
+    .bind(reminder.user_id)
+    .bind(reminder.channel_id)
+    .bind(reminder.guild_id)
+    .bind(reminder.message_id)
+    .bind(&reminder.reason)
+    .bind(reminder.remind_at)
+    .bind(&reminder.recurrence)
+    .bind(reminder.repeat_until)
+    .bind(reminder.repeat_limit)
+    .bind(reminder.ticket_id)
+    .bind(&reminder.mention)
+    .bind(reminder.cancel_on_reply)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(reminder)
+}
+
+/// Drops a ticket's pending follow-ups that were set to cancel when the owner replies.
+pub async fn cancel_reply_followups(pool: &PgPool, ticket_id: Uuid) -> Result<u64> {
+    let result = sqlx::query("DELETE FROM reminders WHERE ticket_id = $1 AND cancel_on_reply = TRUE AND completed = FALSE")
+        .bind(ticket_id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected())
+}
+
+pub async fn delete_ticket_reminders(pool: &PgPool, ticket_id: Uuid) -> Result<()> {
+    sqlx::query("DELETE FROM reminders WHERE ticket_id = $1 AND completed = FALSE")
+        .bind(ticket_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn get_reminder(pool: &PgPool, reminder_id: Uuid) -> Result<Option<Reminder>> {
+    let reminder = sqlx::query_as::<_, Reminder>(
+        "SELECT id, user_id, channel_id, guild_id, message_id, reason, remind_at, created_at, completed, recurrence, repeat_until, repeat_limit, occurrences, ticket_id, mention, cancel_on_reply, attempts, delivery_status, last_error, last_attempt_at FROM reminders WHERE id = $1"
+    )
+    .bind(reminder_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(reminder)
+}
+
+/// Changes a pending reminder's reason and/or time, leaving `None` fields as they are.
+pub async fn update_reminder(
+    pool: &PgPool,
+    reminder_id: Uuid,
+    reason: Option<&str>,
+    remind_at: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<()> {
+    sqlx::query(
+        "UPDATE reminders SET reason = COALESCE($1, reason), remind_at = COALESCE($2, remind_at),
+             attempts = CASE WHEN $2 IS NULL THEN attempts ELSE 0 END,
+             next_attempt_at = CASE WHEN $2 IS NULL THEN next_attempt_at ELSE NULL END,
+             delivery_status = CASE WHEN $2 IS NULL THEN delivery_status ELSE NULL END,
+             last_error = CASE WHEN $2 IS NULL THEN last_error ELSE NULL END,
+             claim_token = CASE WHEN $2 IS NULL THEN claim_token ELSE NULL END
+         WHERE id = $3"
+    )
+        .bind(reason)
+        .bind(remind_at)
         .bind(reminder_id)
         .execute(pool)
         .await?;
@@ -774,7 +1293,7 @@ pub async fn delete_reminder(pool: &PgPool, reminder_id: Uuid) -> Result<()> {
 
 pub async fn get_user_reminders(pool: &PgPool, user_id: i64) -> Result<Vec<Reminder>> {
     let reminders = sqlx::query_as::<_, Reminder>(
-        "SELECT id, user_id, channel_id, guild_id, message_id, reason, remind_at, created_at, completed FROM reminders WHERE user_id = $1 AND completed = FALSE ORDER BY remind_at ASC"
+        "SELECT id, user_id, channel_id, guild_id, message_id, reason, remind_at, created_at, completed, recurrence, repeat_until, repeat_limit, occurrences, ticket_id, mention, cancel_on_reply, attempts, delivery_status, last_error, last_attempt_at FROM reminders WHERE user_id = $1 AND completed = FALSE ORDER BY remind_at ASC"
     )
     .bind(user_id)
     .fetch_all(pool)
@@ -783,6 +1302,39 @@ pub async fn get_user_reminders(pool: &PgPool, user_id: i64) -> Result<Vec<Remin
     Ok(reminders)
 }
 
+// User Settings Functions
+
+pub async fn get_user_timezone(pool: &PgPool, user_id: i64) -> Result<Option<String>> {
+    let timezone = sqlx::query_scalar("SELECT timezone FROM user_settings WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(timezone)
+}
+
+pub async fn set_user_timezone(pool: &PgPool, user_id: i64, timezone: &str) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO user_settings (user_id, timezone) VALUES ($1, $2)
+         ON CONFLICT (user_id) DO UPDATE SET timezone = EXCLUDED.timezone, updated_at = NOW()"
+    )
+    .bind(user_id)
+    .bind(timezone)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn clear_user_timezone(pool: &PgPool, user_id: i64) -> Result<()> {
+    sqlx::query("DELETE FROM user_settings WHERE user_id = $1")
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
 // Backup Category Functions
 
 pub async fn update_category_discord_id(pool: &PgPool, category_id: Uuid, discord_category_id: i64) -> Result<()> {
@@ -895,6 +1447,15 @@ pub async fn update_channel_name_template(pool: &PgPool, guild_id: i64, template
     Ok(())
 }
 
+pub async fn update_exclusive_claim(pool: &PgPool, guild_id: i64, enabled: bool) -> Result<()> {
+    sqlx::query("UPDATE guilds SET exclusive_claim = $1 WHERE guild_id = $2")
+        .bind(enabled)
+        .bind(guild_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
 pub fn format_channel_name(template: &str, ticket_number: i32, user_id: i64, username: &str) -> String {
     template
         .replace("$ticket_number", &ticket_number.to_string())
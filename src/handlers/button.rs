@@ -283,6 +283,7 @@ pub async fn handle_ticket_claim(
         }
 
         crate::database::ticket::claim_ticket(&db.pool, ticket.id, claimer_id).await?;
+        let _ = crate::utils::sync_exclusive_claim(ctx, db, ticket.id, &[]).await;
 
         let _ = crate::database::ticket::deactivate_escalation(&db.pool, ticket.id).await;
 
@@ -376,6 +377,7 @@ pub async fn handle_ticket_unclaim(
         }
 
         crate::database::ticket::unclaim_ticket(&db.pool, ticket.id).await?;
+        let _ = crate::utils::sync_exclusive_claim(ctx, db, ticket.id, &[user_id]).await;
 
         // Send log
         let guild = crate::database::ticket::get_or_create_guild(&db.pool, ticket.guild_id).await?;
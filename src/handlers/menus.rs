@@ -636,21 +636,35 @@ fn create_ticket_commands_embed(prefix: &str) -> CreateEmbed {
             `{}assign @user` - Assign ticket to another user\n\
             `{}escalate` - DM support + hourly reminders until claimed (unanswered only)\n\
             `{}handle` - One-time urgent DM to all support staff\n\
+            `{}away [duration] [reason]` - Stop receiving pings while away\n\
+            `{}back` - Clear your away status\n\
+            `{}staff` - List available and away staff\n\
+            `{}merge #ticket` - Merge this ticket into another\n\
+            `{}followup <when> [message]` - Ping this ticket later unless the owner replies\n\
             `{}transcript` - Generate and download transcript\n\
-            `{}reminder <time> <reason>` - Set reminder (e.g. 30m, 2h, 1d)\n\n\
+            `{}reminder <when> <reason>` - Set reminder (e.g. 30m, 1h30m, tomorrow 9am)\n\
+            `{}reminder every <2h|day|monday> [at 09:00] <reason>` - Repeating reminder\n\
+            `{}reminder list|cancel|snooze|edit` - Manage your reminders\n\
+            `{}timezone [zone]` - Set the timezone your reminders use\n\n\
             **Slash Commands:**\n\
             `/close` - Close ticket channel\n\
             `/claim` - Claim ticket as yours\n\
             `/assign <user>` - Assign ticket to another user\n\
             `/escalate` - DM support + hourly reminders (unanswered tickets)\n\
             `/handle` - One-time urgent notification to support staff\n\
+            `/away` / `/back` - Toggle your away status\n\
+            `/staff` - List available and away staff\n\
+            `/merge <into>` - Merge this ticket into another\n\
+            `/followup <when>` - Schedule a follow-up in this ticket\n\
             `/priority <level>` - Set priority (low/normal/high/urgent)\n\
-            `/note <text>` - Add private note to ticket\n\n\
+            `/note <text>` - Add private note to ticket\n\
+            `/reminder` - Set, list, edit, snooze or cancel reminders\n\
+            `/timezone [zone]` - Set the timezone your reminders use\n\n\
             **Escalate vs Handle:**\n\
             • `escalate` - For unanswered tickets, sends hourly DM reminders\n\
             • `handle` - For any ticket, one-time urgent notification\n\n\
             **Note:** Use `{}doc <command>` for detailed command info",
-            prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix
+            prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix
         )
     )
     .color(0x5865F2)
@@ -661,28 +675,40 @@ fn create_tag_commands_embed(prefix: &str) -> CreateEmbed {
         "Tag System",
         format!(
             "**Using Tags:**\n\
-            `{}tag <name>` - Display a tag\n\
-            `/tag use <name>` - Display a tag (slash)\n\n\
+            `{}tag <name> [values...]` - Display a tag\n\
+            `/tag use <name> [args]` - Display a tag (slash)\n\n\
             **Managing Tags:**\n\
             `{}tag create <name> <content>` - Create new tag\n\
             `{}tag edit <name> <new_content>` - Edit your tag\n\
             `{}tag delete <name>` - Delete your tag\n\
-            `{}tag rename <old> <new>` - Rename your tag\n\n\
+            `{}tag rename <old> <new>` - Rename your tag\n\
+            `{}tag alias add <name> <alias>` - Add another name for your tag\n\
+            `{}tag alias remove <alias>` - Remove an alias\n\
+            `{}tag history <name>` - Show previous versions of a tag\n\
+            `{}tag rollback <name> <revision>` - Restore a previous version\n\
+            `{}tag permissions` - Show or change which roles manage tags\n\
+            `{}tag export` / `{}tag import [skip|overwrite|rename]` - Copy tags between servers\n\
+            `{}tag library [subscribe|unsubscribe]` - Use the shared tag library\n\n\
             **Browsing Tags:**\n\
-            `{}tag list` - List all server tags\n\
+            `{}tag list` - Browse tags by folder with previews\n\
+            `{}tag folder [create|delete|move]` - Organise tags into folders\n\
             `{}tag search <query>` - Search tags by name/content\n\
             `{}tag popular` - Show top 10 most used tags\n\
             `{}tag info <name>` - View tag details and stats\n\
-            `{}tag raw <name>` - View raw tag content\n\n\
+            `{}tag raw <name>` - View raw tag content or embed JSON\n\
+            `/tag embed <name>` - Build an embed tag with buttons\n\n\
             **Slash Commands:**\n\
             `/tag create`, `/tag edit`, `/tag delete`, `/tag info`,\n\
             `/tag list`, `/tag search`, `/tag raw`, `/tag rename`,\n\
-            `/tag popular`, `/tag use`\n\n\
+            `/tag popular`, `/tag use`, `/tag alias`, `/tag embed`,\n\
+            `/tag history`, `/tag rollback`, `/tag permissions`,\n\
+            `/tag export`, `/tag import`, `/tag library`, `/tag folder`\n\n\
             **Notes:**\n\
-            • Tag names are case-insensitive\n\
-            • Only tag creators can edit/delete/rename their tags\n\
+            • Tag names and aliases are case-insensitive, and slash commands autocomplete them\n\
+            • Creators and admins can edit/delete/rename/alias tags; admins can grant this to roles\n\
+            • Content can use `{{user}}`, `{{guild}}`, `{{ticket.number}}`, `{{ticket.owner}}`, `{{claimer}}`, `{{category}}` and `{{1}}`, `{{2}}`, ...\n\
             • Use `{}doc tag` for detailed documentation",
-            prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix
+            prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix
         )
     )
     .color(0x5865F2)
@@ -719,15 +745,16 @@ fn create_setup_commands_embed(prefix: &str) -> CreateEmbed {
             • Delete panels\n\n\
             **Settings Configuration:**\n\
             `{}settings claimbuttons <true|false>` - Toggle claim buttons\n\
-            `{}settings autoclose <hours>` - Auto-close inactive tickets\n\
+            `{}settings autoclose <hours|2d>` - Auto-close inactive tickets\n\
             `{}settings ticketlimit <number>` - Max tickets per user\n\
             `{}settings cooldown <seconds>` - Ticket creation cooldown\n\
             `{}settings dmoncreate <true|false>` - DM on ticket creation\n\
+            `{}settings exclusiveclaim <true|false>` - Lock claimed tickets to the claimer\n\
             `{}settings embedcolor <hex>` - Panel embed color\n\
             `{}settings embedtitle <text>` - Panel embed title\n\
             `{}settings embeddescription <text>` - Panel description\n\
             `{}settings embedfooter <text>` - Panel embed footer",
-            prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix
+            prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix
         )
     )
     .color(0x5865F2)
@@ -746,17 +773,35 @@ fn create_admin_commands_embed(prefix: &str) -> CreateEmbed {
             **Role Management:**\n\
             `{}supportrole @role` - Add/remove support role\n\
             `/supportrole` - Manage support roles (slash)\n\n\
+            **Escalation Tiers:**\n\
+            `{}escalation add <tier> @role <delay> [#channel]` - Notify a role after a delay\n\
+            `{}escalation remove <tier>` - Remove a tier\n\
+            `{}escalation list` - Show configured tiers\n\
+            `/escalation` - Manage escalation tiers (slash)\n\n\
             **Ticket Management:**\n\
             `{}priority <level>` - Set ticket priority\n\
             `{}note <text>` - Add note to current ticket\n\
             `{}blacklist @user` - Blacklist user from creating tickets\n\
-            `{}stats` - View server ticket statistics\n\n\
+            `{}stats [24h|7d|30d|YYYY-MM-DD [YYYY-MM-DD]]` - View server ticket statistics\n\
+            `{}stats chart [period]` - Chart of tickets per day and per category\n\
+            `/stats [period] [from] [to] [chart]` - Statistics for a time window (slash)\n\
+            `{}staffstats [@member] [period] [sort]` - Staff report or leaderboard\n\
+            `/staffstats [member] [period] [sort]` - Staff report or leaderboard (slash)\n\
+            `/export [format] [period]` - Download tickets as CSV or JSON\n\n\
+            **Scheduled Digest:**\n\
+            `{}digest set <daily|weekly> [weekday] <HH:MM> [timezone] [sla hours]` - Post a digest to the log channel\n\
+            `{}digest [show|preview|off]` - View, preview or disable the digest\n\
+            `/digest` - Manage the digest (slash)\n\n\
+            **Auto-Responder:**\n\
+            `{}autorespond add <tag> <pattern> [--regex] [--first] [--cooldown <time>] [--category <name>]` - Reply with a tag on matching ticket messages\n\
+            `{}autorespond [list|remove <number>]` - View or remove rules\n\
+            `/autorespond` - Manage auto-responder rules (slash)\n\n\
             **Panel Customization:**\n\
             • Custom button colors (red, blue, green, gray)\n\
             • Embed image, thumbnail, and footer\n\
             • Button or dropdown style selection\n\
             • Session-locked editing (one user at a time)",
-            prefix, prefix, prefix, prefix, prefix, prefix, prefix
+            prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix
         )
     )
     .color(0x5865F2)
@@ -807,8 +852,9 @@ fn create_owner_commands_embed(prefix: &str) -> CreateEmbed {
             `{}blacklistguild <guild_id>` - Blacklist guild\n\
             `{}unblacklistuser <user_id>` - Unblacklist user\n\
             `{}unblacklistguild <guild_id>` - Unblacklist guild\n\
-            `{}listblacklist` - List blacklisted users/guilds",
-            prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix
+            `{}listblacklist` - List blacklisted users/guilds\n\
+            `{}taglib [publish <tag>|unpublish <name>]` - Manage the shared tag library",
+            prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix
         )
     )
     .color(0xED4245)
@@ -897,8 +943,8 @@ pub async fn handle_delete_panel(
 ) -> Result<()> {
     let guild_id = interaction.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?;
 
-    let panels: Vec<(i64, i64)> = sqlx::query_as(
-        "SELECT channel_id, message_id FROM ticket_panel WHERE guild_id = $1 ORDER BY created_at DESC LIMIT 25"
+    let panels: Vec<(i64, i64, bool)> = sqlx::query_as(
+        "SELECT channel_id, message_id, missing_since IS NOT NULL FROM ticket_panel WHERE guild_id = $1 ORDER BY created_at DESC LIMIT 25"
     )
     .bind(guild_id.get() as i64)
     .fetch_all(&db.pool)
@@ -925,12 +971,17 @@ pub async fn handle_delete_panel(
         return Ok(());
     }
 
-    let select_options: Vec<_> = panels.iter().enumerate().map(|(idx, (channel_id, message_id))| {
+    let select_options: Vec<_> = panels.iter().enumerate().map(|(idx, (channel_id, message_id, missing))| {
+        let description = if *missing {
+            format!("Delete panel {} (message was deleted)", idx + 1)
+        } else {
+            format!("Delete panel {}", idx + 1)
+        };
         serenity::all::CreateSelectMenuOption::new(
             format!("Panel in #{} (ID: {})", channel_id, message_id),
             format!("delete_panel_{}_{}", channel_id, message_id)
         )
-        .description(format!("Delete panel {}", idx + 1))
+        .description(description)
     }).collect();
 
     let select_menu = serenity::all::CreateSelectMenu::new(
@@ -943,10 +994,16 @@ pub async fn handle_delete_panel(
 
     let components = vec![serenity::all::CreateActionRow::SelectMenu(select_menu)];
 
-    let embed = create_embed(
-        "Delete Panel",
-        format!("Found {} panel(s). Select one to delete:", panels.len())
-    ).color(0xED4245);
+    let missing = panels.iter().filter(|(_, _, missing)| *missing).count();
+    let mut description = format!("Found {} panel(s). Select one to delete:", panels.len());
+    if missing > 0 {
+        description.push_str(&format!(
+            "\n\n⚠️ {} panel(s) had their message deleted and no longer show in any channel.",
+            missing
+        ));
+    }
+
+    let embed = create_embed("Delete Panel", description).color(0xED4245);
 
     interaction
         .create_response(
@@ -1131,6 +1188,8 @@ pub async fn handle_panel_edit_modal(
         )
         .await?;
 
+    crate::utils::panel::refresh_guild_panels(&ctx.http, db, guild_id.get() as i64).await;
+
     Ok(())
 }
 
@@ -1170,7 +1229,7 @@ pub async fn handle_panel_send_here(
     ).await?;
 
     sqlx::query(
-        "INSERT INTO ticket_panel (guild_id, channel_id, message_id, title, description) VALUES ($1, $2, $3, $4, $5)"
+        "INSERT INTO ticket_panel (guild_id, channel_id, message_id, title, description, uses_guild_embed) VALUES ($1, $2, $3, $4, $5, TRUE)"
     )
     .bind(guild_id.get() as i64)
     .bind(channel_id.get() as i64)
@@ -1381,16 +1440,18 @@ pub async fn handle_category_add_modal(
             }
         });
 
-    sqlx::query(
-        "INSERT INTO ticket_categories (guild_id, name, description, emoji) VALUES ($1, $2, $3, $4)"
+    let (category_id,): (uuid::Uuid,) = sqlx::query_as(
+        "INSERT INTO ticket_categories (guild_id, name, description, emoji) VALUES ($1, $2, $3, $4) RETURNING id"
     )
     .bind(guild_id.get() as i64)
     .bind(&name)
     .bind(&description)
     .bind(&emoji)
-    .execute(&db.pool)
+    .fetch_one(&db.pool)
     .await?;
 
+    crate::database::ticket::add_category_to_panels(&db.pool, guild_id.get() as i64, category_id).await?;
+
     info!("Created category '{}' for guild {}", name, guild_id.get());
 
     let mut redis_conn = db.redis.clone();
@@ -1417,6 +1478,8 @@ pub async fn handle_category_add_modal(
         )
         .await?;
 
+    crate::utils::panel::refresh_guild_panels(&ctx.http, db, guild_id.get() as i64).await;
+
     Ok(())
 }
 
@@ -1496,6 +1559,162 @@ pub async fn handle_category_edit_select(
     Ok(())
 }
 
+pub async fn handle_category_edit_confirm(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    db: &Database,
+) -> Result<()> {
+    let parts: Vec<&str> = interaction.data.custom_id.split('_').collect();
+    if parts.len() < 4 {
+        return Ok(());
+    }
+
+    let category_id = parts[3];
+    let category_uuid = uuid::Uuid::parse_str(category_id)
+        .map_err(|_| anyhow::anyhow!("Invalid category ID"))?;
+
+    let category: Option<(String, Option<String>, Option<String>)> = sqlx::query_as(
+        "SELECT name, description, emoji FROM ticket_categories WHERE id = $1"
+    )
+    .bind(category_uuid)
+    .fetch_optional(&db.pool)
+    .await?;
+
+    let Some((name, description, emoji)) = category else {
+        let embed = create_error_embed("Error", "Category not found");
+        interaction
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .embed(embed)
+                        .ephemeral(true),
+                ),
+            )
+            .await?;
+        return Ok(());
+    };
+
+    let modal = CreateModal::new(format!("category_edit_modal:{}", category_id), "Edit Ticket Category")
+        .components(vec![
+            serenity::all::CreateActionRow::InputText(
+                CreateInputText::new(InputTextStyle::Short, "Category Name", "category_name")
+                    .value(name)
+                    .required(true)
+                    .max_length(50)
+            ),
+            serenity::all::CreateActionRow::InputText(
+                CreateInputText::new(InputTextStyle::Paragraph, "Description", "category_description")
+                    .value(description.unwrap_or_default())
+                    .required(true)
+                    .max_length(500)
+            ),
+            serenity::all::CreateActionRow::InputText(
+                CreateInputText::new(InputTextStyle::Short, "Emoji (optional)", "category_emoji")
+                    .value(emoji.unwrap_or_default())
+                    .required(false)
+            ),
+        ]);
+
+    interaction
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::Modal(modal),
+        )
+        .await?;
+
+    Ok(())
+}
+
+pub async fn handle_category_edit_modal(
+    ctx: &Context,
+    interaction: &serenity::all::ModalInteraction,
+    db: &Database,
+) -> Result<()> {
+    let guild_id = interaction.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?;
+    let category_uuid = interaction.data.custom_id
+        .strip_prefix("category_edit_modal:")
+        .and_then(|id| uuid::Uuid::parse_str(id).ok())
+        .ok_or_else(|| anyhow::anyhow!("Invalid category ID"))?;
+
+    let inputs = &interaction.data.components;
+    let input = |index: usize| inputs.get(index)
+        .and_then(|row| row.components.first())
+        .and_then(|comp| {
+            if let serenity::all::ActionRowComponent::InputText(input) = comp {
+                input.value.as_ref().and_then(|v| if v.is_empty() { None } else { Some(v.clone()) })
+            } else {
+                None
+            }
+        });
+
+    let name = input(0).unwrap_or_else(|| "Support".to_string());
+    let description = input(1).unwrap_or_else(|| "Support tickets".to_string());
+    let emoji = input(2);
+
+    let old_name: Option<(String,)> = sqlx::query_as(
+        "SELECT name FROM ticket_categories WHERE id = $1 AND guild_id = $2"
+    )
+    .bind(category_uuid)
+    .bind(guild_id.get() as i64)
+    .fetch_optional(&db.pool)
+    .await?;
+
+    let updated = crate::database::ticket::update_ticket_category(
+        &db.pool,
+        guild_id.get() as i64,
+        category_uuid,
+        &name,
+        &description,
+        emoji.as_deref(),
+    ).await?;
+
+    let embed = match old_name {
+        Some((old_name,)) if updated => {
+            info!("Updated category '{}' ({}) in guild {}", name, category_uuid, guild_id.get());
+
+            if old_name != name {
+                let mut redis_conn = db.redis.clone();
+                let _: () = redis::cmd("SREM")
+                    .arg(format!("categories:{}", guild_id.get()))
+                    .arg(&old_name)
+                    .query_async(&mut redis_conn)
+                    .await
+                    .unwrap_or(());
+                let _: () = redis::cmd("SADD")
+                    .arg(format!("categories:{}", guild_id.get()))
+                    .arg(&name)
+                    .query_async(&mut redis_conn)
+                    .await
+                    .unwrap_or(());
+            }
+
+            create_success_embed(
+                "Category Updated",
+                format!("Ticket category **{}** has been updated. Posted panels will show the change.", name)
+            )
+        }
+        _ => create_error_embed("Error", "Category not found"),
+    };
+
+    interaction
+        .create_response(
+            &ctx.http,
+            serenity::all::CreateInteractionResponse::Message(
+                serenity::all::CreateInteractionResponseMessage::new()
+                    .embed(embed)
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+
+    if updated {
+        crate::utils::panel::refresh_guild_panels(&ctx.http, db, guild_id.get() as i64).await;
+    }
+
+    Ok(())
+}
+
 pub async fn handle_category_delete_confirm(
     ctx: &Context,
     interaction: &ComponentInteraction,
@@ -1549,6 +1768,8 @@ pub async fn handle_category_delete_confirm(
                 ),
             )
             .await?;
+
+        crate::utils::panel::refresh_guild_panels(&ctx.http, db, guild_id.get() as i64).await;
     }
 
     Ok(())
@@ -1898,7 +2119,7 @@ async fn send_panel_with_categories(
     };
 
     sqlx::query(
-        "INSERT INTO ticket_panel (guild_id, channel_id, message_id, title, description, selection_type) VALUES ($1, $2, $3, $4, $5, $6)"
+        "INSERT INTO ticket_panel (guild_id, channel_id, message_id, title, description, selection_type, uses_guild_embed) VALUES ($1, $2, $3, $4, $5, $6, TRUE)"
     )
     .bind(guild_id.get() as i64)
     .bind(channel_id.get() as i64)
@@ -2053,7 +2274,7 @@ async fn send_panel_with_custom_colors(
     ).await?;
 
     sqlx::query(
-        "INSERT INTO ticket_panel (guild_id, channel_id, message_id, title, description, selection_type) VALUES ($1, $2, $3, $4, $5, $6)"
+        "INSERT INTO ticket_panel (guild_id, channel_id, message_id, title, description, selection_type, uses_guild_embed) VALUES ($1, $2, $3, $4, $5, $6, TRUE)"
     )
     .bind(guild_id.get() as i64)
     .bind(channel_id.get() as i64)
@@ -6,6 +6,7 @@ mod models;
 mod prefix;
 mod utils;
 mod logging;
+mod metrics;
 
 use anyhow::Result;
 use serenity::all::{
@@ -13,7 +14,7 @@ use serenity::all::{
     GatewayIntents, Interaction, Message, Ready, ActivityData, OnlineStatus,
 };
 use std::sync::Arc;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 struct Handler {
     db: Arc<database::Database>,
@@ -44,6 +45,18 @@ impl EventHandler for Handler {
             commands::tag::register(),
             commands::assign::register(),
             commands::channelname::register(),
+            commands::away::register(),
+            commands::back::register(),
+            commands::staff::register(),
+            commands::escalation::register(),
+            commands::merge::register(),
+            commands::staffstats::register(),
+            commands::export::register(),
+            commands::digest::register(),
+            commands::autorespond::register(),
+            commands::timezone::register(),
+            commands::reminder::register(),
+            commands::followup::register(),
         ];
 
         for command in commands {
@@ -81,6 +94,35 @@ impl EventHandler for Handler {
         }
     }
 
+    async fn message_delete(
+        &self,
+        _ctx: Context,
+        channel_id: serenity::all::ChannelId,
+        deleted_message_id: serenity::all::MessageId,
+        _guild_id: Option<serenity::all::GuildId>,
+    ) {
+        match database::ticket::flag_missing_panel(&self.db.pool, deleted_message_id.get() as i64).await {
+            Ok(true) => warn!("Panel {} in channel {} was deleted, flagged as missing", deleted_message_id, channel_id),
+            Ok(false) => {}
+            Err(e) => error!("Failed to flag deleted panel {}: {}", deleted_message_id, e),
+        }
+    }
+
+    async fn message_delete_bulk(
+        &self,
+        _ctx: Context,
+        channel_id: serenity::all::ChannelId,
+        multiple_deleted_messages_ids: Vec<serenity::all::MessageId>,
+        _guild_id: Option<serenity::all::GuildId>,
+    ) {
+        let message_ids: Vec<i64> = multiple_deleted_messages_ids.iter().map(|id| id.get() as i64).collect();
+        match database::ticket::flag_missing_panels(&self.db.pool, &message_ids).await {
+            Ok(0) => {}
+            Ok(count) => warn!("{} panel(s) in channel {} were bulk deleted, flagged as missing", count, channel_id),
+            Err(e) => error!("Failed to flag bulk deleted panels in channel {}: {}", channel_id, e),
+        }
+    }
+
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
         match interaction {
             Interaction::Command(command) => {
@@ -100,6 +142,7 @@ impl EventHandler for Handler {
                     &options_str
                 ).await;
 
+                let started = std::time::Instant::now();
                 let result = match command.data.name.as_str() {
                     "setup" => commands::setup::run(&ctx, &command, &self.db).await,
                     "supportrole" => commands::supportrole::run(&ctx, &command, &self.db).await,
@@ -117,8 +160,21 @@ impl EventHandler for Handler {
                     "tag" => commands::tag::run(&ctx, &command, &self.db).await,
                     "assign" => commands::assign::run(&ctx, &command, &self.db).await,
                     "channel-name" => commands::channelname::run(&ctx, &command, &self.db).await,
+                    "away" => commands::away::run(&ctx, &command, &self.db).await,
+                    "back" => commands::back::run(&ctx, &command, &self.db).await,
+                    "staff" => commands::staff::run(&ctx, &command, &self.db).await,
+                    "escalation" => commands::escalation::run(&ctx, &command, &self.db).await,
+                    "merge" => commands::merge::run(&ctx, &command, &self.db).await,
+                    "staffstats" => commands::staffstats::run(&ctx, &command, &self.db).await,
+                    "export" => commands::export::run(&ctx, &command, &self.db).await,
+                    "digest" => commands::digest::run(&ctx, &command, &self.db).await,
+                    "autorespond" => commands::autorespond::run(&ctx, &command, &self.db).await,
+                    "timezone" => commands::timezone::run(&ctx, &command, &self.db).await,
+                    "reminder" => commands::reminder::run(&ctx, &command, &self.db).await,
+                    "followup" => commands::followup::run(&ctx, &command, &self.db).await,
                     _ => Ok(()),
                 };
+                metrics::record_command("slash", &command.data.name, started.elapsed(), result.is_ok());
 
                 if let Err(e) = result {
                     error!("Command error: {}", e);
@@ -164,6 +220,7 @@ impl EventHandler for Handler {
                     _ => {}
                 }
 
+                let started = std::time::Instant::now();
                 let result = match component.data.custom_id.as_str() {
                     "ticket_create" => handlers::button::handle_ticket_create(&ctx, &component, &self.db).await,
                     "ticket_claim" => handlers::button::handle_ticket_claim(&ctx, &component, &self.db).await,
@@ -188,6 +245,7 @@ impl EventHandler for Handler {
                     "category_edit_select" => handlers::menus::handle_category_edit_select(&ctx, &component, &self.db).await,
                     "panel_category_select" => handlers::menus::handle_panel_category_select(&ctx, &component, &self.db).await,
                     "ticket_category_select" => handlers::menus::handle_ticket_category_select(&ctx, &component, &self.db).await,
+                    id if id.starts_with("category_edit_confirm_") => handlers::menus::handle_category_edit_confirm(&ctx, &component, &self.db).await,
                     id if id.starts_with("category_delete_confirm_") => handlers::menus::handle_category_delete_confirm(&ctx, &component, &self.db).await,
                     id if id.starts_with("panel_style_button_") => handlers::menus::handle_panel_style_choice(&ctx, &component, &self.db, true).await,
                     id if id.starts_with("panel_style_dropdown_") => handlers::menus::handle_panel_style_choice(&ctx, &component, &self.db, false).await,
@@ -202,8 +260,13 @@ impl EventHandler for Handler {
                     id if id.starts_with("panel_finish_custom:") => commands::panel::handle_finish_custom(&ctx, &component, &self.db).await,
                     id if id.starts_with("ticket_create:") => handlers::button::handle_ticket_create_category(&ctx, &component, &self.db).await,
                     id if id.starts_with("ticket_limit_") => handlers::button::handle_ticket_limit_button(&ctx, &component, &self.db).await,
+                    id if id.starts_with("tag_builder:") => commands::tag::handle_builder_button(&ctx, &component, &self.db).await,
+                    id if id.starts_with("tag_browser") => commands::tag::handle_browser(&ctx, &component, &self.db).await,
+                    id if id.starts_with("reminder_") => commands::reminder::handle_delivery_button(&ctx, &component, &self.db).await,
+                    id if id.starts_with("ticketstats_page:") => prefix::owner::handle_ticket_stats_page(&ctx, &component, &self.db, self.owner_id).await,
                     _ => Ok(()),
                 };
+                metrics::record_interaction("component", started.elapsed(), result.is_ok());
 
                 if let Err(e) = result {
                     error!("Component interaction error: {}", e);
@@ -233,11 +296,15 @@ impl EventHandler for Handler {
                     modal.channel_id.get()
                 ).await;
 
+                let started = std::time::Instant::now();
                 let result = match modal.data.custom_id.as_str() {
                     "panel_edit_modal" => handlers::menus::handle_panel_edit_modal(&ctx, &modal, &self.db).await,
                     "category_add_modal" => handlers::menus::handle_category_add_modal(&ctx, &modal, &self.db).await,
+                    id if id.starts_with("category_edit_modal:") => handlers::menus::handle_category_edit_modal(&ctx, &modal, &self.db).await,
+                    id if id.starts_with("tag_builder_modal:") => commands::tag::handle_builder_modal(&ctx, &modal, &self.db).await,
                     _ => Ok(()),
                 };
+                metrics::record_interaction("modal", started.elapsed(), result.is_ok());
 
                 if let Err(e) = result {
                     error!("Modal interaction error: {}", e);
@@ -254,6 +321,18 @@ impl EventHandler for Handler {
                         .await;
                 }
             }
+            Interaction::Autocomplete(autocomplete) => {
+                let result = match autocomplete.data.name.as_str() {
+                    "tag" | "autorespond" => commands::tag::autocomplete(&ctx, &autocomplete, &self.db).await,
+                    "timezone" => commands::timezone::autocomplete(&ctx, &autocomplete).await,
+                    "reminder" => commands::reminder::autocomplete(&ctx, &autocomplete, &self.db).await,
+                    _ => Ok(()),
+                };
+
+                if let Err(e) = result {
+                    error!("Autocomplete error: {}", e);
+                }
+            }
             _ => {}
         }
     }
@@ -362,14 +441,33 @@ impl EventHandler for Handler {
             }
 
             if msg.author.id.get() as i64 != ticket.owner_id {
-                let _ = database::ticket::mark_ticket_has_messages(&self.db.pool, ticket.id).await;
+                // Only support staff count towards response times and staff activity.
+                let is_staff = utils::has_support_role_or_admin(&ctx, msg.author.id, ticket.guild_id, &self.db).await.unwrap_or(false);
+                let responder = is_staff.then_some(msg.author.id.get() as i64);
+                let _ = database::ticket::mark_ticket_has_messages(&self.db.pool, ticket.id, responder).await;
+                if is_staff {
+                    let _ = database::stats::record_staff_activity(&self.db.pool, ticket.guild_id, msg.author.id.get() as i64, 1, 0).await;
+                }
             }
 
             let _ = database::ticket::update_ticket_last_message(&self.db.pool, ticket.id).await;
+
+            if msg.author.id.get() as i64 == ticket.owner_id {
+                let _ = database::ticket::cancel_reply_followups(&self.db.pool, ticket.id).await;
+            }
+
+            if msg.author.id.get() as i64 == ticket.owner_id && !msg.content.is_empty() {
+                if let Err(e) = utils::autoresponder::respond(&ctx, &self.db, &msg, &ticket).await {
+                    error!("Auto-responder error: {}", e);
+                }
+            }
         }
     }
 }
 
+// Shorter than the 60 second job interval so the next tick can take the lock again.
+const JOB_LOCK_TTL: std::time::Duration = std::time::Duration::from_secs(55);
+
 #[tokio::main]
 async fn main() -> Result<()> {
     tracing_subscriber::fmt()
@@ -398,10 +496,15 @@ async fn main() -> Result<()> {
     let http_clone = Arc::new(serenity::all::Http::new(&config.discord_token));
 
     tokio::spawn(async move {
-        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600));
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
         loop {
             interval.tick().await;
-            if let Err(e) = process_escalations(&db_clone, &http_clone).await {
+            if !db_clone.try_job_lock("escalations", JOB_LOCK_TTL).await {
+                continue;
+            }
+            let result = process_escalations(&db_clone, &http_clone).await;
+            metrics::record_job("escalations", result.is_ok());
+            if let Err(e) = result {
                 error!("Error processing escalations: {}", e);
             }
         }
@@ -414,12 +517,34 @@ async fn main() -> Result<()> {
         let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(30));
         loop {
             interval.tick().await;
-            if let Err(e) = process_reminders(&db_clone2, &http_clone2).await {
+            let result = process_reminders(&db_clone2, &http_clone2).await;
+            metrics::record_job("reminders", result.is_ok());
+            if let Err(e) = result {
                 error!("Error processing reminders: {}", e);
             }
         }
     });
 
+    let db_clone3 = Arc::clone(&db);
+    let http_clone3 = Arc::new(serenity::all::Http::new(&config.discord_token));
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            if !db_clone3.try_job_lock("digests", JOB_LOCK_TTL).await {
+                continue;
+            }
+            let result = process_digests(&db_clone3, &http_clone3).await;
+            metrics::record_job("digests", result.is_ok());
+            if let Err(e) = result {
+                error!("Error processing digests: {}", e);
+            }
+        }
+    });
+
+
+    let db_metrics = Arc::clone(&db);
 
     let mut client = Client::builder(&config.discord_token, intents)
         .event_handler(Handler {
@@ -428,6 +553,15 @@ async fn main() -> Result<()> {
         })
         .await?;
 
+    if let Some(addr) = config.metrics_addr.clone() {
+        let shard_manager = Arc::clone(&client.shard_manager);
+        tokio::spawn(async move {
+            if let Err(e) = metrics::serve(addr, db_metrics, shard_manager).await {
+                error!("Metrics endpoint stopped: {}", e);
+            }
+        });
+    }
+
     info!("Starting bot...");
     client.start().await?;
 
@@ -435,53 +569,83 @@ async fn main() -> Result<()> {
 }
 
 async fn process_escalations(db: &database::Database, http: &serenity::all::Http) -> Result<()> {
+    let _ = database::staff::clear_expired_away(&db.pool).await;
+
     let escalations = database::ticket::get_active_escalations(&db.pool).await?;
 
-    for (ticket_id, seconds_since_last_ping) in escalations {
-        if seconds_since_last_ping >= 3600 {
-            if let Ok(Some(ticket)) = database::ticket::get_ticket_by_id(&db.pool, ticket_id).await {
-                if ticket.is_claimed() {
-                    let _ = database::ticket::deactivate_escalation(&db.pool, ticket_id).await;
-                    continue;
-                }
+    for escalation in escalations {
+        let ticket = match database::ticket::get_ticket_by_id(&db.pool, escalation.ticket_id).await {
+            Ok(Some(ticket)) => ticket,
+            _ => {
+                let _ = database::ticket::deactivate_escalation(&db.pool, escalation.ticket_id).await;
+                continue;
+            }
+        };
+
+        if ticket.is_claimed() {
+            let _ = database::ticket::deactivate_escalation(&db.pool, ticket.id).await;
+            continue;
+        }
+
+        let tiers = database::ticket::get_escalation_tiers(&db.pool, ticket.guild_id).await.unwrap_or_default();
 
+        if tiers.is_empty() {
+            if escalation.seconds_since_last_ping >= 3600 {
                 let support_roles = database::ticket::get_support_roles(&db.pool, ticket.guild_id).await?;
+                let role_ids: Vec<i64> = support_roles.iter().map(|r| r.role_id).collect();
 
-                for role in &support_roles {
-                    if let Ok(guild_id) = serenity::all::GuildId::new(ticket.guild_id as u64).to_partial_guild(http).await {
-                        if let Ok(members) = guild_id.id.members(http, None, None).await {
-                            let role_id = serenity::all::RoleId::new(role.role_id as u64);
-
-                            for member in members {
-                                if member.roles.contains(&role_id) && !member.user.bot {
-                                    if let Ok(dm) = member.user.create_dm_channel(http).await {
-                                        let _ = dm.send_message(
-                                            http,
-                                            serenity::all::CreateMessage::new()
-                                                .embed(utils::create_embed(
-                                                    "Escalated Ticket Reminder",
-                                                    format!(
-                                                        "**Reminder:** This ticket still needs attention!\n\n\
-                                                         **Ticket:** #{}\n\
-                                                         **User:** <@{}>\n\
-                                                         **Channel:** <#{}>\n\n\
-                                                         Please claim this ticket to stop these reminders.",
-                                                        ticket.ticket_number,
-                                                        ticket.owner_id,
-                                                        ticket.channel_id
-                                                    )
-                                                ).color(0xED4245))
-                                        ).await;
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+                dm_escalation_roles(db, http, &ticket, &role_ids, escalation_reminder_embed(&ticket, None)).await;
+                let _ = database::ticket::update_escalation_ping_time(&db.pool, ticket.id).await;
+            }
+            continue;
+        }
 
-                let _ = database::ticket::update_escalation_ping_time(&db.pool, ticket_id).await;
-            } else {
-                let _ = database::ticket::deactivate_escalation(&db.pool, ticket_id).await;
+        // Jump straight to the highest tier whose delay has passed, so a bot restart
+        // doesn't replay every intermediate tier.
+        let elapsed_minutes = escalation.seconds_since_escalated / 60;
+        let due_tier = tiers
+            .iter()
+            .filter(|t| t.tier > escalation.current_tier && t.delay_minutes as i64 <= elapsed_minutes)
+            .max_by_key(|t| t.tier);
+
+        if let Some(tier) = due_tier {
+            notify_escalation_tier(db, http, &ticket, tier).await;
+            let _ = database::ticket::set_escalation_tier(&db.pool, ticket.id, tier.tier).await;
+
+            let channel_embed = utils::create_embed(
+                format!("Escalation Tier {}", tier.tier),
+                format!(
+                    "This ticket is still unclaimed and has been escalated to <@&{}>.\n\
+                     **Tier:** {} of {}\n\
+                     **Delivery:** {}",
+                    tier.role_id,
+                    tier.tier,
+                    tiers.last().map(|t| t.tier).unwrap_or(tier.tier),
+                    tier.describe_delivery()
+                )
+            ).color(0xED4245);
+            let _ = serenity::all::ChannelId::new(ticket.channel_id as u64)
+                .send_message(http, serenity::all::CreateMessage::new().embed(channel_embed))
+                .await;
+
+            if let Ok(guild) = database::ticket::get_or_create_guild(&db.pool, ticket.guild_id).await {
+                if let Some(log_channel_id) = guild.log_channel_id {
+                    let log_embed = utils::create_embed(
+                        "Ticket Escalated",
+                        format!(
+                            "Ticket: #{}\nChannel: <#{}>\nOwner: <@{}>\nTier: {} (<@&{}>)",
+                            ticket.ticket_number, ticket.channel_id, ticket.owner_id, tier.tier, tier.role_id
+                        )
+                    ).color(0xED4245);
+                    let _ = serenity::all::ChannelId::new(log_channel_id as u64)
+                        .send_message(http, serenity::all::CreateMessage::new().embed(log_embed))
+                        .await;
+                }
+            }
+        } else if escalation.seconds_since_last_ping >= 3600 {
+            if let Some(tier) = tiers.iter().find(|t| t.tier == escalation.current_tier) {
+                notify_escalation_tier(db, http, &ticket, tier).await;
+                let _ = database::ticket::update_escalation_ping_time(&db.pool, ticket.id).await;
             }
         }
     }
@@ -489,45 +653,152 @@ async fn process_escalations(db: &database::Database, http: &serenity::all::Http
     Ok(())
 }
 
+fn escalation_reminder_embed(ticket: &models::Ticket, tier: Option<i32>) -> serenity::all::CreateEmbed {
+    let tier_line = tier.map(|t| format!("**Tier:** {}\n", t)).unwrap_or_default();
+
+    utils::create_embed(
+        "Escalated Ticket Reminder",
+        format!(
+            "**Reminder:** This ticket still needs attention!\n\n\
+             {}**Ticket:** #{}\n\
+             **User:** <@{}>\n\
+             **Channel:** <#{}>\n\n\
+             Please claim this ticket to stop these reminders.",
+            tier_line,
+            ticket.ticket_number,
+            ticket.owner_id,
+            ticket.channel_id
+        )
+    ).color(0xED4245)
+}
+
+async fn notify_escalation_tier(
+    db: &database::Database,
+    http: &serenity::all::Http,
+    ticket: &models::Ticket,
+    tier: &models::EscalationTier,
+) {
+    let embed = escalation_reminder_embed(ticket, Some(tier.tier));
+
+    match tier.channel_id {
+        Some(channel_id) if tier.delivery == "channel" => {
+            let _ = serenity::all::ChannelId::new(channel_id as u64)
+                .send_message(
+                    http,
+                    serenity::all::CreateMessage::new()
+                        .content(format!("<@&{}>", tier.role_id))
+                        .embed(embed)
+                )
+                .await;
+        }
+        _ => dm_escalation_roles(db, http, ticket, &[tier.role_id], embed).await,
+    }
+}
+
+async fn dm_escalation_roles(
+    db: &database::Database,
+    http: &serenity::all::Http,
+    ticket: &models::Ticket,
+    role_ids: &[i64],
+    embed: serenity::all::CreateEmbed,
+) {
+    let away_staff = database::staff::get_away_user_ids(&db.pool, ticket.guild_id).await.unwrap_or_default();
+    let role_ids: Vec<serenity::all::RoleId> = role_ids
+        .iter()
+        .map(|id| serenity::all::RoleId::new(*id as u64))
+        .collect();
+
+    let Ok(members) = serenity::all::GuildId::new(ticket.guild_id as u64).members(http, None, None).await else {
+        return;
+    };
+
+    for member in members {
+        if member.user.bot
+            || away_staff.contains(&(member.user.id.get() as i64))
+            || !member.roles.iter().any(|r| role_ids.contains(r))
+        {
+            continue;
+        }
+
+        if let Ok(dm) = member.user.create_dm_channel(http).await {
+            let _ = dm.send_message(http, serenity::all::CreateMessage::new().embed(embed.clone())).await;
+        }
+    }
+}
+
 async fn process_reminders(db: &database::Database, http: &serenity::all::Http) -> Result<()> {
-    let reminders = database::ticket::get_pending_reminders(&db.pool).await?;
+    let claim = uuid::Uuid::new_v4();
+    let reminders = database::ticket::claim_due_reminders(
+        &db.pool,
+        claim,
+        utils::reminder::CLAIM_LEASE_SECONDS,
+        utils::reminder::CLAIM_BATCH_SIZE,
+    )
+    .await?;
 
     for reminder in reminders {
-        let user_id = serenity::all::UserId::new(reminder.user_id as u64);
-        let channel_id = serenity::all::ChannelId::new(reminder.channel_id as u64);
-
-        if let Ok(user) = user_id.to_user(http).await {
-            if let Ok(dm) = user.create_dm_channel(http).await {
-                let created_timestamp = reminder.created_at.timestamp();
-                let message_link = if let (Some(guild_id), Some(message_id)) = (reminder.guild_id, reminder.message_id) {
-                    format!("\n\n[Jump to Message](https://discord.com/channels/{}/{}/{})", guild_id, reminder.channel_id, message_id)
-                } else {
-                    String::new()
-                };
-
-                let embed = utils::create_embed(
-                    "Reminder",
-                    format!(
-                        "<@{}> {}\n\n\
-                        **Set:** <t:{}:F> (<t:{}:R>){}",
-                        user.id.get(),
-                        reminder.reason,
-                        created_timestamp,
-                        created_timestamp,
-                        message_link
-                    )
-                ).color(0x5865F2);
+        // Earlier deliveries in the batch may have used up the lease; skip the
+        // reminder if another instance has claimed it since or it was rescheduled.
+        match database::ticket::renew_reminder_claim(&db.pool, reminder.id, claim, utils::reminder::CLAIM_LEASE_SECONDS).await {
+            Ok(true) => {}
+            Ok(false) => continue,
+            Err(e) => {
+                error!("Failed to renew the claim on reminder {}: {}", reminder.id, e);
+                continue;
+            }
+        }
 
-                let _ = dm.send_message(
-                    http,
-                    serenity::all::CreateMessage::new().embed(embed)
-                ).await;
+        let now = chrono::Utc::now();
+        let next = match reminder.ticket_id {
+            Some(_) => None,
+            None => utils::reminder::next_occurrence(&reminder, now),
+        };
+
+        let result = match reminder.ticket_id {
+            Some(ticket_id) => utils::reminder::deliver_followup(http, db, &reminder, ticket_id).await,
+            None => deliver_reminder(http, &reminder, next).await,
+        };
+
+        let recorded = match result {
+            Ok(status) => database::ticket::finish_reminder_delivery(&db.pool, reminder.id, claim, next, status, None).await,
+            Err(e) if reminder.attempts + 1 >= utils::reminder::MAX_DELIVERY_ATTEMPTS => {
+                error!("Giving up on reminder {} after {} attempts: {}", reminder.id, reminder.attempts + 1, e);
+                database::ticket::finish_reminder_delivery(&db.pool, reminder.id, claim, next, "failed", Some(&e.to_string())).await
+            }
+            Err(e) => {
+                let retry_at = now + utils::reminder::retry_delay(reminder.attempts);
+                warn!("Reminder {} failed ({}), retrying at {}", reminder.id, e, retry_at);
+                database::ticket::schedule_reminder_retry(&db.pool, reminder.id, claim, retry_at, &e.to_string()).await
             }
+        };
+
+        if let Err(e) = recorded {
+            error!("Failed to record delivery of reminder {}: {}", reminder.id, e);
         }
+    }
+
+    Ok(())
+}
 
+/// Posts the reminder in the channel it was set in (first attempt only, so retries
+/// don't repeat it) and DMs the user. Only a failed DM counts as a failed delivery.
+async fn deliver_reminder(
+    http: &serenity::all::Http,
+    reminder: &models::Reminder,
+    next: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<&'static str> {
+    let user_id = serenity::all::UserId::new(reminder.user_id as u64);
+    let channel_id = serenity::all::ChannelId::new(reminder.channel_id as u64);
+    let created_timestamp = reminder.created_at.timestamp();
+
+    let next_note = match next {
+        Some(at) => format!("\n**Next:** <t:{}:F> (<t:{}:R>)", at.timestamp(), at.timestamp()),
+        None => String::new(),
+    };
+
+    if reminder.attempts == 0 {
         if let Ok(channel) = channel_id.to_channel(http).await {
             if let Some(guild_channel) = channel.guild() {
-                let created_timestamp = reminder.created_at.timestamp();
                 let message_link = if let Some(message_id) = reminder.message_id {
                     format!("\n\n[Jump to Original Message](https://discord.com/channels/{}/{}/{})",
                         reminder.guild_id.unwrap_or(0),
@@ -541,11 +812,12 @@ async fn process_reminders(db: &database::Database, http: &serenity::all::Http)
                     "Reminder",
                     format!(
                         "<@{}> {}\n\n\
-                        **Set:** <t:{}:F> (<t:{}:R>){}",
+                        **Set:** <t:{}:F> (<t:{}:R>){}{}",
                         reminder.user_id,
                         reminder.reason,
                         created_timestamp,
                         created_timestamp,
+                        next_note,
                         message_link
                     )
                 ).color(0x5865F2);
@@ -556,10 +828,74 @@ async fn process_reminders(db: &database::Database, http: &serenity::all::Http)
                 ).await;
             }
         }
+    }
 
-        let _ = database::ticket::mark_reminder_completed(&db.pool, reminder.id).await;
+    let user = user_id.to_user(http).await?;
+    let dm = user.create_dm_channel(http).await?;
+    let message_link = if let (Some(guild_id), Some(message_id)) = (reminder.guild_id, reminder.message_id) {
+        format!("\n\n[Jump to Message](https://discord.com/channels/{}/{}/{})", guild_id, reminder.channel_id, message_id)
+    } else {
+        String::new()
+    };
+
+    let embed = utils::create_embed(
+        "Reminder",
+        format!(
+            "<@{}> {}\n\n\
+            **Set:** <t:{}:F> (<t:{}:R>){}{}",
+            user.id.get(),
+            reminder.reason,
+            created_timestamp,
+            created_timestamp,
+            next_note,
+            message_link
+        )
+    ).color(0x5865F2);
+
+    dm.send_message(
+        http,
+        serenity::all::CreateMessage::new()
+            .embed(embed)
+            .components(vec![utils::reminder::delivery_buttons(reminder.id)])
+    ).await?;
+
+    Ok("delivered")
+}
+
+async fn process_digests(db: &database::Database, http: &serenity::all::Http) -> Result<()> {
+    let now = chrono::Utc::now();
+    let digests = database::digest::get_enabled_digests(&db.pool).await?;
+
+    for digest in digests.iter().filter(|d| utils::digest::is_due(d, now)) {
+        // Marked first so a guild with a broken log channel isn't retried every minute.
+        if let Err(e) = database::digest::mark_digest_sent(&db.pool, digest.guild_id).await {
+            error!("Failed to mark digest sent for guild {}: {}", digest.guild_id, e);
+            continue;
+        }
+
+        let guild = match database::ticket::get_or_create_guild(&db.pool, digest.guild_id).await {
+            Ok(guild) => guild,
+            Err(e) => {
+                error!("Failed to load guild {} for digest: {}", digest.guild_id, e);
+                continue;
+            }
+        };
+        let Some(log_channel_id) = guild.log_channel_id else {
+            continue;
+        };
+
+        match utils::digest::build_digest_embed(db, digest).await {
+            Ok(embed) => {
+                if let Err(e) = serenity::all::ChannelId::new(log_channel_id as u64)
+                    .send_message(http, serenity::all::CreateMessage::new().embed(embed))
+                    .await
+                {
+                    error!("Failed to send digest for guild {}: {}", digest.guild_id, e);
+                }
+            }
+            Err(e) => error!("Failed to build digest for guild {}: {}", digest.guild_id, e),
+        }
     }
 
     Ok(())
 }
-
@@ -0,0 +1,246 @@
+use anyhow::Result;
+use prometheus::{
+    Encoder, Gauge, GaugeVec, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+use serenity::gateway::ShardManager;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tracing::{error, info};
+
+use crate::database::Database;
+
+static METRICS: OnceLock<Metrics> = OnceLock::new();
+
+const LATENCY_BUCKETS: &[f64] = &[0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+const REDIS_PING_TIMEOUT: Duration = Duration::from_secs(1);
+
+struct Metrics {
+    registry: Registry,
+    tickets_opened: IntCounterVec,
+    tickets_closed: IntCounterVec,
+    commands: IntCounterVec,
+    command_duration: HistogramVec,
+    interactions: IntCounterVec,
+    interaction_duration: HistogramVec,
+    job_runs: IntCounterVec,
+    job_failures: IntCounterVec,
+    pool_size: IntGauge,
+    pool_idle: IntGauge,
+    redis_latency: Gauge,
+    redis_up: IntGauge,
+    gateway_latency: GaugeVec,
+}
+
+impl Metrics {
+    fn new() -> Result<Self> {
+        let registry = Registry::new_custom(Some("ticketbot".to_string()), None)?;
+
+        let tickets_opened = IntCounterVec::new(
+            Opts::new("tickets_opened_total", "Tickets opened"),
+            &["guild_id"],
+        )?;
+        let tickets_closed = IntCounterVec::new(
+            Opts::new("tickets_closed_total", "Tickets closed"),
+            &["guild_id"],
+        )?;
+        let commands = IntCounterVec::new(
+            Opts::new("commands_total", "Commands handled"),
+            &["kind", "command", "status"],
+        )?;
+        let command_duration = HistogramVec::new(
+            HistogramOpts::new("command_duration_seconds", "Time spent handling a command")
+                .buckets(LATENCY_BUCKETS.to_vec()),
+            &["kind", "command"],
+        )?;
+        let interactions = IntCounterVec::new(
+            Opts::new("interactions_total", "Component and modal interactions handled"),
+            &["kind", "status"],
+        )?;
+        let interaction_duration = HistogramVec::new(
+            HistogramOpts::new("interaction_duration_seconds", "Time spent handling an interaction")
+                .buckets(LATENCY_BUCKETS.to_vec()),
+            &["kind"],
+        )?;
+        let job_runs = IntCounterVec::new(
+            Opts::new("job_runs_total", "Background job runs"),
+            &["job"],
+        )?;
+        let job_failures = IntCounterVec::new(
+            Opts::new("job_failures_total", "Background job runs that returned an error"),
+            &["job"],
+        )?;
+        let pool_size = IntGauge::new("db_pool_connections", "Open Postgres pool connections")?;
+        let pool_idle = IntGauge::new("db_pool_idle_connections", "Idle Postgres pool connections")?;
+        let redis_latency = Gauge::new("redis_ping_seconds", "Redis PING round trip at scrape time")?;
+        let redis_up = IntGauge::new("redis_up", "Whether Redis answered PING within the timeout at scrape time")?;
+        let gateway_latency = GaugeVec::new(
+            Opts::new("gateway_latency_seconds", "Discord gateway heartbeat latency"),
+            &["shard"],
+        )?;
+
+        registry.register(Box::new(tickets_opened.clone()))?;
+        registry.register(Box::new(tickets_closed.clone()))?;
+        registry.register(Box::new(commands.clone()))?;
+        registry.register(Box::new(command_duration.clone()))?;
+        registry.register(Box::new(interactions.clone()))?;
+        registry.register(Box::new(interaction_duration.clone()))?;
+        registry.register(Box::new(job_runs.clone()))?;
+        registry.register(Box::new(job_failures.clone()))?;
+        registry.register(Box::new(pool_size.clone()))?;
+        registry.register(Box::new(pool_idle.clone()))?;
+        registry.register(Box::new(redis_latency.clone()))?;
+        registry.register(Box::new(redis_up.clone()))?;
+        registry.register(Box::new(gateway_latency.clone()))?;
+
+        Ok(Self {
+            registry,
+            tickets_opened,
+            tickets_closed,
+            commands,
+            command_duration,
+            interactions,
+            interaction_duration,
+            job_runs,
+            job_failures,
+            pool_size,
+            pool_idle,
+            redis_latency,
+            redis_up,
+            gateway_latency,
+        })
+    }
+}
+
+fn status(ok: bool) -> &'static str {
+    if ok { "ok" } else { "error" }
+}
+
+// The record_* helpers are no-ops unless METRICS_ADDR is set, so call sites
+// don't need to check whether metrics are enabled.
+
+pub fn record_ticket_opened(guild_id: i64) {
+    if let Some(m) = METRICS.get() {
+        m.tickets_opened.with_label_values(&[&guild_id.to_string()]).inc();
+    }
+}
+
+pub fn record_ticket_closed(guild_id: i64) {
+    if let Some(m) = METRICS.get() {
+        m.tickets_closed.with_label_values(&[&guild_id.to_string()]).inc();
+    }
+}
+
+pub fn record_command(kind: &str, command: &str, elapsed: Duration, ok: bool) {
+    if let Some(m) = METRICS.get() {
+        m.commands.with_label_values(&[kind, command, status(ok)]).inc();
+        m.command_duration.with_label_values(&[kind, command]).observe(elapsed.as_secs_f64());
+    }
+}
+
+pub fn record_interaction(kind: &str, elapsed: Duration, ok: bool) {
+    if let Some(m) = METRICS.get() {
+        m.interactions.with_label_values(&[kind, status(ok)]).inc();
+        m.interaction_duration.with_label_values(&[kind]).observe(elapsed.as_secs_f64());
+    }
+}
+
+pub fn record_job(job: &str, ok: bool) {
+    if let Some(m) = METRICS.get() {
+        m.job_runs.with_label_values(&[job]).inc();
+        if !ok {
+            m.job_failures.with_label_values(&[job]).inc();
+        }
+    }
+}
+
+/// Gauges that are cheaper to sample on scrape than to keep up to date.
+async fn refresh_gauges(m: &Metrics, db: &Database, shard_manager: &ShardManager) {
+    m.pool_size.set(db.pool.size() as i64);
+    m.pool_idle.set(db.pool.num_idle() as i64);
+
+    let mut redis = db.redis.clone();
+    let started = Instant::now();
+    // A hung Redis must not stall the scrape, so give up after a short timeout.
+    let ping = tokio::time::timeout(REDIS_PING_TIMEOUT, redis::cmd("PING").query_async::<String>(&mut redis)).await;
+    if let Ok(Ok(_)) = ping {
+        m.redis_latency.set(started.elapsed().as_secs_f64());
+        m.redis_up.set(1);
+    } else {
+        m.redis_up.set(0);
+    }
+
+    for (shard_id, runner) in shard_manager.runners.lock().await.iter() {
+        if let Some(latency) = runner.latency {
+            m.gateway_latency
+                .with_label_values(&[&shard_id.to_string()])
+                .set(latency.as_secs_f64());
+        }
+    }
+}
+
+async fn render(db: &Database, shard_manager: &ShardManager) -> Result<Vec<u8>> {
+    let m = METRICS.get().ok_or_else(|| anyhow::anyhow!("Metrics are not initialised"))?;
+    refresh_gauges(m, db, shard_manager).await;
+
+    let mut buffer = Vec::new();
+    TextEncoder::new().encode(&m.registry.gather(), &mut buffer)?;
+    Ok(buffer)
+}
+
+async fn handle_connection(
+    mut stream: tokio::net::TcpStream,
+    db: &Database,
+    shard_manager: &ShardManager,
+) -> Result<()> {
+    let mut request = [0u8; 1024];
+    let read = stream.read(&mut request).await?;
+    let request = String::from_utf8_lossy(&request[..read]);
+    let path = request.split_whitespace().nth(1).unwrap_or("/");
+
+    let (status_line, content_type, body) = if request.starts_with("GET ") && path == "/metrics" {
+        match render(db, shard_manager).await {
+            Ok(body) => ("200 OK", TextEncoder::new().format_type().to_string(), body),
+            Err(e) => ("500 Internal Server Error", "text/plain".to_string(), e.to_string().into_bytes()),
+        }
+    } else {
+        ("404 Not Found", "text/plain".to_string(), b"Not Found".to_vec())
+    };
+
+    let header = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status_line,
+        content_type,
+        body.len()
+    );
+    stream.write_all(header.as_bytes()).await?;
+    stream.write_all(&body).await?;
+    stream.shutdown().await?;
+
+    Ok(())
+}
+
+/// Serves `GET /metrics` in the Prometheus text format on `addr` until the
+/// process exits.
+pub async fn serve(addr: String, db: Arc<Database>, shard_manager: Arc<ShardManager>) -> Result<()> {
+    if METRICS.get().is_none() {
+        let _ = METRICS.set(Metrics::new()?);
+    }
+
+    let listener = TcpListener::bind(&addr).await?;
+    info!("Metrics endpoint listening on http://{}/metrics", addr);
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let db = Arc::clone(&db);
+        let shard_manager = Arc::clone(&shard_manager);
+
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, &db, &shard_manager).await {
+                error!("Metrics request failed: {}", e);
+            }
+        });
+    }
+}
@@ -30,6 +30,7 @@ pub struct Guild {
     #[allow(dead_code)]
     pub embed_footer: Option<String>,
     pub channel_name_template: Option<String>,
+    pub exclusive_claim: Option<bool>,
     #[allow(dead_code)]
     pub created_at: DateTime<Utc>,
     #[allow(dead_code)]
@@ -95,7 +96,6 @@ pub struct Ticket {
     pub channel_id: i64,
     pub ticket_number: i32,
     pub owner_id: i64,
-    #[allow(dead_code)]
     pub category_id: Option<Uuid>,
     pub claimed_by: Option<i64>,
     pub assigned_to: Option<i64>,
@@ -137,7 +137,6 @@ pub struct TicketMessage {
 
 #[derive(Debug, Clone, FromRow)]
 pub struct TicketPanel {
-    #[allow(dead_code)]
     pub id: Uuid,
     #[allow(dead_code)]
     pub guild_id: i64,
@@ -153,6 +152,32 @@ pub struct TicketPanel {
     pub created_at: DateTime<Utc>,
 }
 
+/// A posted panel message together with what is needed to re-render it.
+#[derive(Debug, Clone, FromRow)]
+pub struct PostedPanel {
+    pub id: Uuid,
+    pub guild_id: i64,
+    pub channel_id: i64,
+    pub message_id: i64,
+    pub selection_type: Option<String>,
+    pub uses_guild_embed: bool,
+    pub embed_image_url: Option<String>,
+    pub embed_thumbnail_url: Option<String>,
+    pub embed_footer_text: Option<String>,
+    pub embed_footer_icon_url: Option<String>,
+    pub tracks_categories: bool,
+}
+
+/// One category button (or dropdown option) on a posted panel.
+#[derive(Debug, Clone, FromRow)]
+pub struct PanelButton {
+    pub category_id: Uuid,
+    pub name: String,
+    pub emoji: Option<String>,
+    pub button_style: Option<String>,
+    pub button_color: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Attachment {
     pub filename: String,
@@ -195,6 +220,17 @@ pub struct Reminder {
     pub remind_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
     pub completed: bool,
+    pub recurrence: Option<String>,
+    pub repeat_until: Option<DateTime<Utc>>,
+    pub repeat_limit: Option<i32>,
+    pub occurrences: i32,
+    pub ticket_id: Option<Uuid>,
+    pub mention: Option<String>,
+    pub cancel_on_reply: bool,
+    pub attempts: i32,
+    pub delivery_status: Option<String>,
+    pub last_error: Option<String>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, FromRow)]
@@ -207,4 +243,166 @@ pub struct Tag {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub uses: i32,
+    pub embed: Option<sqlx::types::Json<TagEmbed>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TagEmbed {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<TagEmbedField>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub footer: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub buttons: Vec<TagButton>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagEmbedField {
+    pub name: String,
+    pub value: String,
+    #[serde(default)]
+    pub inline: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagButton {
+    pub label: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct TagTrigger {
+    pub id: Uuid,
+    #[allow(dead_code)]
+    pub guild_id: i64,
+    pub tag_name: String,
+    pub pattern: String,
+    pub is_regex: bool,
+    pub category_id: Option<Uuid>,
+    pub category_name: Option<String>,
+    pub cooldown_seconds: i32,
+    pub first_message_only: bool,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct TagRevision {
+    pub revision: i32,
+    pub content: String,
+    pub embed: Option<sqlx::types::Json<TagEmbed>>,
+    pub replaced_by: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct TagFolder {
+    pub id: Uuid,
+    pub name: String,
+    pub tag_count: i64,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct TagLibraryEntry {
+    pub name: String,
+    pub content: String,
+    pub embed: Option<sqlx::types::Json<TagEmbed>>,
+}
+
+/// A reminder with its repeat schedule or ticket follow-up settings, written in
+/// one INSERT so the claimer never sees it half set up.
+pub struct NewReminder {
+    pub user_id: i64,
+    pub channel_id: i64,
+    pub guild_id: Option<i64>,
+    pub message_id: Option<i64>,
+    pub reason: String,
+    pub remind_at: DateTime<Utc>,
+    pub recurrence: Option<String>,
+    pub repeat_until: Option<DateTime<Utc>>,
+    pub repeat_limit: Option<i32>,
+    pub ticket_id: Option<Uuid>,
+    pub mention: Option<String>,
+    pub cancel_on_reply: bool,
+}
+
+/// An auto-responder rule that has been validated but not stored yet.
+pub struct NewTagTrigger {
+    pub tag_id: Uuid,
+    pub tag_name: String,
+    pub pattern: String,
+    pub is_regex: bool,
+    pub category_id: Option<Uuid>,
+    pub category_name: Option<String>,
+    pub cooldown_seconds: i32,
+    pub first_message_only: bool,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct StaffAway {
+    #[allow(dead_code)]
+    pub id: Uuid,
+    #[allow(dead_code)]
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub reason: Option<String>,
+    pub away_until: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct EscalationTier {
+    #[allow(dead_code)]
+    pub id: Uuid,
+    #[allow(dead_code)]
+    pub guild_id: i64,
+    pub tier: i32,
+    pub role_id: i64,
+    pub delay_minutes: i32,
+    pub delivery: String,
+    pub channel_id: Option<i64>,
+    #[allow(dead_code)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl EscalationTier {
+    pub fn describe_delivery(&self) -> String {
+        match self.channel_id {
+            Some(channel_id) if self.delivery == "channel" => format!("<#{}>", channel_id),
+            _ => "DM".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct ActiveEscalation {
+    pub ticket_id: Uuid,
+    pub current_tier: i32,
+    pub seconds_since_escalated: i64,
+    pub seconds_since_last_ping: i64,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct GuildDigest {
+    pub guild_id: i64,
+    pub enabled: bool,
+    pub frequency: String,
+    pub weekday: i16,
+    pub hour: i16,
+    pub minute: i16,
+    pub timezone: String,
+    pub sla_hours: i32,
+    pub last_sent_at: Option<DateTime<Utc>>,
+}
+
+impl GuildDigest {
+    pub fn is_weekly(&self) -> bool {
+        self.frequency == "weekly"
+    }
 }
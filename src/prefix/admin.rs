@@ -2,6 +2,7 @@ use anyhow::Result;
 use serenity::all::{Context, Message, Permissions, CreateEmbed, CreateButton, CreateActionRow, ButtonStyle};
 use std::sync::Arc;
 use crate::database::Database;
+use super::has_admin_permissions;
 use crate::database::ticket as db_ticket;
 use crate::utils::{create_success_embed, create_error_embed, create_embed};
 
@@ -87,6 +88,268 @@ pub async fn supportrole(ctx: &Context, msg: &Message, db: &Arc<Database>, args:
     Ok(())
 }
 
+pub async fn escalation(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str]) -> Result<()> {
+    if !has_admin_permissions(ctx, msg).await? {
+        let embed = create_error_embed("Permission Denied", "You need Administrator permission to use this command");
+        msg.channel_id.send_message(&ctx.http,
+            serenity::all::CreateMessage::new().embed(embed)
+        ).await?;
+        return Ok(());
+    }
+
+    let guild_id = msg.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?.get() as i64;
+
+    let embed = match args.first().copied() {
+        Some("add") | Some("set") => {
+            if args.len() < 4 {
+                create_error_embed(
+                    "Invalid Usage",
+                    "Usage: `!escalation add <tier> <role> <delay> [channel]`\nExample: `!escalation add 2 @Managers 2h #escalations`",
+                )
+            } else {
+                let tier: i32 = args[1].parse()
+                    .map_err(|_| anyhow::anyhow!("Invalid tier number"))?;
+                let role_id = parse_role_id(args[2])?;
+                let duration = crate::utils::time::parse_duration(args[3])?;
+                let channel_id = match args.get(4) {
+                    Some(channel) => Some(
+                        channel.trim_start_matches("<#").trim_end_matches('>').parse::<i64>()
+                            .map_err(|_| anyhow::anyhow!("Invalid channel mention"))?
+                    ),
+                    None => None,
+                };
+
+                if !(1..=10).contains(&tier) {
+                    create_error_embed("Invalid Tier", "Tier must be between 1 and 10")
+                } else if duration.num_minutes() < 1 {
+                    create_error_embed("Invalid Delay", "The delay must be at least one minute")
+                } else if let Ok(delay_minutes) = i32::try_from(duration.num_minutes()) {
+                    db_ticket::upsert_escalation_tier(&db.pool, guild_id, tier, role_id, delay_minutes, channel_id).await?;
+
+                    create_success_embed(
+                        "Escalation Tier Saved",
+                        format!(
+                            "**Tier {}** will notify <@&{}> via {} once an escalated ticket has been unclaimed for **{}**",
+                            tier,
+                            role_id,
+                            channel_id.map(|c| format!("<#{}>", c)).unwrap_or_else(|| "DM".to_string()),
+                            crate::utils::format_minutes(delay_minutes as i64)
+                        ),
+                    )
+                } else {
+                    create_error_embed("Invalid Delay", "That delay is too long")
+                }
+            }
+        }
+        Some("remove") | Some("delete") => {
+            match args.get(1).and_then(|t| t.parse::<i32>().ok()) {
+                Some(tier) if db_ticket::remove_escalation_tier(&db.pool, guild_id, tier).await? => {
+                    create_success_embed("Escalation Tier Removed", format!("Tier {} has been removed", tier))
+                }
+                Some(tier) => create_error_embed("Not Found", format!("Tier {} is not configured", tier)),
+                None => create_error_embed("Invalid Usage", "Usage: `!escalation remove <tier>`"),
+            }
+        }
+        Some("list") | None => {
+            let tiers = db_ticket::get_escalation_tiers(&db.pool, guild_id).await?;
+            create_embed("Escalation Tiers", crate::utils::format_escalation_tiers(&tiers))
+        }
+        Some(_) => create_error_embed("Invalid Subcommand", "Valid subcommands: `add`, `remove`, `list`"),
+    };
+
+    msg.channel_id.send_message(&ctx.http,
+        serenity::all::CreateMessage::new().embed(embed)
+    ).await?;
+
+    Ok(())
+}
+
+struct AutorespondArgs {
+    pattern: String,
+    is_regex: bool,
+    first_message_only: bool,
+    cooldown: Option<String>,
+    category: Option<String>,
+}
+
+/// Splits `<pattern...> [--regex] [--first] [--cooldown <time>] [--category <name>]`.
+/// Category names run until the next flag so they can contain spaces.
+fn parse_autorespond_args(args: &[&str]) -> AutorespondArgs {
+    let mut parsed = AutorespondArgs { pattern: String::new(), is_regex: false, first_message_only: false, cooldown: None, category: None };
+    let mut pattern = Vec::new();
+    let mut i = 0;
+
+    while i < args.len() {
+        match args[i] {
+            "--regex" => parsed.is_regex = true,
+            "--first" => parsed.first_message_only = true,
+            "--cooldown" => {
+                parsed.cooldown = args.get(i + 1).map(|c| c.to_string());
+                i += 1;
+            }
+            "--category" => {
+                let end = args[i + 1..].iter().position(|a| a.starts_with("--")).map_or(args.len(), |p| i + 1 + p);
+                parsed.category = Some(args[i + 1..end].join(" ")).filter(|c| !c.is_empty());
+                i = end - 1;
+            }
+            word => pattern.push(word),
+        }
+        i += 1;
+    }
+
+    parsed.pattern = pattern.join(" ");
+    parsed
+}
+
+pub async fn autorespond(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str]) -> Result<()> {
+    if !has_admin_permissions(ctx, msg).await? {
+        let embed = create_error_embed("Permission Denied", "You need Administrator permission to use this command");
+        msg.channel_id.send_message(&ctx.http,
+            serenity::all::CreateMessage::new().embed(embed)
+        ).await?;
+        return Ok(());
+    }
+
+    let guild_id = msg.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?.get() as i64;
+
+    let embed = match args.first().copied() {
+        Some("add") if args.len() >= 3 => {
+            let parsed = parse_autorespond_args(&args[2..]);
+            let input = crate::utils::autoresponder::TriggerInput {
+                tag: args[1],
+                pattern: &parsed.pattern,
+                is_regex: parsed.is_regex,
+                cooldown: parsed.cooldown.as_deref(),
+                category: parsed.category.as_deref(),
+                first_message_only: parsed.first_message_only,
+            };
+
+            match crate::utils::autoresponder::parse_tag_trigger(db, guild_id, input).await {
+                Ok(trigger) => {
+                    crate::utils::autoresponder::save_tag_trigger(db, guild_id, &trigger, msg.author.id.get() as i64).await?;
+                    create_success_embed("Auto-Responder Added", crate::utils::autoresponder::describe_trigger(
+                        &trigger.tag_name,
+                        &trigger.pattern,
+                        trigger.is_regex,
+                        trigger.category_name.as_deref(),
+                        trigger.cooldown_seconds,
+                        trigger.first_message_only,
+                    ))
+                }
+                Err(e) => create_error_embed("Auto-Responder Not Added", e.to_string()),
+            }
+        }
+        Some("add") => create_error_embed(
+            "Invalid Usage",
+            "Usage: `!autorespond add <tag> <pattern> [--regex] [--first] [--cooldown <time>] [--category <name>]`\nExample: `!autorespond add refund refund, money back --cooldown 1h --category Billing`",
+        ),
+        Some("remove") | Some("delete") => {
+            let triggers = crate::database::tag::get_tag_triggers(&db.pool, guild_id).await?;
+            match args.get(1).and_then(|n| n.trim_start_matches('#').parse::<usize>().ok()) {
+                Some(number) => match number.checked_sub(1).and_then(|i| triggers.get(i)) {
+                    Some(trigger) => {
+                        crate::utils::autoresponder::remove_tag_trigger(db, guild_id, trigger.id).await?;
+                        create_success_embed("Auto-Responder Removed", format!("Rule #{} for tag `{}` has been removed", number, trigger.tag_name))
+                    }
+                    None => create_error_embed("Not Found", format!("There is no rule #{}", number)),
+                },
+                None => create_error_embed("Invalid Usage", "Usage: `!autorespond remove <number>`"),
+            }
+        }
+        Some("list") | None => {
+            let triggers = crate::database::tag::get_tag_triggers(&db.pool, guild_id).await?;
+            create_embed("Auto-Responder Rules", crate::utils::autoresponder::format_tag_triggers(&triggers))
+        }
+        Some(_) => create_error_embed("Invalid Subcommand", "Valid subcommands: `add`, `remove`, `list`"),
+    };
+
+    msg.channel_id.send_message(&ctx.http,
+        serenity::all::CreateMessage::new().embed(embed)
+    ).await?;
+
+    Ok(())
+}
+
+pub async fn digest(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str]) -> Result<()> {
+    if !has_admin_permissions(ctx, msg).await? {
+        let embed = create_error_embed("Permission Denied", "You need Administrator permission to use this command");
+        msg.channel_id.send_message(&ctx.http,
+            serenity::all::CreateMessage::new().embed(embed)
+        ).await?;
+        return Ok(());
+    }
+
+    let guild_id = msg.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?.get() as i64;
+
+    let embed = match args.first().copied() {
+        Some("set") => {
+            // !digest set <daily|weekly> [weekday] <HH:MM> [timezone] [sla hours]
+            let mut rest = &args[1.min(args.len())..];
+            let frequency = rest.first().copied();
+            rest = rest.get(1..).unwrap_or_default();
+
+            let weekday = rest.first().copied().filter(|a| crate::utils::digest::parse_weekday(a).is_some());
+            if weekday.is_some() {
+                rest = &rest[1..];
+            }
+            let time = rest.first().copied();
+            let timezone = rest.get(1).copied().filter(|a| a.parse::<i32>().is_err());
+            let sla_hours = rest.iter().skip(1).find_map(|a| a.parse::<i32>().ok());
+
+            match (frequency, time) {
+                (Some(frequency), Some(time)) => {
+                    match crate::utils::digest::parse_digest_schedule(guild_id, frequency, weekday, time, timezone, sla_hours) {
+                        Ok(digest) => {
+                            crate::database::digest::upsert_digest(&db.pool, &digest).await?;
+                            let guild = db_ticket::get_or_create_guild(&db.pool, guild_id).await?;
+
+                            let mut description = format!(
+                                "{}\nDigests are posted to the log channel.",
+                                crate::utils::digest::describe_schedule(&digest)
+                            );
+                            if guild.log_channel_id.is_none() {
+                                description.push_str("\n\n**Warning:** no log channel is configured, so nothing will be posted until one is set.");
+                            }
+
+                            create_success_embed("Digest Scheduled", description)
+                        }
+                        Err(e) => create_error_embed("Invalid Schedule", e.to_string()),
+                    }
+                }
+                _ => create_error_embed(
+                    "Invalid Usage",
+                    "Usage: `!digest set <daily|weekly> [weekday] <HH:MM> [timezone] [sla hours]`\nExample: `!digest set weekly monday 09:00 Europe/London 12`",
+                ),
+            }
+        }
+        Some("off") | Some("disable") => {
+            if crate::database::digest::set_digest_enabled(&db.pool, guild_id, false).await? {
+                create_success_embed("Digest Disabled", "Scheduled digests will no longer be posted")
+            } else {
+                create_error_embed("Not Configured", "No digest is configured for this server")
+            }
+        }
+        Some("preview") => {
+            let digest = match crate::database::digest::get_digest(&db.pool, guild_id).await? {
+                Some(digest) => digest,
+                None => crate::utils::digest::parse_digest_schedule(guild_id, "daily", None, "09:00", None, None)?,
+            };
+            crate::utils::digest::build_digest_embed(db, &digest).await?
+        }
+        Some("show") | None => {
+            let digest = crate::database::digest::get_digest(&db.pool, guild_id).await?;
+            create_embed("Digest Settings", crate::utils::digest::format_digest_settings(digest.as_ref()))
+        }
+        Some(_) => create_error_embed("Invalid Subcommand", "Valid subcommands: `set`, `off`, `preview`, `show`"),
+    };
+
+    msg.channel_id.send_message(&ctx.http,
+        serenity::all::CreateMessage::new().embed(embed)
+    ).await?;
+
+    Ok(())
+}
+
 pub async fn category(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str]) -> Result<()> {
     if !has_admin_permissions(ctx, msg).await? {
         let embed = create_error_embed("Permission Denied", "You need Administrator permission to use this command");
@@ -120,12 +383,15 @@ pub async fn category(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[
             let description = if args.len() > 2 { Some(args[2..].join(" ")) } else { None };
             let emoji = None;
 
-            db_ticket::create_ticket_category(&db.pool, guild_id, name.clone(), description, emoji).await?;
+            let category = db_ticket::create_ticket_category(&db.pool, guild_id, name.clone(), description, emoji).await?;
+            db_ticket::add_category_to_panels(&db.pool, guild_id, category.id).await?;
 
             let embed = create_success_embed("Category Created", format!("Category '{}' has been created", name));
             msg.channel_id.send_message(&ctx.http,
                 serenity::all::CreateMessage::new().embed(embed)
             ).await?;
+
+            crate::utils::panel::refresh_guild_panels(&ctx.http, db, guild_id).await;
         }
         "list" => {
             let categories = db_ticket::get_ticket_categories(&db.pool, guild_id).await?;
@@ -567,6 +833,8 @@ pub async fn note(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str
             .execute(&db.pool)
             .await?;
 
+            let _ = crate::database::stats::record_staff_activity(&db.pool, ticket.guild_id, msg.author.id.get() as i64, 0, 1).await;
+
             // Delete the admin's command message
             let _ = msg.delete(&ctx.http).await;
 
@@ -619,20 +887,68 @@ pub async fn note(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str
     Ok(())
 }
 
-pub async fn stats(ctx: &Context, msg: &Message, db: &Arc<Database>) -> Result<()> {
+pub async fn stats(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str]) -> Result<()> {
     let guild_id = msg.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?.get() as i64;
 
-    let total_tickets: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM tickets WHERE guild_id = $1")
-        .bind(guild_id)
-        .fetch_one(&db.pool)
-        .await?;
+    if args.first().is_some_and(|a| a.eq_ignore_ascii_case("chart")) {
+        let period = args.get(1).copied().unwrap_or("30d");
+        let (from, to) = if period.eq_ignore_ascii_case("custom") {
+            (args.get(2).copied(), args.get(3).copied())
+        } else {
+            (None, args.get(2).copied())
+        };
+
+        let message = match crate::utils::stats::parse_stats_period(period, from, to) {
+            Ok(period) => {
+                let typing = msg.channel_id.start_typing(&ctx.http);
+                let (embed, attachment) = crate::utils::stats::period_stats_chart(db, guild_id, &period).await?;
+                typing.stop();
+                serenity::all::CreateMessage::new().embed(embed).add_file(attachment)
+            }
+            Err(e) => serenity::all::CreateMessage::new().embed(create_error_embed(
+                "Invalid Period",
+                format!("{}\n\nUsage: `!stats chart [24h|7d|30d]` or `!stats chart <YYYY-MM-DD> [YYYY-MM-DD]`", e),
+            )),
+        };
+
+        msg.channel_id.send_message(&ctx.http, message).await?;
+        return Ok(());
+    }
+
+    if let Some(period) = args.first() {
+        let (from, to) = if period.eq_ignore_ascii_case("custom") {
+            (args.get(1).copied(), args.get(2).copied())
+        } else {
+            (None, args.get(1).copied())
+        };
+
+        let embed = match crate::utils::stats::parse_stats_period(period, from, to) {
+            Ok(period) => crate::utils::stats::period_stats_embed(db, guild_id, &period).await?,
+            Err(e) => create_error_embed(
+                "Invalid Period",
+                format!("{}\n\nUsage: `!stats [24h|7d|30d]` or `!stats <YYYY-MM-DD> [YYYY-MM-DD]`", e),
+            ),
+        };
+
+        msg.channel_id.send_message(&ctx.http,
+            serenity::all::CreateMessage::new().embed(embed)
+        ).await?;
+        return Ok(());
+    }
+
+    let total_tickets: (i64,) = sqlx::query_as(
+        "SELECT (SELECT COUNT(*) FROM tickets WHERE guild_id = $1) + (SELECT COUNT(*) FROM ticket_history WHERE guild_id = $1)"
+    )
+    .bind(guild_id)
+    .fetch_one(&db.pool)
+    .await?;
 
     let open_tickets: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM tickets WHERE guild_id = $1 AND status = 'open'")
         .bind(guild_id)
         .fetch_one(&db.pool)
         .await?;
 
-    let closed_tickets: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM tickets WHERE guild_id = $1 AND status = 'closed'")
+    let closed_tickets: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM ticket_history WHERE guild_id = $1")
         .bind(guild_id)
         .fetch_one(&db.pool)
         .await?;
@@ -657,19 +973,51 @@ pub async fn stats(ctx: &Context, msg: &Message, db: &Arc<Database>) -> Result<(
     Ok(())
 }
 
-async fn has_admin_permissions(ctx: &Context, msg: &Message) -> Result<bool> {
-    let guild_id = match msg.guild_id {
-        Some(id) => id,
-        None => return Ok(false),
+pub async fn staffstats(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str]) -> Result<()> {
+    let guild_id = msg.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?.get() as i64;
+
+    if !is_support_staff(ctx, msg, guild_id, db).await? {
+        let embed = create_error_embed("Permission Denied", "Only support staff can view staff statistics");
+        msg.channel_id.send_message(&ctx.http,
+            serenity::all::CreateMessage::new().embed(embed)
+        ).await?;
+        return Ok(());
+    }
+
+    let mut member = msg.mentions.first().map(|u| u.id.get());
+    let mut sort = crate::utils::stats::StaffSort::Closed;
+    let mut period_args = Vec::new();
+
+    for arg in args {
+        if arg.starts_with("<@") {
+            member = member.or_else(|| parse_user_id(arg).ok().map(|id| id as u64));
+        } else if let Some(s) = crate::utils::stats::StaffSort::parse(arg) {
+            sort = s;
+        } else {
+            period_args.push(*arg);
+        }
+    }
+
+    let period = period_args.first().copied().unwrap_or("30d");
+    let (from, to) = if period.eq_ignore_ascii_case("custom") {
+        (period_args.get(1).copied(), period_args.get(2).copied())
+    } else {
+        (None, period_args.get(1).copied())
     };
 
-    let member = guild_id.member(&ctx.http, msg.author.id).await?;
-    let permissions = {
-        let guild_obj = ctx.cache.guild(guild_id).ok_or_else(|| anyhow::anyhow!("Guild not found"))?;
-        guild_obj.member_permissions(&member)
+    let embed = match crate::utils::stats::parse_stats_period(period, from, to) {
+        Ok(period) => crate::utils::stats::staff_stats_embed(ctx, db, guild_id, &period, member, sort).await?,
+        Err(e) => create_error_embed(
+            "Invalid Period",
+            format!("{}\n\nUsage: `!staffstats [@member] [24h|7d|30d|<YYYY-MM-DD> [YYYY-MM-DD]] [sort]`", e),
+        ),
     };
 
-    Ok(permissions.contains(Permissions::ADMINISTRATOR))
+    msg.channel_id.send_message(&ctx.http,
+        serenity::all::CreateMessage::new().embed(embed)
+    ).await?;
+
+    Ok(())
 }
 
 async fn is_support_staff(ctx: &Context, msg: &Message, guild_id: i64, db: &Arc<Database>) -> Result<bool> {
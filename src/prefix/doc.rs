@@ -1,6 +1,7 @@
 use anyhow::Result;
 use serenity::all::{Context, CreateEmbed, CreateMessage, Message};
 use std::collections::HashMap;
+use crate::commands::doc::{followup_doc, CommandDoc};
 use crate::database::Database;
 
 pub async fn execute(ctx: &Context, msg: &Message, _db: &Database, args: &[&str]) -> Result<()> {
@@ -33,14 +34,6 @@ pub async fn execute(ctx: &Context, msg: &Message, _db: &Database, args: &[&str]
     Ok(())
 }
 
-struct CommandDoc {
-    name: String,
-    description: String,
-    usage: String,
-    examples: String,
-    permissions: String,
-}
-
 fn get_command_docs() -> HashMap<String, CommandDoc> {
     let mut docs = HashMap::new();
 
@@ -70,7 +63,7 @@ fn get_command_docs() -> HashMap<String, CommandDoc> {
         "panel".to_string(),
         CommandDoc {
             name: "panel".to_string(),
-            description: "Create a ticket panel in the current channel. Users can click buttons to open tickets for different categories.".to_string(),
+            description: "Create a ticket panel in the current channel. Users can click buttons to open tickets for different categories. Posted panels update in place when categories or panel settings change.".to_string(),
             usage: "`/panel` or `!panel`".to_string(),
             examples: "`/panel` - Creates a panel with all configured ticket categories".to_string(),
             permissions: "Administrator".to_string(),
@@ -114,7 +107,7 @@ fn get_command_docs() -> HashMap<String, CommandDoc> {
         "escalate".to_string(),
         CommandDoc {
             name: "escalate".to_string(),
-            description: "Escalate a ticket to notify all support staff. Only works if no support messages have been sent yet. Sends hourly DM reminders until claimed or closed.".to_string(),
+            description: "Escalate a ticket to notify all support staff. Only works if no support messages have been sent yet. Sends hourly DM reminders until claimed or closed. If escalation tiers are configured (`escalation add`), each tier notifies its role after its delay and the current tier is posted in the ticket channel.".to_string(),
             usage: "`/escalate` or `!escalate`".to_string(),
             examples: "`/escalate` - Start hourly reminders to all support staff\n`!escalate` - Prefix version".to_string(),
             permissions: "Ticket owner".to_string(),
@@ -132,6 +125,83 @@ fn get_command_docs() -> HashMap<String, CommandDoc> {
         },
     );
 
+    docs.insert(
+        "away".to_string(),
+        CommandDoc {
+            name: "away".to_string(),
+            description: "Mark yourself as away. Away staff are skipped by escalation reminders, handle notifications and ticket assignment. Use `back` to clear the status early, or `staff` to see who is available.".to_string(),
+            usage: "`/away [duration] [reason]` or `!away [duration] [reason]`\n`/back` or `!back`\n`/staff` or `!staff`".to_string(),
+            examples: "`/away duration: 3d reason: Vacation` - Away for three days\n`!away 8h Sleeping` - Away for eight hours\n`!away` - Away until `!back`\n`!staff` - List available and away staff".to_string(),
+            permissions: "Support role".to_string(),
+        },
+    );
+
+    docs.insert(
+        "merge".to_string(),
+        CommandDoc {
+            name: "merge".to_string(),
+            description: "Merge a duplicate ticket into another one. Messages and notes move to the target ticket, a summary is posted there, and this ticket is closed without its own transcript. The target's transcript will include both tickets.".to_string(),
+            usage: "`/merge <into>` or `!merge <#channel>`".to_string(),
+            examples: "`/merge into: #ticket-0042` - Merge the current ticket into #ticket-0042\n`!merge #ticket-0042` - Prefix version".to_string(),
+            permissions: "Support role".to_string(),
+        },
+    );
+
+    docs.insert(
+        "stats".to_string(),
+        CommandDoc {
+            name: "stats".to_string(),
+            description: "View ticket statistics. Without a period it shows all-time totals. With a period it shows opened and closed tickets per day, a split by category and priority, the busiest hours of the day and a comparison with the previous period of the same length. With `chart` it also attaches a PNG with a line chart of opened and closed tickets per day and a bar chart per category.".to_string(),
+            usage: "`/stats [period] [from] [to] [chart]` or `!stats [chart] [24h|7d|30d|<YYYY-MM-DD> [YYYY-MM-DD]]`".to_string(),
+            examples: "`/stats period: Last 7 days` - Weekly report\n`/stats period: Custom range from: 2025-01-01 to: 2025-01-31` - January report\n`!stats 30d` - Last 30 days\n`!stats 2025-01-01 2025-01-31` - Custom range\n`/stats period: Last 7 days chart: True` - Weekly report with a chart\n`!stats chart 30d` - Chart for the last 30 days".to_string(),
+            permissions: "Everyone".to_string(),
+        },
+    );
+
+    docs.insert(
+        "staffstats".to_string(),
+        CommandDoc {
+            name: "staffstats".to_string(),
+            description: "Show a staff member's tickets claimed, assigned and closed, messages sent in tickets, average first response time and notes written over a period. Without a member it shows a leaderboard of all support staff, sortable by any of those metrics. Defaults to the last 30 days.".to_string(),
+            usage: "`/staffstats [member] [period] [sort]` or `!staffstats [@member] [period] [sort]`".to_string(),
+            examples: "`/staffstats member: @Support1 period: Last 7 days` - Weekly report for @Support1\n`!staffstats messages 30d` - Leaderboard by messages sent\n`!staffstats @Support1 2025-01-01 2025-01-31` - Custom range".to_string(),
+            permissions: "Support role".to_string(),
+        },
+    );
+
+    docs.insert(
+        "export".to_string(),
+        CommandDoc {
+            name: "export".to_string(),
+            description: "Download the tickets created in a date range as a CSV or JSON file. Each row has the ticket number, owner, category, priority, claimer, assignee, created and closed timestamps, rating and message count.".to_string(),
+            usage: "`/export [format] [period] [from] [to]`".to_string(),
+            examples: "`/export format: CSV period: Last 30 days` - Last month as CSV\n`/export format: JSON period: Custom range from: 2025-01-01 to: 2025-03-31` - First quarter as JSON".to_string(),
+            permissions: "Administrator".to_string(),
+        },
+    );
+
+    docs.insert(
+        "autorespond".to_string(),
+        CommandDoc {
+            name: "autorespond".to_string(),
+            description: "Reply automatically with a tag when a ticket owner's message matches a rule. Patterns are comma-separated keywords matched as whole words, or a regex. Each rule has a per-ticket cooldown (10 minutes by default), can be limited to one ticket category, and can fire only on the owner's first message. At most one rule fires per message.".to_string(),
+            usage: "`/autorespond add|remove|list` or `!autorespond add <tag> <pattern> [--regex] [--first] [--cooldown <time>] [--category <name>]`, `!autorespond remove <number>`, `!autorespond list`".to_string(),
+            examples: "`!autorespond add refund refund, money back --cooldown 1h --category Billing` - Reply with the refund tag\n`/autorespond add tag: order pattern: order\\s*#?\\d+ regex: True first_message_only: True` - Regex rule for the first message only\n`!autorespond remove 2` - Remove rule #2".to_string(),
+            permissions: "Administrator".to_string(),
+        },
+    );
+
+    docs.insert(
+        "digest".to_string(),
+        CommandDoc {
+            name: "digest".to_string(),
+            description: "Post a daily or weekly digest to the log channel at a set time in the server's timezone. It covers tickets opened and closed compared with the previous period, the current backlog, the oldest unclaimed tickets, the top staff by tickets closed and the number of tickets that missed the first response SLA.".to_string(),
+            usage: "`/digest set|disable|show|preview` or `!digest set <daily|weekly> [weekday] <HH:MM> [timezone] [sla hours]`".to_string(),
+            examples: "`/digest set frequency: Weekly time: 09:00 weekday: Monday timezone: Europe/London` - Weekly Monday digest\n`!digest set daily 18:00 America/New_York 12` - Daily digest with a 12 hour SLA\n`!digest preview` - Preview with current data\n`!digest off` - Stop posting".to_string(),
+            permissions: "Administrator".to_string(),
+        },
+    );
+
     docs.insert(
         "priority".to_string(),
         CommandDoc {
@@ -180,12 +250,38 @@ fn get_command_docs() -> HashMap<String, CommandDoc> {
         "reminder".to_string(),
         CommandDoc {
             name: "reminder".to_string(),
-            description: "Set a timed reminder for yourself. Bot will ping you and send a DM when the time is up.".to_string(),
-            usage: "`!reminder <time> <reason>` or `!remind <time> <reason>`".to_string(),
+            description: "Set a timed reminder for yourself. Bot will ping you and send a DM when the time is up. The time can be a duration (`1h30m`), a time (`14:00`, `9am`) or a date (`tomorrow`, `friday`, `2026-11-01`) with an optional time. Reminders can also repeat on a schedule, optionally ending on a date (`--until`) or after a number of reminders (`--count`). Times are read in your timezone (see `timezone`), UTC if you haven't set one. The reminder DM has buttons to snooze it for 10 minutes or an hour. If the DM can't be sent it is retried a few times with increasing delays, and `reminder list` shows how your recent reminders were delivered.".to_string(),
+            usage: "`!reminder <when> <reason>` or `!remind <when> <reason>`\n\
+                `!reminder every <interval|day|weekday> [at <time>] [--until YYYY-MM-DD] [--count N] <reason>`\n\
+                `!reminder list`, `!reminder cancel <id>`, `!reminder snooze <id> [duration]`\n\
+                `!reminder edit <id> time <when>` or `!reminder edit <id> reason <text>`\n\
+                `/reminder set|list|cancel|edit|snooze`".to_string(),
             examples: "`!reminder 30m Check ticket status` - Remind in 30 minutes\n\
-                `!remind 2h Close ticket` - Remind in 2 hours\n\
-                `!remindme 1d Follow up with user` - Remind in 1 day\n\n\
-                Time formats: s (seconds), m (minutes), h (hours), d (days), w (weeks)".to_string(),
+                `!remind 1h30m Close ticket` - Remind in an hour and a half\n\
+                `!remindme tomorrow 9am Follow up with user` - Tomorrow at 09:00\n\
+                `!reminder 2026-11-01 14:00 Renew the license` - On a date and time\n\
+                `!reminder every day at 09:00 Check the queue` - Every day at 09:00\n\
+                `!reminder every monday 9am --count 4 Weekly report` - The next four Mondays\n\
+                `!reminder every 2h --until 2026-12-31 Review escalations` - Every 2 hours until the end of the year\n\
+                `!reminder snooze 1a2b3c4d 1h` - Push a reminder back an hour\n\
+                `/reminder edit reminder: Renew the license when: friday 10am` - Move a reminder\n\n\
+                Durations combine s (seconds), m (minutes), h (hours), d (days) and w (weeks)".to_string(),
+            permissions: "Everyone".to_string(),
+        },
+    );
+
+    docs.insert("followup".to_string(), followup_doc());
+
+    docs.insert(
+        "timezone".to_string(),
+        CommandDoc {
+            name: "timezone".to_string(),
+            description: "Set the timezone used to read dates and times in your reminders, such as `tomorrow 9am` or `every day at 09:00`. Without one, times are UTC. The setting is yours and follows you across servers.".to_string(),
+            usage: "`/timezone [zone]` or `!timezone [zone|reset]`".to_string(),
+            examples: "`/timezone zone: Europe/London` - Set your timezone\n\
+                `!timezone America/New_York` - Prefix version\n\
+                `!tz` - Show your timezone and local time\n\
+                `!timezone reset` - Go back to UTC".to_string(),
             permissions: "Everyone".to_string(),
         },
     );
@@ -205,17 +301,28 @@ fn get_command_docs() -> HashMap<String, CommandDoc> {
         "tag".to_string(),
         CommandDoc {
             name: "tag".to_string(),
-            description: "Tag system for creating and managing server-wide text snippets. Tags are case-insensitive and can be used to store frequently used messages, rules, FAQs, and more.".to_string(),
-            usage: "`!tag <name>` or `/tag use <name>` - Display a tag\n\
+            description: "Tag system for creating and managing server-wide text snippets. Tags are case-insensitive and can be used to store frequently used messages, rules, FAQs, and more. A tag can have several aliases; names and aliases are unique within the server. Tag content can contain placeholders: `{user}`, `{user.mention}`, `{user.id}`, `{channel}` and `{guild}` always, `{ticket.number}`, `{ticket.owner}`, `{claimer}` and `{category}` inside a ticket, and `{1}`, `{2}`, ... for values given after the tag name. Unknown placeholders are left as written. Embed tags hold a full embed (title, description, color, fields, image, footer) and up to 5 link buttons; build them with `/tag embed`. Slash commands autocomplete tag names, and unknown names get \"did you mean\" suggestions.".to_string(),
+            usage: "`!tag <name> [values...]` or `/tag use <name> [args]` - Display a tag\n\
                 `!tag create <name> <content>` - Create a new tag\n\
                 `!tag edit <name> <content>` - Edit your tag\n\
                 `!tag delete <name>` - Delete your tag\n\
-                `!tag list` - List all tags\n\
+                `!tag list` - Browse tags with a folder picker, page buttons and previews\n\
                 `!tag search <query>` - Search tags\n\
                 `!tag info <name>` - View tag details\n\
                 `!tag popular` - Show most used tags\n\
-                `!tag raw <name>` - View raw content\n\
-                `!tag rename <old> <new>` - Rename your tag".to_string(),
+                `!tag raw <name>` - View raw content, or the JSON of an embed tag\n\
+                `/tag embed <name>` - Create or edit an embed tag with the builder\n\
+                `!tag rename <old> <new>` - Rename your tag\n\
+                `!tag alias add <name> <alias>` or `/tag alias add` - Add an alias to your tag\n\
+                `!tag alias remove <alias>` or `/tag alias remove` - Remove an alias\n\
+                `!tag history <name>` - Show the previous versions of a tag\n\
+                `!tag rollback <name> <revision>` - Restore a previous version\n\
+                `!tag permissions [add|remove <create|edit|delete> <@role>]` - Show or change the tag roles\n\
+                `!tag export` or `/tag export` - Download every tag as a JSON file\n\
+                `!tag import [skip|overwrite|rename]` (with the file attached) or `/tag import` - Import tags from an export\n\
+                `!tag library [browse|subscribe|unsubscribe]` or `/tag library` - Use the shared tag library\n\
+                `!tag folder [list|create <name>|delete <name>]` - Manage tag folders\n\
+                `!tag folder move <tag> [folder|none]` or `/tag folder move` - File a tag into a folder".to_string(),
             examples: "`!tag create rules Server rules: 1. Be respectful 2. No spam` - Create a rules tag\n\
                 `/tag create name: faq content: Check our FAQ at...` - Create FAQ tag (slash)\n\
                 `!tag rules` - Display the rules tag\n\
@@ -228,8 +335,18 @@ fn get_command_docs() -> HashMap<String, CommandDoc> {
                 `!tag popular` - Show top 10 most used tags\n\
                 `!tag raw rules` - View tag content in code block\n\
                 `!tag rename rules server-rules` - Rename a tag\n\
+                `!tag alias add rules guidelines` - `!tag guidelines` now shows the rules tag\n\
+                `!tag create refund Hi {ticket.owner}, refund #{1} is on its way.` - Create a tag with placeholders\n\
+                `!tag refund 42` - Display it with `{1}` filled in as 42\n\
+                `/tag embed name: welcome` - Open the embed builder for `welcome`\n\
+                `!tag history rules` - See who changed the rules tag and what it said before\n\
+                `!tag rollback rules 2` - Put back revision #2 of the rules tag\n\
+                `!tag permissions add edit @Support` - Let the Support role edit any tag\n\
+                `/tag import file: tags-123.json conflicts: rename` - Import tags, renaming any that clash to `name-2`\n\
+                `!tag library subscribe` - Add the shared library tags to this server\n\
+                `!tag folder create billing` then `!tag folder move refund billing` - File the refund tag under billing\n\
                 `!tag delete rules` - Delete your tag".to_string(),
-            permissions: "Everyone can use tags and create their own tags. Only tag creators can edit, delete, or rename their own tags.".to_string(),
+            permissions: "Everyone can use tags. Creators can always edit, delete, rename, alias and roll back their own tags, and administrators can manage any tag. Administrators can give roles permission to create, edit or delete tags; once a role is set for creating tags, only members with it can create them. Changing tag permissions, importing tags and subscribing to the library require Administrator. Library tags follow library updates until your server edits or renames them. Creating and deleting folders requires Administrator or a tag edit role.".to_string(),
         },
     );
 
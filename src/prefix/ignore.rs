@@ -1,32 +1,11 @@
 use anyhow::Result;
-use serenity::all::{Context, Message, Permissions};
+use serenity::all::{Context, Message};
 use std::sync::Arc;
 use crate::database::Database;
+use super::has_admin_permissions;
 use crate::database::ignore as db_ignore;
 use crate::utils::{create_success_embed, create_error_embed, create_embed};
 
-async fn has_admin_permissions(ctx: &Context, msg: &Message) -> Result<bool> {
-    if let Some(guild_id) = msg.guild_id {
-        if let Some(member) = msg.member.as_ref() {
-            if let Some(permissions) = member.permissions {
-                if permissions.contains(Permissions::ADMINISTRATOR) {
-                    return Ok(true);
-                }
-            }
-        }
-
-        if let Ok(guild) = ctx.http.get_guild(guild_id).await {
-            if let Ok(member) = guild.member(&ctx.http, msg.author.id).await {
-                if let Ok(guild_obj) = ctx.cache.guild(guild_id).ok_or_else(|| anyhow::anyhow!("Guild not in cache")) {
-                    let permissions = guild_obj.member_permissions(&member);
-                    return Ok(permissions.contains(Permissions::ADMINISTRATOR));
-                }
-            }
-        }
-    }
-    Ok(false)
-}
-
 fn parse_channel_id(s: &str) -> Result<i64> {
     let cleaned = s.trim_start_matches("<#").trim_end_matches('>');
     Ok(cleaned.parse()?)
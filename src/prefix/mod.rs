@@ -12,12 +12,29 @@ pub mod reminder;
 pub mod tag;
 pub mod channelname;
 pub mod ignore;
+pub mod staff;
 
 use anyhow::Result;
-use serenity::all::{Context, Message};
+use serenity::all::{Context, Message, Permissions};
 use std::sync::Arc;
 use crate::database::Database;
 
+/// Whether the author of a prefix command has Administrator in the guild.
+pub async fn has_admin_permissions(ctx: &Context, msg: &Message) -> Result<bool> {
+    let guild_id = match msg.guild_id {
+        Some(id) => id,
+        None => return Ok(false),
+    };
+
+    let member = guild_id.member(&ctx.http, msg.author.id).await?;
+    let permissions = {
+        let guild_obj = ctx.cache.guild(guild_id).ok_or_else(|| anyhow::anyhow!("Guild not found"))?;
+        guild_obj.member_permissions(&member)
+    };
+
+    Ok(permissions.contains(Permissions::ADMINISTRATOR))
+}
+
 pub async fn handle_prefix_command(
     ctx: &Context,
     msg: &Message,
@@ -35,7 +52,8 @@ pub async fn handle_prefix_command(
     let command = parts[0].to_lowercase();
     let args = &parts[1..];
 
-    match command.as_str() {
+    let started = std::time::Instant::now();
+    let result = match command.as_str() {
         "help" => help::execute(ctx, msg, db).await,
         "doc" => doc::execute(ctx, msg, db, args).await,
         "tag" => tag::handle(ctx, msg, db, args).await,
@@ -43,23 +61,33 @@ pub async fn handle_prefix_command(
         "reminder" | "remind" | "remindme" => reminder::reminder(ctx, msg, db, args).await,
         "ignore" => ignore::ignore(ctx, msg, db, args).await,
         "ping" => utility::ping(ctx, msg, db).await,
+        "timezone" | "tz" => utility::timezone(ctx, msg, db, args).await,
         "roast" => roast::execute(ctx, msg, db, owner_id).await,
         "toast" => toast::execute(ctx, msg, db, args).await,
         "setup" | "panel" => setup::execute(ctx, msg, db, args).await,
         "settings" | "config" => settings::settings(ctx, msg, db, args).await,
         "prefix" => setup::set_prefix(ctx, msg, db, args).await,
         "supportrole" | "sr" => admin::supportrole(ctx, msg, db, args).await,
+        "escalation" | "tiers" => admin::escalation(ctx, msg, db, args).await,
+        "digest" => admin::digest(ctx, msg, db, args).await,
+        "autorespond" | "ar" => admin::autorespond(ctx, msg, db, args).await,
         "category" | "cat" => admin::category(ctx, msg, db, args).await,
         "priority" => admin::priority(ctx, msg, db, args).await,
         "blacklist" | "bl" => admin::blacklist(ctx, msg, db, args).await,
         "note" => admin::note(ctx, msg, db, args).await,
-        "stats" => admin::stats(ctx, msg, db).await,
+        "stats" => admin::stats(ctx, msg, db, args).await,
+        "staffstats" | "ss" => admin::staffstats(ctx, msg, db, args).await,
         "close" => ticket::close(ctx, msg, db).await,
         "claim" => ticket::claim(ctx, msg, db).await,
         "assign" => ticket::assign(ctx, msg, db, args).await,
         "transcript" | "trans" => ticket::transcript(ctx, msg, db).await,
         "escalate" => ticket::escalate(ctx, msg, db).await,
         "handle" => ticket::handle(ctx, msg, db).await,
+        "merge" => ticket::merge(ctx, msg, db, args).await,
+        "followup" | "fu" => ticket::followup(ctx, msg, db, args).await,
+        "away" | "afk" => staff::away(ctx, msg, db, args).await,
+        "back" => staff::back(ctx, msg, db).await,
+        "staff" => staff::staff(ctx, msg, db).await,
         "profile" => owner::profile(ctx, msg, db).await,
         "botstats" => owner::stats(ctx, msg, db, owner_id).await,
         "addprem" => owner::add_premium(ctx, msg, db, args, owner_id).await,
@@ -78,8 +106,12 @@ pub async fn handle_prefix_command(
         "ticketstats" => owner::ticket_stats(ctx, msg, db, owner_id).await,
         "sysinfo" => owner::system_info(ctx, msg, db, owner_id).await,
         "backupdb" => owner::backup_db(ctx, msg, db, owner_id).await,
-        _ => Ok(()),
-    }
+        "taglib" => owner::tag_library(ctx, msg, db, args, owner_id).await,
+        _ => return Ok(()),
+    };
+
+    crate::metrics::record_command("prefix", &command, started.elapsed(), result.is_ok());
+    result
 }
 
 pub async fn get_prefix(pool: &sqlx::PgPool, guild_id: u64) -> String {
@@ -1,5 +1,8 @@
 use anyhow::Result;
-use serenity::all::{Context, Message};
+use serenity::all::{
+    ButtonStyle, ComponentInteraction, Context, CreateActionRow, CreateButton,
+    CreateInteractionResponse, CreateInteractionResponseMessage, Message,
+};
 use std::sync::Arc;
 use sysinfo::System;
 use sqlx::Row;
@@ -560,50 +563,67 @@ pub async fn ticket_stats(ctx: &Context, msg: &Message, db: &Arc<Database>, owne
         return Ok(());
     }
 
-    let total_tickets: (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM tickets"
-    )
-    .fetch_one(&db.pool)
-    .await?;
+    let pages = crate::utils::stats::owner_stats_pages(ctx, db).await?;
+    let page_count = pages.len();
+    let first = pages.into_iter().next().ok_or_else(|| anyhow::anyhow!("No statistics pages"))?;
 
-    let open_tickets: (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM tickets WHERE status = 'open'"
-    )
-    .fetch_one(&db.pool)
-    .await?;
+    msg.channel_id.send_message(&ctx.http,
+        serenity::all::CreateMessage::new()
+            .embed(first)
+            .components(ticket_stats_buttons(0, page_count))
+    ).await?;
+    Ok(())
+}
 
-    let closed_tickets: (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM tickets WHERE status = 'closed'"
-    )
-    .fetch_one(&db.pool)
-    .await?;
+fn ticket_stats_buttons(page: usize, page_count: usize) -> Vec<CreateActionRow> {
+    if page_count <= 1 {
+        return vec![];
+    }
+
+    vec![CreateActionRow::Buttons(vec![
+        CreateButton::new(format!("ticketstats_page:{}", page.saturating_sub(1)))
+            .label("Previous")
+            .style(ButtonStyle::Secondary)
+            .disabled(page == 0),
+        CreateButton::new(format!("ticketstats_page:{}", (page + 1).min(page_count - 1)))
+            .label("Next")
+            .style(ButtonStyle::Secondary)
+            .disabled(page + 1 >= page_count),
+    ])]
+}
 
-    let avg_resolution_time: (Option<f64>,) = sqlx::query_as(
-        "SELECT AVG(EXTRACT(EPOCH FROM (closed_at - created_at))/3600.0)
-         FROM tickets
-         WHERE status = 'closed' AND closed_at IS NOT NULL"
-    )
-    .fetch_one(&db.pool)
-    .await?;
+/// Handles the Previous/Next buttons on `ticketstats`. Pages are rebuilt on each
+/// click so the numbers stay current.
+pub async fn handle_ticket_stats_page(
+    ctx: &Context,
+    interaction: &ComponentInteraction,
+    db: &Database,
+    owner_id: u64,
+) -> Result<()> {
+    if interaction.user.id.get() != owner_id {
+        interaction.create_response(&ctx.http, CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .embed(create_error_embed("Permission Denied", "This command is owner-only"))
+                .ephemeral(true)
+        )).await?;
+        return Ok(());
+    }
 
-    let total_messages: (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM ticket_messages"
-    )
-    .fetch_one(&db.pool)
-    .await?;
+    let requested: usize = interaction.data.custom_id
+        .strip_prefix("ticketstats_page:")
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(0);
 
-    let embed = create_success_embed("Global Ticket Statistics", "")
-        .field("Total Tickets", format!("{}", total_tickets.0), true)
-        .field("Open Tickets", format!("{}", open_tickets.0), true)
-        .field("Closed Tickets", format!("{}", closed_tickets.0), true)
-        .field("Total Messages", format!("{}", total_messages.0), true)
-        .field(
-            "Avg Resolution Time",
-            format!("{:.1} hours", avg_resolution_time.0.unwrap_or(0.0)),
-            true
-        );
+    let pages = crate::utils::stats::owner_stats_pages(ctx, db).await?;
+    let page_count = pages.len();
+    let page = requested.min(page_count.saturating_sub(1));
+    let embed = pages.into_iter().nth(page).ok_or_else(|| anyhow::anyhow!("No statistics pages"))?;
 
-    msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+    interaction.create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(
+        CreateInteractionResponseMessage::new()
+            .embed(embed)
+            .components(ticket_stats_buttons(page, page_count))
+    )).await?;
     Ok(())
 }
 
@@ -634,6 +654,50 @@ pub async fn system_info(ctx: &Context, msg: &Message, _db: &Arc<Database>, owne
     Ok(())
 }
 
+/// `taglib` lists the shared tag library, `taglib publish <tag>` copies a tag from
+/// this server into it (or updates it), and `taglib unpublish <name>` removes it.
+pub async fn tag_library(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str], owner_id: u64) -> Result<()> {
+    if msg.author.id.get() != owner_id {
+        let embed = create_error_embed("Permission Denied", "This command is owner-only");
+        msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let embed = match (args.first().copied(), args.get(1)) {
+        (None, _) | (Some("list"), _) => {
+            let entries = crate::database::tag::list_library_tags(&db.pool).await?;
+            create_success_embed(format!("Tag Library ({})", entries.len()), crate::utils::tag::format_library_tags(&entries))
+        }
+        (Some("publish"), Some(&name)) => {
+            let guild_id = msg.guild_id.map(|g| g.get() as i64).unwrap_or(0);
+            match crate::database::tag::get_tag(&db.pool, guild_id, name).await? {
+                Some(tag) => {
+                    let created = crate::database::tag::publish_library_tag(
+                        &db.pool, &tag.name, &tag.content, tag.embed.as_deref(), msg.author.id.get() as i64,
+                    ).await?;
+                    if created {
+                        create_success_embed("Tag Published", format!("`{}` was added to the library and copied to subscribed servers", tag.name))
+                    } else {
+                        create_success_embed("Tag Updated", format!("`{}` was updated in the library and in subscribed servers that haven't edited it", tag.name))
+                    }
+                }
+                None => create_error_embed("Tag Not Found", format!("This server has no tag `{}` to publish", name)),
+            }
+        }
+        (Some("unpublish"), Some(&name)) => {
+            if crate::database::tag::unpublish_library_tag(&db.pool, name).await? {
+                create_success_embed("Tag Unpublished", format!("`{}` was removed from the library and from subscribed servers that haven't edited it", name))
+            } else {
+                create_error_embed("Not Found", format!("The library has no tag `{}`", name))
+            }
+        }
+        _ => create_error_embed("Invalid Usage", "Usage: `!taglib [list|publish <tag>|unpublish <name>]`"),
+    };
+
+    msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+    Ok(())
+}
+
 pub async fn backup_db(ctx: &Context, msg: &Message, db: &Arc<Database>, owner_id: u64) -> Result<()> {
     if msg.author.id.get() != owner_id {
         let embed = create_error_embed("Permission Denied", "This command is owner-only");
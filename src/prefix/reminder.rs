@@ -2,28 +2,44 @@ use anyhow::Result;
 use serenity::all::{Context, CreateEmbed, CreateMessage, Message};
 use std::sync::Arc;
 use crate::database::Database;
-use chrono::{Duration, Utc};
+use crate::models::NewReminder;
+use chrono::Utc;
 
 pub async fn reminder(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str]) -> Result<()> {
+    match args.first().map(|a| a.to_lowercase()).as_deref() {
+        Some("list") => return list(ctx, msg, db).await,
+        Some("cancel") | Some("delete") => return cancel(ctx, msg, db, &args[1..]).await,
+        Some("edit") => return edit(ctx, msg, db, &args[1..]).await,
+        Some("snooze") => return snooze(ctx, msg, db, &args[1..]).await,
+        _ => {}
+    }
+
     if args.len() < 2 {
         let embed = crate::utils::create_error_embed(
             "Invalid Usage",
-            "Usage: `!reminder <time> <reason>`\n\n\
+            "Usage: `!reminder <when> <reason>`\n\n\
             Examples:\n\
             `!reminder 30m Check ticket status`\n\
-            `!reminder 2h Close ticket`\n\
-            `!reminder 1d Follow up with user`\n\n\
-            Time formats: s (seconds), m (minutes), h (hours), d (days)"
+            `!reminder 1h30m Close ticket`\n\
+            `!reminder tomorrow 9am Follow up with user`\n\
+            `!reminder 2026-11-01 14:00 Renew the license`\n\
+            `!reminder every day 09:00 Check the queue`\n\
+            `!reminder every monday 9am --count 4 Weekly report`\n\
+            `!reminder every 2h --until 2026-12-31 Review escalations`\n\n\
+            Manage them with `!reminder list`, `cancel <id>`, `snooze <id> <duration>` and `edit <id> time|reason <value>`.\n\
+            Durations combine s, m, h, d and w (e.g. `1h30m`). Dates and times use your timezone, set with `!timezone`."
         );
         msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await?;
         return Ok(());
     }
 
-    let time_str = args[0];
-    let reason = args[1..].join(" ");
+    if args[0].eq_ignore_ascii_case("every") {
+        return recurring(ctx, msg, db, &args[1..]).await;
+    }
 
-    let duration = match parse_duration(time_str) {
-        Ok(d) => d,
+    let tz = crate::utils::time::user_timezone(db, msg.author.id.get() as i64).await?;
+    let (remind_at, used) = match crate::utils::time::parse_when(args, Utc::now(), tz) {
+        Ok(when) => when,
         Err(e) => {
             let embed = crate::utils::create_error_embed("Invalid Time Format", &format!("{}", e));
             msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await?;
@@ -31,7 +47,12 @@ pub async fn reminder(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[
         }
     };
 
-    let remind_at = Utc::now() + duration;
+    let reason = args[used..].join(" ");
+    if reason.is_empty() {
+        let embed = crate::utils::create_error_embed("Invalid Usage", "Add what the reminder is for after the time");
+        msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await?;
+        return Ok(());
+    }
 
     let reminder = crate::database::ticket::create_reminder(
         &db.pool,
@@ -61,27 +82,156 @@ pub async fn reminder(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[
     Ok(())
 }
 
-fn parse_duration(time_str: &str) -> Result<Duration> {
-    let time_str = time_str.trim();
+async fn recurring(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str]) -> Result<()> {
+    let tz = crate::utils::time::user_timezone(db, msg.author.id.get() as i64).await?;
+    let parsed = match crate::utils::reminder::parse_recurring(args, tz) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            let embed = crate::utils::create_error_embed("Invalid Schedule", e.to_string());
+            msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await?;
+            return Ok(());
+        }
+    };
 
-    if time_str.is_empty() {
-        return Err(anyhow::anyhow!("Time string is empty"));
-    }
+    let remind_at = parsed.recurrence.first(Utc::now());
 
-    let unit = time_str.chars().last().ok_or_else(|| anyhow::anyhow!("Invalid time format"))?;
-    let value_str = &time_str[..time_str.len() - 1];
-    let value: i64 = value_str.parse().map_err(|_| anyhow::anyhow!("Invalid number: {}", value_str))?;
+    let reminder = crate::database::ticket::create_scheduled_reminder(&db.pool, &NewReminder {
+        user_id: msg.author.id.get() as i64,
+        channel_id: msg.channel_id.get() as i64,
+        guild_id: msg.guild_id.map(|g| g.get() as i64),
+        message_id: Some(msg.id.get() as i64),
+        reason: parsed.reason.clone(),
+        remind_at,
+        recurrence: Some(parsed.recurrence.to_spec()),
+        repeat_until: parsed.repeat_until,
+        repeat_limit: parsed.repeat_limit,
+        ticket_id: None,
+        mention: None,
+        cancel_on_reply: false,
+    })
+    .await?;
 
-    if value <= 0 {
-        return Err(anyhow::anyhow!("Time value must be positive"));
+    let embed = CreateEmbed::new()
+        .title("Recurring Reminder Set")
+        .description(format!(
+            "**Reason:** {}\n\
+            **First reminder:** <t:{}:F> (<t:{}:R>)\n\
+            **{}**",
+            parsed.reason,
+            remind_at.timestamp(),
+            remind_at.timestamp(),
+            crate::utils::reminder::describe_repeat(parsed.recurrence, parsed.repeat_until, parsed.repeat_limit)
+        ))
+        .color(0x5865F2)
+        .footer(serenity::all::CreateEmbedFooter::new(format!("Reminder ID: {}", reminder.id)));
+
+    msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await?;
+
+    Ok(())
+}
+
+async fn list(ctx: &Context, msg: &Message, db: &Arc<Database>) -> Result<()> {
+    let reminders = crate::database::ticket::get_user_reminders(&db.pool, msg.author.id.get() as i64).await?;
+    let recent = crate::database::ticket::get_recent_delivered_reminders(&db.pool, msg.author.id.get() as i64, 5).await?;
+
+    let mut embed = CreateEmbed::new()
+        .title("Your Reminders")
+        .description(crate::utils::reminder::format_reminder_list(&reminders))
+        .color(0x5865F2)
+        .footer(serenity::all::CreateEmbedFooter::new("Use the ID with !reminder cancel, snooze or edit"));
+    if let Some(recent) = crate::utils::reminder::format_recent_deliveries(&recent) {
+        embed = embed.field("Recently Delivered", recent, false);
     }
 
-    match unit {
-        's' => Ok(Duration::seconds(value)),
-        'm' => Ok(Duration::minutes(value)),
-        'h' => Ok(Duration::hours(value)),
-        'd' => Ok(Duration::days(value)),
-        'w' => Ok(Duration::weeks(value)),
-        _ => Err(anyhow::anyhow!("Invalid time unit '{}'. Use s (seconds), m (minutes), h (hours), d (days), or w (weeks)", unit)),
+    msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await?;
+    Ok(())
+}
+
+async fn cancel(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str]) -> Result<()> {
+    let embed = match find_own_reminder(db, msg, args.first().copied()).await? {
+        Ok(reminder) => {
+            crate::database::ticket::delete_reminder(&db.pool, reminder.id).await?;
+            crate::utils::create_success_embed("Reminder Cancelled", format!("Cancelled `{}`: {}", crate::utils::reminder::short_id(reminder.id), reminder.reason))
+        }
+        Err(e) => crate::utils::create_error_embed("Reminder Not Found", e.to_string()),
+    };
+
+    msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await?;
+    Ok(())
+}
+
+async fn snooze(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str]) -> Result<()> {
+    let embed = match find_own_reminder(db, msg, args.first().copied()).await? {
+        Ok(reminder) => match crate::utils::time::parse_duration(args.get(1).copied().unwrap_or("10m"))
+            .and_then(|by| crate::utils::reminder::snoozed_until(&reminder, by, Utc::now()))
+        {
+            Ok(until) => {
+                crate::database::ticket::update_reminder(&db.pool, reminder.id, None, Some(until)).await?;
+                crate::utils::create_success_embed(
+                    "Reminder Snoozed",
+                    format!("`{}` will now go off <t:{}:F> (<t:{}:R>)", crate::utils::reminder::short_id(reminder.id), until.timestamp(), until.timestamp()),
+                )
+            }
+            Err(e) => crate::utils::create_error_embed("Invalid Time Format", e.to_string()),
+        },
+        Err(e) => crate::utils::create_error_embed("Reminder Not Found", e.to_string()),
+    };
+
+    msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await?;
+    Ok(())
+}
+
+async fn edit(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str]) -> Result<()> {
+    if args.len() < 3 {
+        let embed = crate::utils::create_error_embed(
+            "Invalid Usage",
+            "Usage: `!reminder edit <id> time <when>` or `!reminder edit <id> reason <text>`\n\n\
+            Examples:\n\
+            `!reminder edit 1a2b3c4d time tomorrow 9am`\n\
+            `!reminder edit 1a2b3c4d reason Check the refund ticket`"
+        );
+        msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await?;
+        return Ok(());
     }
+
+    let reminder = match find_own_reminder(db, msg, Some(args[0])).await? {
+        Ok(reminder) => reminder,
+        Err(e) => {
+            let embed = crate::utils::create_error_embed("Reminder Not Found", e.to_string());
+            msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await?;
+            return Ok(());
+        }
+    };
+
+    let embed = match args[1].to_lowercase().as_str() {
+        "time" | "when" => {
+            let tz = crate::utils::time::user_timezone(db, msg.author.id.get() as i64).await?;
+            match crate::utils::time::parse_when(&args[2..], Utc::now(), tz) {
+                Ok((at, _)) => {
+                    crate::database::ticket::update_reminder(&db.pool, reminder.id, None, Some(at)).await?;
+                    crate::utils::create_success_embed("Reminder Updated", format!("`{}` will now go off <t:{}:F> (<t:{}:R>)", crate::utils::reminder::short_id(reminder.id), at.timestamp(), at.timestamp()))
+                }
+                Err(e) => crate::utils::create_error_embed("Invalid Time Format", e.to_string()),
+            }
+        }
+        "reason" | "text" => {
+            let reason = args[2..].join(" ");
+            crate::database::ticket::update_reminder(&db.pool, reminder.id, Some(&reason), None).await?;
+            crate::utils::create_success_embed("Reminder Updated", format!("`{}` is now: {}", crate::utils::reminder::short_id(reminder.id), reason))
+        }
+        other => crate::utils::create_error_embed("Invalid Usage", format!("Unknown field `{}`. Edit either `time` or `reason`", other)),
+    };
+
+    msg.channel_id.send_message(&ctx.http, CreateMessage::new().embed(embed)).await?;
+    Ok(())
+}
+
+/// Looks up one of the author's pending reminders, with the lookup error kept
+/// separate so callers can show it instead of failing the command.
+async fn find_own_reminder(db: &Arc<Database>, msg: &Message, key: Option<&str>) -> Result<Result<crate::models::Reminder>> {
+    let Some(key) = key else {
+        return Ok(Err(anyhow::anyhow!("Give the reminder's ID. Use `!reminder list` to see them")));
+    };
+    let reminders = crate::database::ticket::get_user_reminders(&db.pool, msg.author.id.get() as i64).await?;
+    Ok(crate::utils::reminder::find_reminder(&reminders, key).cloned())
 }
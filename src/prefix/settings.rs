@@ -1,7 +1,8 @@
 use anyhow::Result;
-use serenity::all::{Context, Message, Permissions};
+use serenity::all::{Context, Message};
 use std::sync::Arc;
 use crate::database::Database;
+use super::has_admin_permissions;
 use crate::utils::{create_success_embed, create_error_embed};
 
 pub async fn settings(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str]) -> Result<()> {
@@ -48,13 +49,22 @@ pub async fn settings(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[
         }
         "autoclose" => {
             if args.len() < 2 {
-                let embed = create_error_embed("Missing Value", "Usage: `!settings autoclose <hours>` (0 to disable)");
+                let embed = create_error_embed("Missing Value", "Usage: `!settings autoclose <hours|duration>` (e.g. `48`, `2d`, `1d12h`; 0 to disable)");
                 msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
                 return Ok(());
             }
 
-            let hours: i32 = args[1].parse()
-                .map_err(|_| anyhow::anyhow!("Invalid hours value"))?;
+            let hours: i32 = match args[1].parse() {
+                Ok(hours) if hours >= 0 => hours,
+                Ok(_) => anyhow::bail!("Auto-close hours can't be negative"),
+                Err(_) => {
+                    let duration = crate::utils::time::parse_duration(args[1])?;
+                    if duration.num_seconds() % 3600 != 0 {
+                        anyhow::bail!("Auto-close works in whole hours, e.g. `36h` or `1d12h`");
+                    }
+                    i32::try_from(duration.num_hours()).map_err(|_| anyhow::anyhow!("That auto-close time is too long"))?
+                }
+            };
 
             let value = if hours == 0 { None } else { Some(hours) };
 
@@ -161,6 +171,30 @@ pub async fn settings(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[
 
             msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
         }
+        "exclusiveclaim" => {
+            if args.len() < 2 {
+                let embed = create_error_embed("Missing Value", "Usage: `!settings exclusiveclaim <true|false>`");
+                msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+                return Ok(());
+            }
+
+            let value = args[1].to_lowercase() == "true" || args[1] == "1" || args[1] == "on";
+
+            crate::database::ticket::update_exclusive_claim(&db.pool, guild_id, value).await?;
+            let synced = crate::utils::resync_exclusive_claims(ctx, db, guild_id, value).await?;
+
+            let mut msg_text = if value {
+                "Exclusive claims **enabled**. Claiming a ticket makes it read-only for other support staff".to_string()
+            } else {
+                "Exclusive claims **disabled**. All support staff can reply to claimed tickets".to_string()
+            };
+            if synced > 0 {
+                msg_text.push_str(&format!("\nUpdated **{}** open claimed ticket(s)", synced));
+            }
+
+            let embed = create_success_embed("Setting Updated", msg_text);
+            msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+        }
         "embedcolor" => {
             if args.len() < 2 {
                 let embed = create_error_embed("Missing Value", "Usage: `!settings embedcolor <hex_color>` (e.g., #5865F2)");
@@ -275,10 +309,11 @@ pub async fn settings(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[
                 "Unknown Setting",
                 "Available settings:\n\
                 • `claimbuttons` - Enable/disable claim buttons\n\
-                • `autoclose` - Auto-close tickets after X hours\n\
+                • `autoclose` - Auto-close tickets after X hours (or a duration like `2d`)\n\
                 • `ticketlimit` - Max tickets per user\n\
                 • `cooldown` - Cooldown between tickets (seconds)\n\
                 • `dmoncreate` - DM users when ticket is created\n\
+                • `exclusiveclaim` - Only the claimer can reply in claimed tickets\n\
                 • `embedcolor` - Panel embed color (hex)\n\
                 • `embedtitle` - Panel embed title\n\
                 • `embeddescription` - Panel embed description\n\
@@ -289,6 +324,10 @@ pub async fn settings(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[
         }
     }
 
+    if matches!(setting.as_str(), "embedcolor" | "embedtitle" | "embeddescription" | "embeddesc" | "embedfooter") {
+        crate::utils::panel::refresh_guild_panels(&ctx.http, db, guild_id).await;
+    }
+
     Ok(())
 }
 
@@ -301,6 +340,7 @@ async fn show_settings(ctx: &Context, msg: &Message, db: &Arc<Database>, guild_i
     let ticket_limit = guild.ticket_limit_per_user.unwrap_or(1);
     let cooldown = guild.ticket_cooldown_seconds.unwrap_or(0);
     let dm_on_create = guild.dm_on_create.unwrap_or(true);
+    let exclusive_claim = guild.exclusive_claim.unwrap_or(false);
 
     let embed_color = guild.embed_color.unwrap_or(5865714);
     let embed_title = guild.embed_title.unwrap_or_else(|| "Support Ticket".to_string());
@@ -313,7 +353,8 @@ async fn show_settings(ctx: &Context, msg: &Message, db: &Arc<Database>, guild_i
         • Auto Close: **{}**\n\
         • Ticket Limit: **{}** per user\n\
         • Cooldown: **{}** seconds\n\
-        • DM on Create: **{}**\n\n\
+        • DM on Create: **{}**\n\
+        • Exclusive Claim: **{}**\n\n\
         **Embed Customization:**\n\
         • Color: `#{:06X}`\n\
         • Title: {}\n\
@@ -325,6 +366,7 @@ async fn show_settings(ctx: &Context, msg: &Message, db: &Arc<Database>, guild_i
         if ticket_limit == 0 { "Unlimited".to_string() } else { ticket_limit.to_string() },
         cooldown,
         if dm_on_create { "Enabled" } else { "Disabled" },
+        if exclusive_claim { "Enabled" } else { "Disabled" },
         embed_color,
         embed_title,
         if embed_desc.len() > 100 { format!("{}...", &embed_desc[..100]) } else { embed_desc },
@@ -339,18 +381,3 @@ async fn show_settings(ctx: &Context, msg: &Message, db: &Arc<Database>, guild_i
     Ok(())
 }
 
-async fn has_admin_permissions(ctx: &Context, msg: &Message) -> Result<bool> {
-    let guild_id = match msg.guild_id {
-        Some(id) => id,
-        None => return Ok(false),
-    };
-
-    let member = guild_id.member(&ctx.http, msg.author.id).await?;
-
-    let permissions = {
-        let guild_obj = ctx.cache.guild(guild_id).ok_or_else(|| anyhow::anyhow!("Guild not found"))?;
-        guild_obj.member_permissions(&member)
-    };
-
-    Ok(permissions.contains(Permissions::ADMINISTRATOR))
-}
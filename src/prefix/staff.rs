@@ -0,0 +1,72 @@
+use anyhow::Result;
+use serenity::all::{Context, Message};
+use std::sync::Arc;
+use crate::database::Database;
+use crate::utils::{create_error_embed, create_success_embed};
+
+pub async fn away(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str]) -> Result<()> {
+    let guild_id = msg.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?.get() as i64;
+    let user_id = msg.author.id.get() as i64;
+
+    if !crate::utils::has_support_role_or_admin(ctx, msg.author.id, guild_id, db).await? {
+        let embed = create_error_embed("Permission Denied", "Only support staff can set an away status");
+        msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+        return Ok(());
+    }
+
+    // The first argument is treated as a duration only if it parses as one,
+    // so `!away on holiday` still works without an expiry.
+    let (away_until, reason_args) = match args.first().map(|a| crate::utils::time::parse_duration(a)) {
+        Some(Ok(duration)) => (Some(chrono::Utc::now() + duration), &args[1..]),
+        _ => (None, args),
+    };
+
+    let reason = if reason_args.is_empty() {
+        None
+    } else {
+        Some(reason_args.join(" "))
+    };
+
+    crate::database::staff::set_away(&db.pool, guild_id, user_id, reason.as_deref(), away_until).await?;
+
+    let prefix = crate::prefix::get_prefix(&db.pool, guild_id as u64).await;
+    let until_text = match away_until {
+        Some(until) => format!("until <t:{}:f>", until.timestamp()),
+        None => format!("until you use `{}back`", prefix),
+    };
+
+    let embed = create_success_embed(
+        "Away",
+        format!(
+            "<@{}> is now marked as away {}.\nThey won't receive escalation or handle DMs and can't be assigned tickets.",
+            user_id, until_text
+        ),
+    );
+    msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+
+    Ok(())
+}
+
+pub async fn back(ctx: &Context, msg: &Message, db: &Arc<Database>) -> Result<()> {
+    let guild_id = msg.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?.get() as i64;
+    let user_id = msg.author.id.get() as i64;
+
+    let embed = if crate::database::staff::clear_away(&db.pool, guild_id, user_id).await? {
+        create_success_embed("Welcome Back", format!("<@{}> is now marked as available", user_id))
+    } else {
+        create_error_embed("Not Away", "You are not marked as away")
+    };
+
+    msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+
+    Ok(())
+}
+
+pub async fn staff(ctx: &Context, msg: &Message, db: &Arc<Database>) -> Result<()> {
+    let guild_id = msg.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?.get() as i64;
+
+    let embed = crate::utils::build_staff_availability_embed(ctx, db, guild_id).await?;
+    msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+
+    Ok(())
+}
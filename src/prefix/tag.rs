@@ -2,7 +2,9 @@ use anyhow::Result;
 use serenity::all::{Context, Message};
 use std::sync::Arc;
 use crate::database::Database;
+use super::has_admin_permissions;
 use crate::utils::{create_error_embed, create_success_embed, create_embed};
+use crate::utils::tag::{BrowserFolder, ConflictStrategy, TagAction};
 
 pub async fn handle(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str]) -> Result<()> {
     let guild_id = msg.guild_id.map(|g| g.get()).unwrap_or(0);
@@ -25,6 +27,14 @@ pub async fn handle(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&s
         "raw" => raw(ctx, msg, db, &args[1..], &prefix).await?,
         "rename" => rename(ctx, msg, db, &args[1..], &prefix).await?,
         "popular" => popular(ctx, msg, db).await?,
+        "alias" => alias(ctx, msg, db, &args[1..], &prefix).await?,
+        "history" => history(ctx, msg, db, &args[1..], &prefix).await?,
+        "rollback" => rollback(ctx, msg, db, &args[1..], &prefix).await?,
+        "permissions" => permissions(ctx, msg, db, &args[1..], &prefix).await?,
+        "export" => export(ctx, msg, db).await?,
+        "import" => import(ctx, msg, db, &args[1..], &prefix).await?,
+        "library" => library(ctx, msg, db, &args[1..], &prefix).await?,
+        "folder" | "folders" => folder(ctx, msg, db, &args[1..], &prefix).await?,
         _ => use_tag(ctx, msg, db, args, &prefix).await?,
     }
 
@@ -40,13 +50,20 @@ async fn show_doc_page(ctx: &Context, msg: &Message, prefix: &str) -> Result<()>
             `{}tag create <name> <content>` - Create a new tag\n\
             `{}tag edit <name> <new content>` - Edit your tag\n\
             `{}tag delete <name>` - Delete your tag\n\
-            `{}tag list` - List all tags in this server\n\
+            `{}tag list` - Browse tags by folder, page by page\n\
             `{}tag search <query>` - Search for tags\n\
             `{}tag info <name>` - View tag information\n\
             `{}tag raw <name>` - View raw tag content\n\
             `{}tag rename <old> <new>` - Rename your tag\n\
-            `{}tag popular` - View most popular tags",
-            prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix
+            `{}tag alias add <name> <alias>` - Add another name for your tag\n\
+            `{}tag alias remove <alias>` - Remove an alias\n\
+            `{}tag popular` - View most popular tags\n\
+            `{}tag folder [create|delete|move]` - Group tags into folders\n\
+            `{}tag history <name>` / `{}tag rollback <name> <revision>` - Undo changes\n\
+            `{}tag export` / `{}tag import` - Copy tags between servers\n\n\
+            Use `{}doc tag` for permissions, placeholders and the shared library",
+            prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix, prefix,
+            prefix, prefix, prefix, prefix, prefix, prefix
         )
     ).color(0x5865F2);
 
@@ -71,6 +88,12 @@ async fn create(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str],
         return Ok(());
     }
 
+    if !crate::utils::tag::can_manage_tag(ctx, db, msg.guild_id.unwrap(), msg.author.id, TagAction::Create, None).await? {
+        let embed = create_error_embed("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Create));
+        msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+        return Ok(());
+    }
+
     if crate::database::tag::get_tag(&db.pool, guild_id, name).await?.is_some() {
         let embed = create_error_embed("Tag Exists", format!("Tag `{}` already exists", name));
         msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
@@ -99,19 +122,25 @@ async fn edit(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str], p
     let tag = crate::database::tag::get_tag(&db.pool, guild_id, name).await?;
 
     if tag.is_none() {
-        let embed = create_error_embed("Tag Not Found", format!("Tag `{}` does not exist", name));
+        let embed = crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?;
         msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
         return Ok(());
     }
 
     let tag = tag.unwrap();
-    if tag.creator_id != msg.author.id.get() as i64 {
-        let embed = create_error_embed("Permission Denied", "You can only edit tags you created");
+    if !crate::utils::tag::can_manage_tag(ctx, db, msg.guild_id.unwrap(), msg.author.id, TagAction::Edit, Some(tag.creator_id)).await? {
+        let embed = create_error_embed("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Edit));
+        msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+        return Ok(());
+    }
+
+    if tag.embed.is_some() {
+        let embed = create_error_embed("Embed Tag", format!("Tag `{}` is an embed tag. Use `/tag embed` to edit it.", tag.name));
         msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
         return Ok(());
     }
 
-    crate::database::tag::update_tag(&db.pool, guild_id, name, &content).await?;
+    crate::database::tag::update_tag(&db.pool, guild_id, &tag.name, &content, msg.author.id.get() as i64).await?;
 
     let embed = create_success_embed("Tag Updated", format!("Tag `{}` has been updated successfully", name));
     msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
@@ -132,19 +161,19 @@ async fn delete(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str],
     let tag = crate::database::tag::get_tag(&db.pool, guild_id, name).await?;
 
     if tag.is_none() {
-        let embed = create_error_embed("Tag Not Found", format!("Tag `{}` does not exist", name));
+        let embed = crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?;
         msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
         return Ok(());
     }
 
     let tag = tag.unwrap();
-    if tag.creator_id != msg.author.id.get() as i64 {
-        let embed = create_error_embed("Permission Denied", "You can only delete tags you created");
+    if !crate::utils::tag::can_manage_tag(ctx, db, msg.guild_id.unwrap(), msg.author.id, TagAction::Delete, Some(tag.creator_id)).await? {
+        let embed = create_error_embed("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Delete));
         msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
         return Ok(());
     }
 
-    crate::database::tag::delete_tag(&db.pool, guild_id, name).await?;
+    crate::database::tag::delete_tag(&db.pool, guild_id, &tag.name).await?;
 
     let embed = create_success_embed("Tag Deleted", format!("Tag `{}` has been deleted", name));
     msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
@@ -165,12 +194,14 @@ async fn info(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str], p
     let tag = crate::database::tag::get_tag(&db.pool, guild_id, name).await?;
 
     if let Some(tag) = tag {
+        let aliases = crate::database::tag::get_tag_aliases(&db.pool, tag.id).await?;
         let embed = create_embed(
             format!("Tag: {}", tag.name),
             format!(
-                "**Creator:** <@{}>\n**Uses:** {}\n**Created:** <t:{}:R>\n**Updated:** <t:{}:R>",
+                "**Creator:** <@{}>\n**Uses:** {}\n**Aliases:** {}\n**Created:** <t:{}:R>\n**Updated:** <t:{}:R>",
                 tag.creator_id,
                 tag.uses,
+                crate::utils::tag::format_tag_aliases(&aliases),
                 tag.created_at.timestamp(),
                 tag.updated_at.timestamp()
             )
@@ -178,7 +209,7 @@ async fn info(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str], p
 
         msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
     } else {
-        let embed = create_error_embed("Tag Not Found", format!("Tag `{}` does not exist", name));
+        let embed = crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?;
         msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
     }
 
@@ -187,23 +218,49 @@ async fn info(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str], p
 
 async fn list(ctx: &Context, msg: &Message, db: &Arc<Database>) -> Result<()> {
     let guild_id = msg.guild_id.unwrap().get() as i64;
-    let tags = crate::database::tag::list_tags(&db.pool, guild_id).await?;
+    let (embed, components) = crate::utils::tag::tag_browser(db, guild_id, BrowserFolder::All, 0, None).await?;
 
-    if tags.is_empty() {
-        let embed = create_error_embed("No Tags", "There are no tags in this server");
-        msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
-        return Ok(());
-    }
+    msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed).components(components)).await?;
 
-    let tag_list: Vec<String> = tags.iter()
-        .take(25)
-        .map(|t| format!("`{}` (uses: {})", t.name, t.uses))
-        .collect();
+    Ok(())
+}
 
-    let embed = create_embed(
-        format!("Tags ({} total)", tags.len()),
-        tag_list.join("\n")
-    );
+async fn folder(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str], prefix: &str) -> Result<()> {
+    let guild_id = msg.guild_id.unwrap().get() as i64;
+
+    let embed = match (args.first().copied(), args.get(1).copied()) {
+        (None, _) | (Some("list"), _) => {
+            let folders = crate::database::tag::get_tag_folders(&db.pool, guild_id).await?;
+            create_embed("Tag Folders", crate::utils::tag::format_tag_folders(&folders))
+        }
+        (Some("move"), Some(name)) => match crate::database::tag::get_tag(&db.pool, guild_id, name).await? {
+            None => crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?,
+            Some(tag) if !crate::utils::tag::can_manage_tag(ctx, db, msg.guild_id.unwrap(), msg.author.id, TagAction::Edit, Some(tag.creator_id)).await? => {
+                create_error_embed("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Edit))
+            }
+            Some(tag) => crate::utils::tag::move_tag_to_folder(db, guild_id, &tag, args.get(2).copied()).await?,
+        },
+        (Some("create" | "delete"), Some(_)) if !crate::utils::tag::can_manage_tag(ctx, db, msg.guild_id.unwrap(), msg.author.id, TagAction::Edit, None).await? => {
+            create_error_embed("Permission Denied", "You need Administrator or a tag edit role to manage folders")
+        }
+        (Some("create"), Some(name)) => {
+            if !crate::utils::tag::valid_folder_name(name) {
+                create_error_embed("Invalid Name", "Folder names must be one word of 50 characters or less, and can't be `none`")
+            } else if crate::database::tag::create_tag_folder(&db.pool, guild_id, name, msg.author.id.get() as i64).await?.is_some() {
+                create_success_embed("Folder Created", format!("Folder `{}` has been created. Add tags with `{}tag folder move <tag> {}`.", name, prefix, name))
+            } else {
+                create_error_embed("Folder Exists", format!("Folder `{}` already exists", name))
+            }
+        }
+        (Some("delete"), Some(name)) => {
+            if crate::database::tag::delete_tag_folder(&db.pool, guild_id, name).await? {
+                create_success_embed("Folder Deleted", format!("Folder `{}` has been deleted. Its tags are now unfiled.", name))
+            } else {
+                create_error_embed("Folder Not Found", format!("Folder `{}` does not exist", name))
+            }
+        }
+        _ => create_error_embed("Invalid Usage", format!("Use `{}tag folder [list|create <name>|delete <name>|move <tag> [folder|none]]`", prefix)),
+    };
 
     msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
 
@@ -255,10 +312,20 @@ async fn raw(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str], pr
     let tag = crate::database::tag::get_tag(&db.pool, guild_id, name).await?;
 
     if let Some(tag) = tag {
+        if let Some(embed) = &tag.embed {
+            let (content, file) = crate::utils::tag::export_tag_embed(&tag.name, embed)?;
+            let mut message = serenity::all::CreateMessage::new().content(content);
+            if let Some(file) = file {
+                message = message.add_file(file);
+            }
+            msg.channel_id.send_message(&ctx.http, message).await?;
+            return Ok(());
+        }
+
         let content = format!("```\n{}\n```", tag.content.replace("```", "\\`\\`\\`"));
         msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().content(content)).await?;
     } else {
-        let embed = create_error_embed("Tag Not Found", format!("Tag `{}` does not exist", name));
+        let embed = crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?;
         msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
     }
 
@@ -285,14 +352,14 @@ async fn rename(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str],
     let tag = crate::database::tag::get_tag(&db.pool, guild_id, old_name).await?;
 
     if tag.is_none() {
-        let embed = create_error_embed("Tag Not Found", format!("Tag `{}` does not exist", old_name));
+        let embed = crate::utils::tag::tag_not_found_embed(db, guild_id, old_name).await?;
         msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
         return Ok(());
     }
 
     let tag = tag.unwrap();
-    if tag.creator_id != msg.author.id.get() as i64 {
-        let embed = create_error_embed("Permission Denied", "You can only rename tags you created");
+    if !crate::utils::tag::can_manage_tag(ctx, db, msg.guild_id.unwrap(), msg.author.id, TagAction::Edit, Some(tag.creator_id)).await? {
+        let embed = create_error_embed("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Edit));
         msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
         return Ok(());
     }
@@ -303,7 +370,7 @@ async fn rename(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str],
         return Ok(());
     }
 
-    crate::database::tag::rename_tag(&db.pool, guild_id, old_name, new_name).await?;
+    crate::database::tag::rename_tag(&db.pool, guild_id, &tag.name, new_name).await?;
 
     let embed = create_success_embed("Tag Renamed", format!("Tag `{}` has been renamed to `{}`", old_name, new_name));
     msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
@@ -311,6 +378,213 @@ async fn rename(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str],
     Ok(())
 }
 
+async fn alias(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str], prefix: &str) -> Result<()> {
+    let guild_id = msg.guild_id.unwrap().get() as i64;
+
+    let embed = match (args.first().copied(), args.len()) {
+        (Some("add"), 3) => {
+            let (name, alias) = (args[1], args[2]);
+            match crate::database::tag::get_tag(&db.pool, guild_id, name).await? {
+                None => crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?,
+                Some(tag) if !crate::utils::tag::can_manage_tag(ctx, db, msg.guild_id.unwrap(), msg.author.id, TagAction::Edit, Some(tag.creator_id)).await? => {
+                    create_error_embed("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Edit))
+                }
+                Some(tag) => match crate::utils::tag::check_tag_alias(db, guild_id, alias).await? {
+                    Some(reason) => create_error_embed("Alias Not Added", reason),
+                    None => {
+                        crate::database::tag::add_tag_alias(&db.pool, guild_id, tag.id, alias, msg.author.id.get() as i64).await?;
+                        create_success_embed("Alias Added", format!("`{}` now points to tag `{}`", alias, tag.name))
+                    }
+                },
+            }
+        }
+        (Some("remove") | Some("delete"), 2) => {
+            let alias = args[1];
+            match crate::database::tag::get_tag(&db.pool, guild_id, alias).await? {
+                Some(tag) if !tag.name.eq_ignore_ascii_case(alias) => {
+                    if !crate::utils::tag::can_manage_tag(ctx, db, msg.guild_id.unwrap(), msg.author.id, TagAction::Edit, Some(tag.creator_id)).await? {
+                        create_error_embed("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Edit))
+                    } else {
+                        crate::database::tag::remove_tag_alias(&db.pool, guild_id, alias).await?;
+                        create_success_embed("Alias Removed", format!("`{}` no longer points to tag `{}`", alias, tag.name))
+                    }
+                }
+                _ => create_error_embed("Alias Not Found", format!("`{}` is not a tag alias", alias)),
+            }
+        }
+        _ => create_error_embed(
+            "Invalid Usage",
+            format!("Use `{}tag alias add <name> <alias>` or `{}tag alias remove <alias>`", prefix, prefix),
+        ),
+    };
+
+    msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+
+    Ok(())
+}
+
+async fn history(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str], prefix: &str) -> Result<()> {
+    let guild_id = msg.guild_id.unwrap().get() as i64;
+
+    let embed = match args.first() {
+        None => create_error_embed("Invalid Usage", format!("Use `{}tag history <name>`", prefix)),
+        Some(&name) => match crate::database::tag::get_tag(&db.pool, guild_id, name).await? {
+            None => crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?,
+            Some(tag) if !crate::utils::tag::can_manage_tag(ctx, db, msg.guild_id.unwrap(), msg.author.id, TagAction::Edit, Some(tag.creator_id)).await? => {
+                create_error_embed("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Edit))
+            }
+            Some(tag) => {
+                let revisions = crate::database::tag::get_tag_revisions(&db.pool, tag.id, 10).await?;
+                create_embed(format!("History: {}", tag.name), crate::utils::tag::format_tag_history(&tag, &revisions))
+                    .footer(serenity::all::CreateEmbedFooter::new(format!("Restore a version with {}tag rollback {} <number>", prefix, tag.name)))
+            }
+        },
+    };
+
+    msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+
+    Ok(())
+}
+
+async fn rollback(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str], prefix: &str) -> Result<()> {
+    let guild_id = msg.guild_id.unwrap().get() as i64;
+
+    let (name, number) = match (args.first(), args.get(1).and_then(|n| n.trim_start_matches('#').parse::<i32>().ok())) {
+        (Some(&name), Some(number)) => (name, number),
+        _ => {
+            let embed = create_error_embed("Invalid Usage", format!("Use `{}tag rollback <name> <revision>`", prefix));
+            msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+            return Ok(());
+        }
+    };
+
+    let embed = match crate::database::tag::get_tag(&db.pool, guild_id, name).await? {
+        None => crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?,
+        Some(tag) if !crate::utils::tag::can_manage_tag(ctx, db, msg.guild_id.unwrap(), msg.author.id, TagAction::Edit, Some(tag.creator_id)).await? => {
+            create_error_embed("Permission Denied", crate::utils::tag::permission_denied_message(TagAction::Edit))
+        }
+        Some(tag) => match crate::database::tag::get_tag_revision(&db.pool, tag.id, number).await? {
+            Some(revision) => {
+                crate::database::tag::restore_tag_revision(&db.pool, guild_id, &tag.name, &revision, msg.author.id.get() as i64).await?;
+                create_success_embed("Tag Restored", format!("Tag `{}` has been restored to revision #{}", tag.name, number))
+            }
+            None => create_error_embed("Revision Not Found", format!("Tag `{}` has no revision #{}. Use `{}tag history {}` to see its versions.", tag.name, number, prefix, tag.name)),
+        },
+    };
+
+    msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+
+    Ok(())
+}
+
+async fn permissions(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str], prefix: &str) -> Result<()> {
+    let guild_id = msg.guild_id.unwrap().get() as i64;
+
+    let embed = match args.first().copied() {
+        None | Some("show") | Some("list") => {
+            let roles = crate::database::tag::get_tag_permission_roles(&db.pool, guild_id).await?;
+            create_embed("Tag Permissions", crate::utils::tag::format_tag_permissions(&roles))
+        }
+        Some(_) if !has_admin_permissions(ctx, msg).await? => {
+            create_error_embed("Permission Denied", "You need Administrator permission to change tag permissions")
+        }
+        Some(sub @ ("add" | "remove")) => {
+            let action = args.get(1).and_then(|a| TagAction::parse(a));
+            let role_id = args.get(2).and_then(|r| r.trim_start_matches("<@&").trim_end_matches('>').parse::<i64>().ok());
+
+            match (action, role_id) {
+                (Some(action), Some(role_id)) if sub == "add" => {
+                    crate::database::tag::add_tag_permission_role(&db.pool, guild_id, action.as_str(), role_id).await?;
+                    create_success_embed("Tag Permissions Updated", format!("<@&{}> can now {} tags", role_id, action.as_str()))
+                }
+                (Some(action), Some(role_id)) => {
+                    if crate::database::tag::remove_tag_permission_role(&db.pool, guild_id, action.as_str(), role_id).await? {
+                        create_success_embed("Tag Permissions Updated", format!("<@&{}> can no longer {} tags", role_id, action.as_str()))
+                    } else {
+                        create_error_embed("Not Found", format!("<@&{}> is not a tag {} role", role_id, action.as_str()))
+                    }
+                }
+                _ => create_error_embed("Invalid Usage", format!("Use `{}tag permissions {} <create|edit|delete> <@role>`", prefix, sub)),
+            }
+        }
+        Some(_) => create_error_embed("Invalid Usage", format!("Use `{}tag permissions [show|add|remove] <create|edit|delete> <@role>`", prefix)),
+    };
+
+    msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+
+    Ok(())
+}
+
+async fn export(ctx: &Context, msg: &Message, db: &Arc<Database>) -> Result<()> {
+    let guild_id = msg.guild_id.unwrap().get() as i64;
+
+    let (count, file) = crate::utils::tag::export_tags(db, guild_id).await?;
+    let embed = create_success_embed("Tags Exported", format!("Exported {} tags. Load them into another server with `tag import`.", count));
+    msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed).add_file(file)).await?;
+
+    Ok(())
+}
+
+async fn import(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str], prefix: &str) -> Result<()> {
+    let guild_id = msg.guild_id.unwrap().get() as i64;
+    let strategy = match args.first() {
+        None => Some(ConflictStrategy::Skip),
+        Some(arg) => ConflictStrategy::parse(arg),
+    };
+
+    let embed = match (msg.attachments.first(), strategy) {
+        _ if !has_admin_permissions(ctx, msg).await? => {
+            create_error_embed("Permission Denied", "You need Administrator permission to import tags")
+        }
+        (Some(attachment), Some(strategy)) if attachment.size <= crate::utils::tag::MAX_IMPORT_BYTES => {
+            let data = attachment.download().await?;
+            match crate::utils::tag::import_tags(db, guild_id, &data, strategy, msg.author.id.get() as i64).await {
+                Ok(summary) => create_success_embed("Tags Imported", crate::utils::tag::format_import_summary(&summary)),
+                Err(e) => create_error_embed("Import Failed", e.to_string()),
+            }
+        }
+        (Some(_), Some(_)) => create_error_embed("Import Failed", "Import files can be at most 1 MB"),
+        _ => create_error_embed("Invalid Usage", format!("Attach a file from `{}tag export` and use `{}tag import [skip|overwrite|rename]`", prefix, prefix)),
+    };
+
+    msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+
+    Ok(())
+}
+
+async fn library(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str], prefix: &str) -> Result<()> {
+    let guild_id = msg.guild_id.unwrap().get() as i64;
+
+    let embed = match args.first().copied() {
+        None | Some("browse") | Some("list") => {
+            let entries = crate::database::tag::list_library_tags(&db.pool).await?;
+            let subscribed = crate::database::tag::is_tag_library_subscribed(&db.pool, guild_id).await?;
+            create_embed(format!("Tag Library ({})", entries.len()), crate::utils::tag::format_library_tags(&entries))
+                .footer(serenity::all::CreateEmbedFooter::new(if subscribed {
+                    "This server is subscribed".to_string()
+                } else {
+                    format!("Subscribe with {}tag library subscribe", prefix)
+                }))
+        }
+        Some(_) if !has_admin_permissions(ctx, msg).await? => {
+            create_error_embed("Permission Denied", "You need Administrator permission to manage the library subscription")
+        }
+        Some("subscribe") => match crate::database::tag::subscribe_tag_library(&db.pool, guild_id, msg.author.id.get() as i64).await? {
+            Some((added, skipped)) => create_success_embed("Subscribed", crate::utils::tag::library_subscribed_message(added, skipped)),
+            None => create_error_embed("Already Subscribed", "This server is already subscribed to the tag library"),
+        },
+        Some("unsubscribe") => match crate::database::tag::unsubscribe_tag_library(&db.pool, guild_id).await? {
+            Some(removed) => create_success_embed("Unsubscribed", format!("Removed {} library tags. Tags you edited have been kept.", removed)),
+            None => create_error_embed("Not Subscribed", "This server is not subscribed to the tag library"),
+        },
+        Some(_) => create_error_embed("Invalid Usage", format!("Use `{}tag library [browse|subscribe|unsubscribe]`", prefix)),
+    };
+
+    msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+
+    Ok(())
+}
+
 async fn popular(ctx: &Context, msg: &Message, db: &Arc<Database>) -> Result<()> {
     let guild_id = msg.guild_id.unwrap().get() as i64;
     let tags = crate::database::tag::get_popular_tags(&db.pool, guild_id, 10).await?;
@@ -334,18 +608,20 @@ async fn popular(ctx: &Context, msg: &Message, db: &Arc<Database>) -> Result<()>
 
 async fn use_tag(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str], prefix: &str) -> Result<()> {
     let guild_id = msg.guild_id.unwrap().get() as i64;
-    let name = args.join(" ");
+    let name = args[0];
 
-    let tag = crate::database::tag::get_tag(&db.pool, guild_id, &name).await?;
+    let tag = crate::database::tag::get_tag(&db.pool, guild_id, name).await?;
 
     if let Some(tag) = tag {
-        crate::database::tag::increment_tag_uses(&db.pool, guild_id, &name).await?;
+        crate::database::tag::increment_tag_uses(&db.pool, guild_id, &tag.name).await?;
 
-        let embed = create_embed(&tag.name, &tag.content);
+        let vars = crate::utils::tag::tag_variables(ctx, db, msg.guild_id, msg.channel_id, &msg.author).await?;
+        let (embed, components) = crate::utils::tag::tag_message(&tag, &vars, &args[1..]);
 
-        msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+        msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed).components(components)).await?;
     } else {
-        let embed = create_error_embed("Tag Not Found", format!("Tag `{}` does not exist. Use `{}tag list` to see all tags.", name, prefix));
+        let embed = crate::utils::tag::tag_not_found_embed(db, guild_id, name).await?
+            .footer(serenity::all::CreateEmbedFooter::new(format!("Use {}tag list to see all tags", prefix)));
         msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
     }
 
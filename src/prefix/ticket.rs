@@ -69,6 +69,7 @@ pub async fn claim(ctx: &Context, msg: &Message, db: &Arc<Database>) -> Result<(
     }
 
     db_ticket::claim_ticket(&db.pool, ticket.id, msg.author.id.get() as i64).await?;
+    let _ = crate::utils::sync_exclusive_claim(ctx, db, ticket.id, &[]).await;
 
     let _ = db_ticket::deactivate_escalation(&db.pool, ticket.id).await;
 
@@ -260,13 +261,14 @@ pub async fn escalate(ctx: &Context, msg: &Message, db: &Arc<Database>) -> Resul
 
     let support_roles = db_ticket::get_support_roles(&db.pool, ticket.guild_id).await?;
     let guild_id = msg.guild_id.ok_or_else(|| anyhow::anyhow!("Not in guild"))?;
+    let away_staff = crate::database::staff::get_away_user_ids(&db.pool, ticket.guild_id).await?;
 
     for role in &support_roles {
         let members = guild_id.members(&ctx.http, None, None).await?;
         let role_id = serenity::all::RoleId::new(role.role_id as u64);
 
         for member in members {
-            if member.roles.contains(&role_id) && !member.user.bot {
+            if member.roles.contains(&role_id) && !member.user.bot && !away_staff.contains(&(member.user.id.get() as i64)) {
                 let dm = member.user.create_dm_channel(&ctx.http).await?;
                 let _ = dm.send_message(
                     &ctx.http,
@@ -342,13 +344,14 @@ pub async fn handle(ctx: &Context, msg: &Message, db: &Arc<Database>) -> Result<
     }
 
     let guild_id = msg.guild_id.ok_or_else(|| anyhow::anyhow!("Not in guild"))?;
+    let away_staff = crate::database::staff::get_away_user_ids(&db.pool, ticket.guild_id).await?;
 
     for role in &support_roles {
         let members = guild_id.members(&ctx.http, None, None).await?;
         let role_id = serenity::all::RoleId::new(role.role_id as u64);
 
         for member in members {
-            if member.roles.contains(&role_id) && !member.user.bot {
+            if member.roles.contains(&role_id) && !member.user.bot && !away_staff.contains(&(member.user.id.get() as i64)) {
                 let dm = member.user.create_dm_channel(&ctx.http).await?;
                 let _ = dm.send_message(
                     &ctx.http,
@@ -496,8 +499,22 @@ pub async fn assign(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&s
         return Ok(());
     }
 
+    if let Some(away) = crate::database::staff::get_away_status(&db.pool, ticket.guild_id, mentioned_user_id).await? {
+        let embed = create_error_embed(
+            "Staff Away",
+            format!("<@{}> is currently away{}. Assign someone else or wait until they are back", mentioned_user_id, crate::utils::format_away_until(&away)),
+        );
+        msg.channel_id.send_message(&ctx.http,
+            serenity::all::CreateMessage::new().embed(embed)
+        ).await?;
+        return Ok(());
+    }
+
     db_ticket::assign_ticket(&db.pool, ticket.id, mentioned_user_id).await?;
 
+    let previous_assignee: Vec<i64> = ticket.assigned_to.into_iter().collect();
+    let _ = crate::utils::sync_exclusive_claim(ctx, db, ticket.id, &previous_assignee).await;
+
     if let Ok(user) = serenity::all::UserId::new(mentioned_user_id as u64).to_user(&ctx.http).await {
         if let Ok(dm_channel) = user.create_dm_channel(&ctx.http).await {
             let dm_embed = crate::utils::create_embed(
@@ -544,3 +561,100 @@ pub async fn assign(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&s
 
     Ok(())
 }
+
+pub async fn merge(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str]) -> Result<()> {
+    let guild_id = msg.guild_id.ok_or_else(|| anyhow::anyhow!("Not in a guild"))?.get() as i64;
+
+    if !is_support_staff(ctx, msg, guild_id, db).await? {
+        let embed = create_error_embed("Permission Denied", "Only support staff can merge tickets");
+        msg.channel_id.send_message(&ctx.http,
+            serenity::all::CreateMessage::new().embed(embed)
+        ).await?;
+        return Ok(());
+    }
+
+    let source = match db_ticket::get_ticket_by_channel(&db.pool, msg.channel_id.get() as i64).await? {
+        Some(t) => t,
+        None => {
+            let embed = create_error_embed("Not a Ticket", "Run this command in the ticket you want to merge away");
+            msg.channel_id.send_message(&ctx.http,
+                serenity::all::CreateMessage::new().embed(embed)
+            ).await?;
+            return Ok(());
+        }
+    };
+
+    let target_channel_id = match msg.mention_channels.first() {
+        Some(channel) => Some(channel.id.get() as i64),
+        None => args.first().and_then(|a| a.trim_start_matches("<#").trim_end_matches('>').parse::<i64>().ok()),
+    };
+
+    let target = match target_channel_id {
+        Some(channel_id) => db_ticket::get_ticket_by_channel(&db.pool, channel_id).await?,
+        None => None,
+    };
+
+    let target = match target {
+        Some(t) if t.guild_id == guild_id && t.id != source.id => t,
+        _ => {
+            let embed = create_error_embed(
+                "Invalid Target",
+                "Usage: `!merge #ticket-channel`. The target must be another open ticket in this server",
+            );
+            msg.channel_id.send_message(&ctx.http,
+                serenity::all::CreateMessage::new().embed(embed)
+            ).await?;
+            return Ok(());
+        }
+    };
+
+    crate::utils::merge_tickets(ctx, db, source, target, msg.author.id.get()).await?;
+
+    Ok(())
+}
+
+pub async fn followup(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str]) -> Result<()> {
+    let ticket = match db_ticket::get_ticket_by_channel(&db.pool, msg.channel_id.get() as i64).await? {
+        Some(t) => t,
+        None => {
+            let embed = create_error_embed("Not a Ticket", "This command can only be used in ticket channels");
+            msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+            return Ok(());
+        }
+    };
+
+    if !is_support_staff(ctx, msg, ticket.guild_id, db).await? {
+        let embed = create_error_embed("Permission Denied", "Only support staff can set follow-ups");
+        msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+        return Ok(());
+    }
+
+    if args.is_empty() {
+        let embed = create_error_embed(
+            "Invalid Usage",
+            "Usage: `!followup <when> [--mention owner|claimer|both] [--always] [message]`\n\n\
+            Examples:\n\
+            `!followup 2d` - Ping the owner in 2 days unless they reply first\n\
+            `!followup tomorrow 10am --mention both Did the fix work?`\n\
+            `!followup 1w --always Closing soon if there's nothing else`\n\n\
+            Cancel one with `!reminder cancel <id>`."
+        );
+        msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+        return Ok(());
+    }
+
+    let tz = crate::utils::time::user_timezone(db, msg.author.id.get() as i64).await?;
+    let followup = match crate::utils::reminder::parse_followup(args, chrono::Utc::now(), tz) {
+        Ok(followup) => followup,
+        Err(e) => {
+            let embed = create_error_embed("Invalid Follow-up", e.to_string());
+            msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+            return Ok(());
+        }
+    };
+
+    let embed = crate::utils::reminder::create_followup(db, &ticket, msg.author.id.get() as i64, &followup).await?;
+    msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use anyhow::Result;
 use serenity::all::{Context, Message};
 use std::sync::Arc;
 use crate::database::Database;
-use crate::utils::create_embed;
+use crate::utils::{create_embed, create_error_embed, create_success_embed};
 
 pub async fn ping(ctx: &Context, msg: &Message, _db: &Arc<Database>) -> Result<()> {
     let start = std::time::Instant::now();
@@ -66,3 +66,39 @@ pub async fn bot_mention(ctx: &Context, msg: &Message, db: &Arc<Database>) -> Re
 
     Ok(())
 }
+
+pub async fn timezone(ctx: &Context, msg: &Message, db: &Arc<Database>, args: &[&str]) -> Result<()> {
+    let user_id = msg.author.id.get() as i64;
+    let now = chrono::Utc::now();
+
+    let embed = match args.first().map(|a| a.to_lowercase()) {
+        None => {
+            let tz = crate::utils::time::user_timezone(db, user_id).await?;
+            create_embed(
+                "Your Timezone",
+                format!(
+                    "Your timezone is {}.\n\nSet it with `!timezone <zone>`, e.g. `!timezone Europe/London` or `!timezone America/New_York`. \
+                    Reminders like `tomorrow 9am` are read in this timezone.",
+                    crate::utils::time::describe_timezone(tz, now)
+                ),
+            )
+        }
+        Some(arg) if arg == "reset" || arg == "clear" => {
+            crate::database::ticket::clear_user_timezone(&db.pool, user_id).await?;
+            create_success_embed("Timezone Reset", "Your timezone is back to **UTC**")
+        }
+        Some(_) => match crate::utils::time::parse_timezone(args[0]) {
+            Some(tz) => {
+                crate::database::ticket::set_user_timezone(&db.pool, user_id, tz.name()).await?;
+                create_success_embed("Timezone Set", format!("Your timezone is now {}", crate::utils::time::describe_timezone(tz, now)))
+            }
+            None => create_error_embed(
+                "Unknown Timezone",
+                format!("`{}` isn't a timezone I know. Use a name like `Europe/London`, `America/New_York` or `Asia/Tokyo`", args[0]),
+            ),
+        },
+    };
+
+    msg.channel_id.send_message(&ctx.http, serenity::all::CreateMessage::new().embed(embed)).await?;
+    Ok(())
+}
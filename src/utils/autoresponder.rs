@@ -0,0 +1,261 @@
+use anyhow::Result;
+use regex_automata::meta::Regex;
+use regex_automata::util::syntax;
+use serenity::all::{Context, CreateMessage, Message};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::models::{NewTagTrigger, TagTrigger, Ticket};
+
+pub const MAX_TRIGGERS_PER_GUILD: usize = 50;
+const MAX_PATTERN_LENGTH: usize = 500;
+const DEFAULT_COOLDOWN_SECONDS: i32 = 600;
+// Leaves room under Discord's 4096-character embed description.
+const LIST_CHAR_BUDGET: usize = 3900;
+
+// Compiled regex rules keyed by trigger id. Rules can't be edited, only removed,
+// so an entry stays valid for as long as its trigger exists.
+static REGEX_CACHE: OnceLock<Mutex<HashMap<Uuid, Regex>>> = OnceLock::new();
+
+fn regex_cache() -> &'static Mutex<HashMap<Uuid, Regex>> {
+    REGEX_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Raw auto-responder rule options as typed by an admin.
+pub struct TriggerInput<'a> {
+    pub tag: &'a str,
+    pub pattern: &'a str,
+    pub is_regex: bool,
+    pub cooldown: Option<&'a str>,
+    pub category: Option<&'a str>,
+    pub first_message_only: bool,
+}
+
+fn compile_pattern(pattern: &str) -> Result<Regex> {
+    // Keep the compiled automaton small; patterns come from server admins.
+    Regex::builder()
+        .syntax(syntax::Config::new().case_insensitive(true))
+        .configure(Regex::config().nfa_size_limit(Some(1 << 20)))
+        .build(pattern)
+        .map_err(|e| anyhow::anyhow!("Invalid regex: {}", e))
+}
+
+/// Validates user input for an auto-responder rule. Keyword patterns are
+/// comma-separated phrases; the cooldown defaults to 10 minutes.
+pub async fn parse_tag_trigger(db: &Database, guild_id: i64, input: TriggerInput<'_>) -> Result<NewTagTrigger> {
+    let TriggerInput { tag, pattern, is_regex, cooldown, category, first_message_only } = input;
+    let tag = crate::database::tag::get_tag(&db.pool, guild_id, tag)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Tag `{}` does not exist", tag))?;
+
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return Err(anyhow::anyhow!("The pattern can't be empty"));
+    }
+    if pattern.len() > MAX_PATTERN_LENGTH {
+        return Err(anyhow::anyhow!("Patterns must be {} characters or less", MAX_PATTERN_LENGTH));
+    }
+    if is_regex {
+        compile_pattern(pattern)?;
+    } else if keywords(pattern).next().is_none() {
+        return Err(anyhow::anyhow!("Give at least one keyword"));
+    }
+
+    let cooldown_seconds = match cooldown {
+        Some("0") | Some("off") | Some("none") => 0,
+        Some(cooldown) => crate::utils::time::parse_duration(cooldown)?.num_seconds().min(i32::MAX as i64) as i32,
+        None => DEFAULT_COOLDOWN_SECONDS,
+    };
+
+    let (category_id, category_name) = match category {
+        Some(name) => {
+            let id = crate::database::ticket::find_category_by_name(&db.pool, guild_id, name)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("Ticket category `{}` does not exist", name))?;
+            (Some(id), Some(name.to_string()))
+        }
+        None => (None, None),
+    };
+
+    let existing = crate::database::tag::get_tag_triggers(&db.pool, guild_id).await?;
+    if existing.len() >= MAX_TRIGGERS_PER_GUILD {
+        return Err(anyhow::anyhow!("A server can have at most {} auto-responder rules", MAX_TRIGGERS_PER_GUILD));
+    }
+
+    Ok(NewTagTrigger {
+        tag_id: tag.id,
+        tag_name: tag.name,
+        pattern: pattern.to_string(),
+        is_regex,
+        category_id,
+        category_name,
+        cooldown_seconds,
+        first_message_only,
+    })
+}
+
+fn keywords(pattern: &str) -> impl Iterator<Item = String> + '_ {
+    pattern.split(',').map(|k| k.trim().to_lowercase()).filter(|k| !k.is_empty())
+}
+
+/// True when `phrase` appears in `haystack` as whole words.
+fn contains_phrase(haystack: &str, phrase: &str) -> bool {
+    haystack.match_indices(phrase).any(|(start, _)| {
+        let end = start + phrase.len();
+        let before = haystack[..start].chars().next_back();
+        let after = haystack[end..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+/// The compiled regex for a rule. Rules saved on another instance, or before a
+/// restart, are compiled on first use.
+fn trigger_regex(trigger: &TagTrigger) -> Option<Regex> {
+    if let Some(re) = regex_cache().lock().unwrap().get(&trigger.id) {
+        return Some(re.clone());
+    }
+    let re = compile_pattern(&trigger.pattern).ok()?;
+    regex_cache().lock().unwrap().insert(trigger.id, re.clone());
+    Some(re)
+}
+
+/// Saves a validated rule and caches its compiled regex.
+pub async fn save_tag_trigger(db: &Database, guild_id: i64, trigger: &NewTagTrigger, created_by: i64) -> Result<()> {
+    let id = crate::database::tag::add_tag_trigger(&db.pool, guild_id, trigger, created_by).await?;
+    if trigger.is_regex {
+        let re = compile_pattern(&trigger.pattern)?;
+        regex_cache().lock().unwrap().insert(id, re);
+    }
+    Ok(())
+}
+
+pub async fn remove_tag_trigger(db: &Database, guild_id: i64, trigger_id: Uuid) -> Result<bool> {
+    regex_cache().lock().unwrap().remove(&trigger_id);
+    crate::database::tag::remove_tag_trigger(&db.pool, guild_id, trigger_id).await
+}
+
+pub fn trigger_matches(trigger: &TagTrigger, content: &str) -> bool {
+    if trigger.is_regex {
+        trigger_regex(trigger).is_some_and(|re| re.is_match(content))
+    } else {
+        let content = content.to_lowercase();
+        keywords(&trigger.pattern).any(|keyword| contains_phrase(&content, &keyword))
+    }
+}
+
+fn describe_cooldown(seconds: i32) -> String {
+    match seconds {
+        0 => "no cooldown".to_string(),
+        s if s < 60 => format!("{}s cooldown", s),
+        s => format!("{} cooldown", super::format_minutes(s as i64 / 60)),
+    }
+}
+
+pub fn describe_trigger(
+    tag_name: &str,
+    pattern: &str,
+    is_regex: bool,
+    category_name: Option<&str>,
+    cooldown_seconds: i32,
+    first_message_only: bool,
+) -> String {
+    let mut line = format!(
+        "`{}` on {} `{}` • {} • {}",
+        tag_name,
+        if is_regex { "regex" } else { "keywords" },
+        pattern,
+        category_name.map(|c| format!("category {}", c)).unwrap_or_else(|| "all categories".to_string()),
+        describe_cooldown(cooldown_seconds)
+    );
+    if first_message_only {
+        line.push_str(" • first message only");
+    }
+    line
+}
+
+pub fn format_tag_triggers(triggers: &[TagTrigger]) -> String {
+    if triggers.is_empty() {
+        return "No auto-responder rules are configured.".to_string();
+    }
+
+    let mut list = String::new();
+    for (i, t) in triggers.iter().enumerate() {
+        let line = format!(
+            "**#{}** {}",
+            i + 1,
+            describe_trigger(&t.tag_name, &t.pattern, t.is_regex, t.category_name.as_deref(), t.cooldown_seconds, t.first_message_only)
+        );
+        if list.len() + line.len() + 1 > LIST_CHAR_BUDGET {
+            list.push_str(&format!("\n...and {} more", triggers.len() - i));
+            break;
+        }
+        if !list.is_empty() {
+            list.push('\n');
+        }
+        list.push_str(&line);
+    }
+    list
+}
+
+/// Replies with the first matching tag when a ticket owner's message matches a rule.
+/// At most one rule fires per message.
+pub async fn respond(ctx: &Context, db: &Database, msg: &Message, ticket: &Ticket) -> Result<()> {
+    let triggers = crate::database::tag::get_tag_triggers(&db.pool, ticket.guild_id).await?;
+    let mut is_first_message = None;
+
+    for trigger in triggers {
+        if trigger.category_id.is_some() && trigger.category_id != ticket.category_id {
+            continue;
+        }
+        if !trigger_matches(&trigger, &msg.content) {
+            continue;
+        }
+
+        if trigger.first_message_only {
+            let first = match is_first_message {
+                Some(first) => first,
+                None => {
+                    // The message has already been logged, so the first one counts as 1.
+                    let count = crate::database::ticket::count_ticket_messages_by(&db.pool, ticket.id, ticket.owner_id).await?;
+                    *is_first_message.insert(count <= 1)
+                }
+            };
+            if !first {
+                continue;
+            }
+        }
+
+        if trigger.cooldown_seconds > 0 {
+            let mut redis_conn = db.redis.clone();
+            let acquired: Option<String> = redis::cmd("SET")
+                .arg(format!("tag_trigger_cooldown:{}:{}", ticket.id, trigger.id))
+                .arg(1)
+                .arg("NX")
+                .arg("EX")
+                .arg(trigger.cooldown_seconds)
+                .query_async(&mut redis_conn)
+                .await?;
+            if acquired.is_none() {
+                continue;
+            }
+        }
+
+        let Some(tag) = crate::database::tag::get_tag(&db.pool, ticket.guild_id, &trigger.tag_name).await? else {
+            continue;
+        };
+
+        let vars = super::tag::tag_variables(ctx, db, msg.guild_id, msg.channel_id, &msg.author).await?;
+        let (embed, components) = super::tag::tag_message(&tag, &vars, &[]);
+        msg.channel_id.send_message(
+            &ctx.http,
+            CreateMessage::new().embed(embed).components(components).reference_message(msg),
+        ).await?;
+        crate::database::tag::increment_tag_uses(&db.pool, ticket.guild_id, &tag.name).await?;
+
+        break;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use crate::database::stats::{DailyCount, PeriodStats};
+use anyhow::Result;
+use plotters::coord::ranged1d::SegmentValue;
+use plotters::prelude::*;
+use std::sync::Once;
+
+use super::stats::StatsPeriod;
+
+// Bundled so charts render the same on hosts without system fonts (e.g. the slim Docker image).
+static FONT: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans.ttf");
+static REGISTER_FONT: Once = Once::new();
+
+const WIDTH: u32 = 1000;
+const HEIGHT: u32 = 800;
+const MAX_CATEGORIES: usize = 10;
+
+const BLURPLE: RGBColor = RGBColor(88, 101, 242);
+const GREEN: RGBColor = RGBColor(59, 165, 93);
+
+/// Renders opened/closed per day and tickets per category as a PNG.
+pub fn render_stats_chart(period: &StatsPeriod, stats: &PeriodStats) -> Result<Vec<u8>> {
+    REGISTER_FONT.call_once(|| {
+        let _ = plotters::style::register_font("sans-serif", FontStyle::Normal, FONT);
+    });
+
+    let mut pixels = vec![0u8; (WIDTH * HEIGHT * 3) as usize];
+    {
+        let root = BitMapBackend::with_buffer(&mut pixels, (WIDTH, HEIGHT)).into_drawing_area();
+        root.fill(&WHITE)?;
+
+        let root = root.titled(&format!("Ticket Volume - {}", period.label), ("sans-serif", 28))?;
+        let (top, bottom) = root.split_vertically((HEIGHT - 40) / 2);
+
+        draw_daily_chart(&top, &stats.daily)?;
+        draw_category_chart(&bottom, &stats.by_category)?;
+
+        root.present()?;
+    }
+
+    encode_png(&pixels)
+}
+
+fn draw_daily_chart(area: &DrawingArea<BitMapBackend, plotters::coord::Shift>, daily: &[DailyCount]) -> Result<()> {
+    let last = daily.len().saturating_sub(1).max(1) as i32;
+    let max = daily.iter().map(|d| d.opened.max(d.closed)).max().unwrap_or(0).max(1);
+
+    let mut chart = ChartBuilder::on(area)
+        .caption("Opened and closed per day", ("sans-serif", 20))
+        .margin(15)
+        .x_label_area_size(35)
+        .y_label_area_size(45)
+        .build_cartesian_2d(0..last, 0..max + max / 10 + 1)?;
+
+    chart
+        .configure_mesh()
+        .x_labels(daily.len().min(10))
+        .x_label_formatter(&|i| {
+            daily.get(*i as usize).map(|d| d.day.format("%b %d").to_string()).unwrap_or_default()
+        })
+        .light_line_style(WHITE.mix(0.0))
+        .label_style(("sans-serif", 14))
+        .draw()?;
+
+    chart
+        .draw_series(LineSeries::new(
+            daily.iter().enumerate().map(|(i, d)| (i as i32, d.opened)),
+            BLURPLE.stroke_width(3),
+        ))?
+        .label("Opened")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLURPLE.stroke_width(3)));
+
+    chart
+        .draw_series(LineSeries::new(
+            daily.iter().enumerate().map(|(i, d)| (i as i32, d.closed)),
+            GREEN.stroke_width(3),
+        ))?
+        .label("Closed")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], GREEN.stroke_width(3)));
+
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperLeft)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK.mix(0.3))
+        .label_font(("sans-serif", 16))
+        .draw()?;
+
+    Ok(())
+}
+
+fn draw_category_chart(area: &DrawingArea<BitMapBackend, plotters::coord::Shift>, by_category: &[(String, i64)]) -> Result<()> {
+    let categories: Vec<(String, i64)> = by_category
+        .iter()
+        .take(MAX_CATEGORIES)
+        .map(|(name, count)| (truncate_label(name), *count))
+        .collect();
+
+    if categories.is_empty() {
+        area.draw_text(
+            "No tickets were opened in this period",
+            &("sans-serif", 20).into_text_style(area).color(&BLACK.mix(0.6)),
+            (WIDTH as i32 / 2 - 170, area.dim_in_pixel().1 as i32 / 2),
+        )?;
+        return Ok(());
+    }
+
+    let max = categories.iter().map(|(_, count)| *count).max().unwrap_or(0).max(1);
+
+    let mut chart = ChartBuilder::on(area)
+        .caption("Tickets opened per category", ("sans-serif", 20))
+        .margin(15)
+        .x_label_area_size(35)
+        .y_label_area_size(45)
+        .build_cartesian_2d((0..categories.len() - 1).into_segmented(), 0..max + max / 10 + 1)?;
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_labels(categories.len())
+        .x_label_formatter(&|value| match value {
+            SegmentValue::CenterOf(i) | SegmentValue::Exact(i) => {
+                categories.get(*i).map(|(name, _)| name.clone()).unwrap_or_default()
+            }
+            SegmentValue::Last => String::new(),
+        })
+        .light_line_style(WHITE.mix(0.0))
+        .label_style(("sans-serif", 14))
+        .draw()?;
+
+    chart.draw_series(
+        Histogram::vertical(&chart)
+            .style(BLURPLE.filled())
+            .margin(12)
+            .data(categories.iter().enumerate().map(|(i, (_, count))| (i, *count))),
+    )?;
+
+    Ok(())
+}
+
+fn truncate_label(name: &str) -> String {
+    if name.chars().count() > 14 {
+        format!("{}...", name.chars().take(12).collect::<String>())
+    } else {
+        name.to_string()
+    }
+}
+
+fn encode_png(pixels: &[u8]) -> Result<Vec<u8>> {
+    let mut png = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut png, WIDTH, HEIGHT);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_compression(png::Compression::Best);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(pixels)?;
+    }
+    Ok(png)
+}
+
@@ -0,0 +1,199 @@
+use crate::models::GuildDigest;
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use serenity::all::CreateEmbed;
+
+use super::stats::{format_change, format_duration_seconds, StaffSort, StatsPeriod};
+use super::time::parse_timezone;
+
+const WEEKDAYS: [&str; 7] = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+
+// How late a digest may still go out, e.g. after the bot was offline at the scheduled time.
+const CATCH_UP_WINDOW_HOURS: i64 = 6;
+
+/// Parses a weekday name or three-letter abbreviation into 0 (Monday) to 6 (Sunday).
+pub fn parse_weekday(input: &str) -> Option<i16> {
+    let input = input.to_lowercase();
+    WEEKDAYS
+        .iter()
+        .position(|d| d.to_lowercase() == input || d[..3].to_lowercase() == input)
+        .map(|i| i as i16)
+}
+
+/// Parses a 24-hour `HH:MM` time.
+pub fn parse_time(input: &str) -> Option<(i16, i16)> {
+    let time = NaiveTime::parse_from_str(input, "%H:%M").ok()?;
+    Some((time.hour() as i16, time.minute() as i16))
+}
+
+pub fn describe_schedule(digest: &GuildDigest) -> String {
+    let when = if digest.is_weekly() {
+        format!("Every {}", WEEKDAYS.get(digest.weekday as usize).unwrap_or(&"Monday"))
+    } else {
+        "Every day".to_string()
+    };
+
+    format!("{} at {:02}:{:02} ({})", when, digest.hour, digest.minute, digest.timezone)
+}
+
+/// Validates user input for a digest schedule. Weekly digests default to Monday, and
+/// the timezone defaults to UTC.
+pub fn parse_digest_schedule(
+    guild_id: i64,
+    frequency: &str,
+    weekday: Option<&str>,
+    time: &str,
+    timezone: Option<&str>,
+    sla_hours: Option<i32>,
+) -> Result<GuildDigest> {
+    let frequency = match frequency.to_lowercase().as_str() {
+        "daily" | "day" => "daily",
+        "weekly" | "week" => "weekly",
+        other => return Err(anyhow::anyhow!("Unknown frequency `{}`, use `daily` or `weekly`", other)),
+    };
+
+    let weekday = match weekday {
+        Some(day) => parse_weekday(day).ok_or_else(|| anyhow::anyhow!("Unknown weekday `{}`", day))?,
+        None => 0,
+    };
+
+    let (hour, minute) = parse_time(time)
+        .ok_or_else(|| anyhow::anyhow!("Invalid time `{}`, use 24-hour `HH:MM`", time))?;
+
+    let timezone = match timezone {
+        Some(tz) => parse_timezone(tz)
+            .ok_or_else(|| anyhow::anyhow!("Unknown timezone `{}`, use an IANA name like `Europe/London`", tz))?
+            .name()
+            .to_string(),
+        None => "UTC".to_string(),
+    };
+
+    let sla_hours = sla_hours.unwrap_or(24);
+    if !(1..=720).contains(&sla_hours) {
+        return Err(anyhow::anyhow!("The SLA must be between 1 and 720 hours"));
+    }
+
+    Ok(GuildDigest {
+        guild_id,
+        enabled: true,
+        frequency: frequency.to_string(),
+        weekday,
+        hour,
+        minute,
+        timezone,
+        sla_hours,
+        last_sent_at: None,
+    })
+}
+
+pub fn format_digest_settings(digest: Option<&GuildDigest>) -> String {
+    match digest {
+        Some(digest) => format!(
+            "**Status:** {}\n**Schedule:** {}\n**SLA:** first response within {}h\n**Last Sent:** {}",
+            if digest.enabled { "Enabled" } else { "Disabled" },
+            describe_schedule(digest),
+            digest.sla_hours,
+            digest.last_sent_at
+                .map(|t| format!("<t:{}:R>", t.timestamp()))
+                .unwrap_or_else(|| "Never".to_string())
+        ),
+        None => "No digest is configured.".to_string(),
+    }
+}
+
+/// The most recent scheduled send time at or before `now`.
+fn last_scheduled_run(digest: &GuildDigest, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let tz = parse_timezone(&digest.timezone).unwrap_or(Tz::UTC);
+    let local_now = now.with_timezone(&tz);
+    let time = NaiveTime::from_hms_opt(digest.hour as u32, digest.minute as u32, 0)?;
+    let step = if digest.is_weekly() { 7 } else { 1 };
+
+    let mut date = local_now.date_naive();
+    if digest.is_weekly() {
+        let days_back = (local_now.weekday().num_days_from_monday() as i64 - digest.weekday as i64).rem_euclid(7);
+        date -= Duration::days(days_back);
+    }
+
+    let scheduled = tz.from_local_datetime(&date.and_time(time)).earliest()?.with_timezone(&Utc);
+    if scheduled <= now {
+        return Some(scheduled);
+    }
+
+    let date = date - Duration::days(step);
+    Some(tz.from_local_datetime(&date.and_time(time)).earliest()?.with_timezone(&Utc))
+}
+
+pub fn is_due(digest: &GuildDigest, now: DateTime<Utc>) -> bool {
+    let Some(scheduled) = last_scheduled_run(digest, now) else {
+        return false;
+    };
+
+    now - scheduled < Duration::hours(CATCH_UP_WINDOW_HOURS)
+        && digest.last_sent_at.is_none_or(|sent| sent < scheduled)
+}
+
+/// Builds the digest from the same queries that back `/stats` and `/staffstats`.
+pub async fn build_digest_embed(db: &crate::database::Database, digest: &GuildDigest) -> Result<CreateEmbed> {
+    let period = if digest.is_weekly() {
+        StatsPeriod::last(Duration::days(7), "Last 7 days")
+    } else {
+        StatsPeriod::last(Duration::hours(24), "Last 24 hours")
+    };
+    let previous = period.previous();
+
+    let stats = crate::database::stats::get_period_stats(&db.pool, digest.guild_id, period.start, period.end).await?;
+    let (prev_opened, prev_closed) =
+        crate::database::stats::count_opened_closed(&db.pool, digest.guild_id, previous.start, previous.end).await?;
+    let (open, unclaimed) = crate::database::stats::get_backlog(&db.pool, digest.guild_id).await?;
+    let oldest = crate::database::stats::get_oldest_unclaimed(&db.pool, digest.guild_id, 5).await?;
+    let breaches = crate::database::stats::count_sla_breaches(
+        &db.pool, digest.guild_id, period.start, period.end, digest.sla_hours,
+    ).await?;
+
+    let mut staff = crate::database::stats::get_staff_stats(&db.pool, digest.guild_id, period.start, period.end).await?;
+    staff.retain(|s| s.closed > 0 || s.claimed > 0);
+    StaffSort::Closed.sort(&mut staff);
+
+    let oldest_str = if oldest.is_empty() {
+        "None".to_string()
+    } else {
+        oldest
+            .iter()
+            .map(|(number, channel_id, created_at)| format!("#{} <#{}> - opened <t:{}:R>", number, channel_id, created_at.timestamp()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let staff_str = if staff.is_empty() {
+        "No data".to_string()
+    } else {
+        staff
+            .iter()
+            .take(3)
+            .map(|s| format!("<@{}> - {} closed, {} claimed", s.user_id, s.closed, s.claimed))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    Ok(CreateEmbed::new()
+        .title(if digest.is_weekly() { "Weekly Ticket Digest" } else { "Daily Ticket Digest" })
+        .description(format!(
+            "**Period:** {}\n<t:{}:f> - <t:{}:f>",
+            period.label,
+            period.start.timestamp(),
+            period.end.timestamp()
+        ))
+        .color(serenity::all::Colour::from_rgb(88, 101, 242))
+        .field("Opened", format_change(stats.opened, prev_opened), true)
+        .field("Closed", format_change(stats.closed, prev_closed), true)
+        .field(
+            "Avg. Resolution Time",
+            stats.avg_resolution_seconds.map(format_duration_seconds).unwrap_or_else(|| "N/A".to_string()),
+            true,
+        )
+        .field("Backlog", format!("{} open, {} unclaimed", open, unclaimed), true)
+        .field(format!("SLA Breaches ({}h first response)", digest.sla_hours), breaches.to_string(), true)
+        .field("Oldest Unclaimed", oldest_str, false)
+        .field("Top Staff", staff_str, false))
+}
@@ -0,0 +1,138 @@
+use crate::database::export::TicketExportRow;
+use anyhow::Result;
+use futures::TryStreamExt;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use super::stats::StatsPeriod;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.to_lowercase().as_str() {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+const CSV_HEADER: &str = "ticket_number,owner_id,category,priority,claimed_by,assigned_to,created_at,closed_at,rating,message_count";
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_row(row: &TicketExportRow) -> String {
+    let opt = |v: Option<String>| v.unwrap_or_default();
+
+    [
+        row.ticket_number.to_string(),
+        row.owner_id.to_string(),
+        csv_field(row.category.as_deref().unwrap_or("")),
+        csv_field(row.priority.as_deref().unwrap_or("")),
+        opt(row.claimed_by.map(|v| v.to_string())),
+        opt(row.assigned_to.map(|v| v.to_string())),
+        row.created_at.to_rfc3339(),
+        opt(row.closed_at.map(|v| v.to_rfc3339())),
+        opt(row.rating.map(|v| v.to_string())),
+        row.message_count.to_string(),
+    ]
+    .join(",")
+}
+
+/// Writes the export row by row to a file under `exports/` and returns its path
+/// and the number of tickets written. The caller is responsible for deleting it.
+pub async fn write_ticket_export(
+    db: &crate::database::Database,
+    guild_id: i64,
+    period: &StatsPeriod,
+    format: ExportFormat,
+) -> Result<(String, u64)> {
+    let dir = Path::new("exports");
+    if !dir.exists() {
+        fs::create_dir_all(dir)?;
+    }
+
+    // The random suffix keeps two exports of the same range from sharing a file.
+    let filename = format!(
+        "tickets-{}-{}-{}-{}.{}",
+        guild_id,
+        period.start.format("%Y%m%d"),
+        period.end.format("%Y%m%d"),
+        &uuid::Uuid::new_v4().simple().to_string()[..8],
+        format.extension()
+    );
+    let filepath = dir.join(&filename);
+
+    match write_rows(db, guild_id, period, format, &filepath).await {
+        Ok(count) => Ok((filepath.to_string_lossy().to_string(), count)),
+        Err(e) => {
+            let _ = fs::remove_file(&filepath);
+            Err(e)
+        }
+    }
+}
+
+async fn write_rows(
+    db: &crate::database::Database,
+    guild_id: i64,
+    period: &StatsPeriod,
+    format: ExportFormat,
+    filepath: &Path,
+) -> Result<u64> {
+    let mut writer = BufWriter::new(File::create(filepath)?);
+
+    let mut rows = crate::database::export::stream_ticket_export(&db.pool, guild_id, period.start, period.end);
+    let mut count: u64 = 0;
+
+    match format {
+        ExportFormat::Csv => writeln!(writer, "{}", CSV_HEADER)?,
+        ExportFormat::Json => write!(writer, "[")?,
+    }
+
+    while let Some(row) = rows.try_next().await? {
+        match format {
+            ExportFormat::Csv => writeln!(writer, "{}", csv_row(&row))?,
+            ExportFormat::Json => {
+                if count > 0 {
+                    write!(writer, ",")?;
+                }
+                write!(writer, "\n  {}", serde_json::to_string(&row)?)?;
+            }
+        }
+        count += 1;
+    }
+
+    if format == ExportFormat::Json {
+        writeln!(writer, "\n]")?;
+    }
+
+    writer.flush()?;
+
+    Ok(count)
+}
+
+pub async fn delete_export(filepath: &str) -> Result<()> {
+    if Path::new(filepath).exists() {
+        fs::remove_file(filepath)?;
+    }
+    Ok(())
+}
@@ -1,4 +1,13 @@
 pub mod transcript;
+pub mod stats;
+pub mod export;
+pub mod digest;
+pub mod chart;
+pub mod tag;
+pub mod autoresponder;
+pub mod reminder;
+pub mod time;
+pub mod panel;
 
 use serenity::all::{Colour, CreateEmbed, Context, ChannelId};
 use anyhow::Result;
@@ -39,6 +48,16 @@ pub async fn send_log(
     Ok(())
 }
 
+/// Drops everything that keeps working on a ticket while it is open: the
+/// priority ping keys, any active escalation and pending follow-ups. Called by
+/// every path that closes a ticket.
+async fn release_ticket_state(db: &crate::database::Database, ticket_id: uuid::Uuid) {
+    let mut redis_conn = db.redis.clone();
+    let _ = crate::database::ticket::cleanup_priority_ping(&mut redis_conn, ticket_id).await;
+    let _ = crate::database::ticket::deactivate_escalation(&db.pool, ticket_id).await;
+    let _ = crate::database::ticket::delete_ticket_reminders(&db.pool, ticket_id).await;
+}
+
 pub async fn close_ticket_unified(
     ctx: &Context,
     ticket: crate::models::Ticket,
@@ -73,7 +92,7 @@ pub async fn close_ticket_unified(
         "SELECT guild_id, ticket_category_id, log_channel_id, transcript_channel_id, prefix,
                 claim_buttons_enabled, auto_close_hours, ticket_limit_per_user, ticket_cooldown_seconds,
                 dm_on_create, embed_color, embed_title, embed_description, embed_footer,
-                channel_name_template, created_at, updated_at
+                channel_name_template, exclusive_claim, created_at, updated_at
          FROM guilds WHERE guild_id = $1"
     )
     .bind(ticket.guild_id)
@@ -114,11 +133,7 @@ pub async fn close_ticket_unified(
 
     let _ = transcript::delete_transcript(&filepath).await;
 
-    crate::database::ticket::delete_ticket_messages(&db.pool, ticket.id).await?;
-
-    let mut redis_conn = db.redis.clone();
-    let _ = crate::database::ticket::cleanup_priority_ping(&mut redis_conn, ticket.id).await;
-    let _ = crate::database::ticket::deactivate_escalation(&db.pool, ticket.id).await;
+    release_ticket_state(db, ticket.id).await;
 
     if let Ok(guild) = crate::database::ticket::get_or_create_guild(&db.pool, ticket.guild_id).await {
         let log_embed = create_embed(
@@ -129,7 +144,9 @@ pub async fn close_ticket_unified(
         let _ = send_log(ctx, guild.log_channel_id, log_embed).await;
     }
 
-    crate::database::ticket::close_ticket(&db.pool, ticket.id).await?;
+    crate::database::ticket::close_ticket(&db.pool, ticket.id, closer_user_id as i64).await?;
+    // Only cleared after close_ticket has counted them into the history row.
+    crate::database::ticket::delete_ticket_messages(&db.pool, ticket.id).await?;
 
     tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
     let channel_id = ChannelId::new(ticket.channel_id as u64);
@@ -163,3 +180,364 @@ pub async fn has_support_role_or_admin(
 
     Ok(has_support_role)
 }
+
+/// Rewrites a ticket channel's staff overwrites to match its claim state.
+///
+/// With exclusive claims enabled, a claimed ticket denies Send Messages to the
+/// support roles and grants send access to the claimer and assignee only. Otherwise
+/// the support roles get Send Messages back. Only that one bit is touched on role
+/// overwrites, so any other per-role customisation is kept. `revoke` lists staff
+/// whose personal overwrite should be dropped (e.g. the previous claimer).
+/// Administrators bypass channel overwrites entirely, so they always keep access.
+pub async fn sync_exclusive_claim(
+    ctx: &Context,
+    db: &crate::database::Database,
+    ticket_id: uuid::Uuid,
+    revoke: &[i64],
+) -> Result<()> {
+    let ticket = match crate::database::ticket::get_ticket_by_id(&db.pool, ticket_id).await? {
+        Some(t) => t,
+        None => return Ok(()),
+    };
+
+    let guild = crate::database::ticket::get_or_create_guild(&db.pool, ticket.guild_id).await?;
+    let exclusive = guild.exclusive_claim.unwrap_or(false);
+
+    if !exclusive && revoke.is_empty() {
+        return Ok(());
+    }
+
+    apply_exclusive_claim(ctx, db, &ticket, exclusive, revoke).await
+}
+
+/// Re-applies claim locks to every open claimed ticket after the exclusive claim
+/// setting changes. Returns how many tickets were updated.
+pub async fn resync_exclusive_claims(
+    ctx: &Context,
+    db: &crate::database::Database,
+    guild_id: i64,
+    exclusive: bool,
+) -> Result<usize> {
+    let tickets = crate::database::ticket::get_claimed_open_tickets(&db.pool, guild_id).await?;
+    let mut updated = 0;
+
+    for ticket in &tickets {
+        // Turning the setting off leaves the claimer's own overwrite in place;
+        // it grants nothing the support roles don't already have.
+        match apply_exclusive_claim(ctx, db, ticket, exclusive, &[]).await {
+            Ok(()) => updated += 1,
+            Err(e) => tracing::warn!("Failed to sync claim lock for ticket {}: {}", ticket.id, e),
+        }
+    }
+
+    Ok(updated)
+}
+
+async fn apply_exclusive_claim(
+    ctx: &Context,
+    db: &crate::database::Database,
+    ticket: &crate::models::Ticket,
+    exclusive: bool,
+    revoke: &[i64],
+) -> Result<()> {
+    use serenity::all::{PermissionOverwrite, PermissionOverwriteType, Permissions, RoleId, UserId};
+
+    let channel = ChannelId::new(ticket.channel_id as u64);
+    let locked = exclusive && ticket.is_claimed();
+    let full_access = Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::READ_MESSAGE_HISTORY;
+
+    let overwrites = channel
+        .to_channel(&ctx.http)
+        .await?
+        .guild()
+        .map(|c| c.permission_overwrites)
+        .unwrap_or_default();
+
+    let current = |kind: PermissionOverwriteType| {
+        overwrites
+            .iter()
+            .find(|o| o.kind == kind)
+            .map(|o| (o.allow, o.deny))
+            .unwrap_or((Permissions::empty(), Permissions::empty()))
+    };
+
+    if locked {
+        let support_roles = crate::database::ticket::get_support_roles(&db.pool, ticket.guild_id).await?;
+        for role in support_roles {
+            let kind = PermissionOverwriteType::Role(RoleId::new(role.role_id as u64));
+            let (allow, deny) = current(kind);
+            // Already denied, either by an earlier claim or by hand; leave it alone.
+            if deny.contains(Permissions::SEND_MESSAGES) {
+                continue;
+            }
+
+            crate::database::ticket::record_claim_lock(&db.pool, ticket.id, role.role_id, allow.contains(Permissions::SEND_MESSAGES)).await?;
+            channel
+                .create_permission(&ctx.http, PermissionOverwrite {
+                    allow: allow - Permissions::SEND_MESSAGES,
+                    deny: deny | Permissions::SEND_MESSAGES,
+                    kind,
+                })
+                .await?;
+        }
+    } else {
+        // Only lift the denies the claim added, putting back an explicit allow if there was one.
+        for (role_id, send_allowed) in crate::database::ticket::take_claim_locks(&db.pool, ticket.id).await? {
+            let kind = PermissionOverwriteType::Role(RoleId::new(role_id as u64));
+            let (allow, deny) = current(kind);
+            if !deny.contains(Permissions::SEND_MESSAGES) {
+                continue;
+            }
+
+            let allow = if send_allowed { allow | Permissions::SEND_MESSAGES } else { allow };
+            channel
+                .create_permission(&ctx.http, PermissionOverwrite { allow, deny: deny - Permissions::SEND_MESSAGES, kind })
+                .await?;
+        }
+    }
+
+    let holders: Vec<i64> = if locked {
+        ticket.claimed_by.into_iter().chain(ticket.assigned_to).collect()
+    } else {
+        Vec::new()
+    };
+
+    for user_id in revoke {
+        if *user_id != ticket.owner_id && !holders.contains(user_id) {
+            let _ = channel
+                .delete_permission(&ctx.http, PermissionOverwriteType::Member(UserId::new(*user_id as u64)))
+                .await;
+        }
+    }
+
+    for user_id in holders {
+        if user_id == ticket.owner_id {
+            continue;
+        }
+
+        channel
+            .create_permission(
+                &ctx.http,
+                PermissionOverwrite {
+                    allow: full_access,
+                    deny: Permissions::empty(),
+                    kind: PermissionOverwriteType::Member(UserId::new(user_id as u64)),
+                },
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub fn format_away_until(away: &crate::models::StaffAway) -> String {
+    match away.away_until {
+        Some(until) => format!(" until <t:{}:f>", until.timestamp()),
+        None => String::new(),
+    }
+}
+
+pub async fn build_staff_availability_embed(
+    ctx: &Context,
+    db: &crate::database::Database,
+    guild_id: i64,
+) -> Result<CreateEmbed> {
+    const MAX_LISTED: usize = 25;
+
+    let support_roles = crate::database::ticket::get_support_roles(&db.pool, guild_id).await?;
+
+    if support_roles.is_empty() {
+        return Ok(create_error_embed("No Support Roles", "No support roles configured for this server."));
+    }
+
+    let away_staff = crate::database::staff::get_away_staff(&db.pool, guild_id).await?;
+    let away_ids: std::collections::HashSet<i64> = away_staff.iter().map(|a| a.user_id).collect();
+
+    let role_ids: Vec<serenity::all::RoleId> = support_roles
+        .iter()
+        .map(|r| serenity::all::RoleId::new(r.role_id as u64))
+        .collect();
+
+    let members = serenity::all::GuildId::new(guild_id as u64)
+        .members(&ctx.http, None, None)
+        .await?;
+
+    let staff: Vec<i64> = members
+        .iter()
+        .filter(|m| !m.user.bot && m.roles.iter().any(|r| role_ids.contains(r)))
+        .map(|m| m.user.id.get() as i64)
+        .collect();
+
+    let available: Vec<String> = staff
+        .iter()
+        .filter(|id| !away_ids.contains(id))
+        .map(|id| format!("<@{}>", id))
+        .collect();
+
+    let away: Vec<String> = away_staff
+        .iter()
+        .filter(|a| staff.contains(&a.user_id))
+        .map(|a| {
+            let reason = a.reason.as_deref().map(|r| format!(" - {}", r)).unwrap_or_default();
+            format!("<@{}> since <t:{}:R>{}{}", a.user_id, a.created_at.timestamp(), format_away_until(a), reason)
+        })
+        .collect();
+
+    let format_list = |entries: &[String], empty: &str| -> String {
+        if entries.is_empty() {
+            return empty.to_string();
+        }
+
+        let mut list = entries.iter().take(MAX_LISTED).cloned().collect::<Vec<_>>().join("\n");
+        if entries.len() > MAX_LISTED {
+            list.push_str(&format!("\n...and {} more", entries.len() - MAX_LISTED));
+        }
+        list
+    };
+
+    Ok(create_embed(
+        "Support Staff",
+        format!(
+            "**Available ({}):**\n{}\n\n**Away ({}):**\n{}",
+            available.len(),
+            format_list(&available, "No staff currently available"),
+            away.len(),
+            format_list(&away, "Nobody is away"),
+        ),
+    ))
+}
+
+pub fn format_minutes(minutes: i64) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
+pub fn format_escalation_tiers(tiers: &[crate::models::EscalationTier]) -> String {
+    if tiers.is_empty() {
+        return "No escalation tiers configured. Escalated tickets DM all support roles every hour.".to_string();
+    }
+
+    tiers
+        .iter()
+        .map(|t| format!(
+            "**Tier {}** - <@&{}> after {} via {}",
+            t.tier,
+            t.role_id,
+            format_minutes(t.delay_minutes as i64),
+            t.describe_delivery()
+        ))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Folds `source` into `target` and closes `source` without a transcript.
+///
+/// The source's logged messages and notes are re-parented onto the target, so the
+/// target's transcript covers both tickets once it is closed.
+pub async fn merge_tickets(
+    ctx: &Context,
+    db: &crate::database::Database,
+    source: crate::models::Ticket,
+    target: crate::models::Ticket,
+    merged_by: u64,
+) -> Result<()> {
+    use serenity::all::{PermissionOverwrite, PermissionOverwriteType, Permissions, UserId};
+
+    let (messages, notes) = crate::database::ticket::merge_ticket_records(&db.pool, source.id, target.id).await?;
+
+    let target_channel = ChannelId::new(target.channel_id as u64);
+    let source_channel = ChannelId::new(source.channel_id as u64);
+
+    if source.owner_id != target.owner_id {
+        let _ = target_channel
+            .create_permission(
+                &ctx.http,
+                PermissionOverwrite {
+                    allow: Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::READ_MESSAGE_HISTORY,
+                    deny: Permissions::empty(),
+                    kind: PermissionOverwriteType::Member(UserId::new(source.owner_id as u64)),
+                },
+            )
+            .await;
+    }
+
+    let summary = format!(
+        "Ticket #{} from <@{}> has been merged into this ticket by <@{}>.\n\n\
+         **Messages moved:** {}\n\
+         **Notes moved:** {}\n\
+         **Originally opened:** <t:{}:F>",
+        source.ticket_number,
+        source.owner_id,
+        merged_by,
+        messages,
+        notes,
+        source.created_at.timestamp()
+    );
+
+    let summary_message = target_channel
+        .send_message(
+            &ctx.http,
+            serenity::all::CreateMessage::new()
+                .content(format!("<@{}>", source.owner_id))
+                .embed(create_embed("Ticket Merged", summary).color(0x5865F2)),
+        )
+        .await?;
+
+    // Bot messages aren't logged by the message handler, so record the merge
+    // explicitly to mark where the source ticket joins the combined transcript.
+    let bot = ctx.cache.current_user().clone();
+    let _ = crate::database::ticket::add_ticket_message(
+        &db.pool,
+        target.id,
+        summary_message.id.get() as i64,
+        bot.id.get() as i64,
+        bot.name.clone(),
+        None,
+        bot.avatar_url(),
+        format!("[Merged ticket #{}] {} messages and {} notes moved into this ticket", source.ticket_number, messages, notes),
+        serde_json::json!([]),
+    )
+    .await;
+
+    let _ = source_channel
+        .send_message(
+            &ctx.http,
+            serenity::all::CreateMessage::new().embed(create_success_embed(
+                "Ticket Merged",
+                format!(
+                    "This ticket has been merged into <#{}>. This channel will be deleted in 5 seconds",
+                    target.channel_id
+                ),
+            )),
+        )
+        .await;
+
+    release_ticket_state(db, source.id).await;
+
+    if let Ok(guild) = crate::database::ticket::get_or_create_guild(&db.pool, source.guild_id).await {
+        let log_embed = create_embed(
+            "Tickets Merged",
+            format!(
+                "Source: #{} (<@{}>)\nTarget: #{} <#{}>\nMerged by: <@{}>\nMessages moved: {}\nNotes moved: {}",
+                source.ticket_number,
+                source.owner_id,
+                target.ticket_number,
+                target.channel_id,
+                merged_by,
+                messages,
+                notes
+            ),
+        );
+        let _ = send_log(ctx, guild.log_channel_id, log_embed).await;
+    }
+
+    crate::database::ticket::close_ticket(&db.pool, source.id, merged_by as i64).await?;
+
+    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+    let _ = source_channel.delete(&ctx.http).await;
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use anyhow::Result;
+use serenity::all::{
+    ButtonStyle, ChannelId, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter,
+    CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, EditMessage, Http, MessageId,
+    ReactionType,
+};
+use tracing::{info, warn};
+
+use crate::database::Database;
+use crate::models::{PanelButton, PostedPanel};
+use crate::utils::create_embed;
+
+// Discord allows five buttons per row and panels only ever use one row.
+const MAX_PANEL_BUTTONS: usize = 5;
+const MAX_PANEL_OPTIONS: usize = 25;
+
+// Discord error codes for a message or channel that no longer exists.
+const UNKNOWN_CHANNEL: isize = 10003;
+const UNKNOWN_MESSAGE: isize = 10008;
+
+/// The style name for a button colour, accepting the colour names `/panel` offers
+/// (`blurple`, `gray`/`grey`, `green`, `red`) as well as the style names.
+pub fn normalize_button_color(color: &str) -> &'static str {
+    match color.to_lowercase().as_str() {
+        "secondary" | "gray" | "grey" => "secondary",
+        "success" | "green" => "success",
+        "danger" | "red" => "danger",
+        _ => "primary",
+    }
+}
+
+fn button_style(button: &PanelButton) -> ButtonStyle {
+    // Panels with per-category colours store them in `button_color`, single-colour
+    // panels in `button_style`; whichever is not the default wins.
+    let color = match button.button_color.as_deref().map(normalize_button_color) {
+        Some(color) if color != "primary" => color,
+        _ => button.button_style.as_deref().map(normalize_button_color).unwrap_or("primary"),
+    };
+    match color {
+        "secondary" => ButtonStyle::Secondary,
+        "success" => ButtonStyle::Success,
+        "danger" => ButtonStyle::Danger,
+        _ => ButtonStyle::Primary,
+    }
+}
+
+/// Builds the buttons or dropdown for a panel. A panel whose categories have all
+/// been deleted falls back to the plain Create Ticket button.
+pub fn render_components(panel: &PostedPanel, buttons: &[PanelButton]) -> Vec<CreateActionRow> {
+    if buttons.is_empty() {
+        let button = CreateButton::new("ticket_create")
+            .label("Create Ticket")
+            .style(ButtonStyle::Primary);
+        return vec![CreateActionRow::Buttons(vec![button])];
+    }
+
+    if panel.selection_type.as_deref() == Some("dropdown") {
+        let options = buttons.iter().take(MAX_PANEL_OPTIONS).map(|b| {
+            let display = match &b.emoji {
+                Some(e) => format!("{} {}", e, b.name),
+                None => b.name.clone(),
+            };
+            CreateSelectMenuOption::new(display, format!("ticket_create_cat_{}", b.category_id))
+        }).collect();
+
+        let select_menu = CreateSelectMenu::new("ticket_category_select", CreateSelectMenuKind::String { options })
+            .placeholder("🎫 Open a ticket - Select a category");
+        return vec![CreateActionRow::SelectMenu(select_menu)];
+    }
+
+    let buttons = buttons.iter().take(MAX_PANEL_BUTTONS).map(|b| {
+        let label: String = b.name.chars().take(80).collect();
+        let mut button = CreateButton::new(format!("ticket_create_cat_{}", b.category_id))
+            .label(label)
+            .style(button_style(b));
+        if let Some(emoji) = b.emoji.as_ref().and_then(|e| e.parse::<ReactionType>().ok()) {
+            button = button.emoji(emoji);
+        }
+        button
+    }).collect();
+
+    vec![CreateActionRow::Buttons(buttons)]
+}
+
+/// The panel embed built from the guild's panel settings, with the panel's own
+/// image, thumbnail and footer overrides on top.
+pub async fn render_guild_embed(db: &Database, panel: &PostedPanel) -> Result<CreateEmbed> {
+    let guild = crate::database::ticket::get_or_create_guild(&db.pool, panel.guild_id).await?;
+
+    let default_description = if panel.tracks_categories {
+        "Select a category to create a ticket"
+    } else {
+        "Click the button below to create a ticket"
+    };
+    let mut embed = create_embed(
+        guild.embed_title.unwrap_or_else(|| "Support Ticket".to_string()),
+        guild.embed_description.unwrap_or_else(|| default_description.to_string()),
+    )
+    .color(guild.embed_color.unwrap_or(5865714));
+
+    if let Some(footer) = panel.embed_footer_text.clone().or(guild.embed_footer) {
+        let mut footer = CreateEmbedFooter::new(footer);
+        if let Some(icon) = &panel.embed_footer_icon_url {
+            footer = footer.icon_url(icon);
+        }
+        embed = embed.footer(footer);
+    }
+    if let Some(url) = &panel.embed_thumbnail_url {
+        embed = embed.thumbnail(url);
+    }
+    if let Some(url) = &panel.embed_image_url {
+        embed = embed.image(url);
+    }
+
+    Ok(embed)
+}
+
+fn is_missing_message(err: &serenity::Error) -> bool {
+    matches!(
+        err,
+        serenity::Error::Http(serenity::all::HttpError::UnsuccessfulRequest(response))
+            if response.error.code == UNKNOWN_MESSAGE || response.error.code == UNKNOWN_CHANNEL
+    )
+}
+
+async fn refresh_panel(http: &Http, db: &Database, panel: &PostedPanel) -> Result<()> {
+    let mut edit = EditMessage::new();
+    if panel.tracks_categories {
+        let buttons = crate::database::ticket::get_panel_buttons(&db.pool, panel.id).await?;
+        edit = edit.components(render_components(panel, &buttons));
+    }
+    if panel.uses_guild_embed {
+        edit = edit.embed(render_guild_embed(db, panel).await?);
+    }
+
+    let channel_id = ChannelId::new(panel.channel_id as u64);
+    match channel_id.edit_message(http, MessageId::new(panel.message_id as u64), edit).await {
+        Ok(_) => Ok(()),
+        Err(e) if is_missing_message(&e) => {
+            crate::database::ticket::flag_missing_panel(&db.pool, panel.message_id).await?;
+            warn!("Panel {} in channel {} is gone, flagged as missing", panel.message_id, panel.channel_id);
+            Ok(())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Re-renders every posted panel in a guild in place after a category or panel
+/// setting changes. Failures are logged rather than returned so the command that
+/// made the change still succeeds.
+pub async fn refresh_guild_panels(http: &Http, db: &Database, guild_id: i64) {
+    let panels = match crate::database::ticket::get_posted_panels(&db.pool, guild_id).await {
+        Ok(panels) => panels,
+        Err(e) => {
+            warn!("Failed to load panels for guild {}: {}", guild_id, e);
+            return;
+        }
+    };
+
+    for panel in &panels {
+        if let Err(e) = refresh_panel(http, db, panel).await {
+            warn!("Failed to refresh panel {} in guild {}: {}", panel.message_id, guild_id, e);
+        }
+    }
+
+    if !panels.is_empty() {
+        info!("Refreshed {} panel(s) in guild {}", panels.len(), guild_id);
+    }
+}
@@ -0,0 +1,647 @@
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+use serenity::all::{ButtonStyle, CreateActionRow, CreateButton};
+use uuid::Uuid;
+
+use crate::models::Reminder;
+use crate::utils::time::{local_to_utc, parse_time_of_day, parse_weekday};
+
+// Reminders are checked every 30 seconds; anything tighter than this would just be spam.
+const MIN_REPEAT_INTERVAL_MINUTES: i64 = 5;
+
+/// How often a reminder repeats. Stored in `reminders.recurrence` as a short spec
+/// such as `interval:7200`, `daily:09:00@Europe/London` or `weekly:mon:09:00@UTC`.
+/// Specs without a zone are read as UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recurrence {
+    Interval(Duration),
+    Daily(NaiveTime, Tz),
+    Weekly(Weekday, NaiveTime, Tz),
+}
+
+impl Recurrence {
+    pub fn to_spec(self) -> String {
+        match self {
+            Recurrence::Interval(step) => format!("interval:{}", step.num_seconds()),
+            Recurrence::Daily(at, tz) => format!("daily:{}@{}", at.format("%H:%M"), tz.name()),
+            Recurrence::Weekly(day, at, tz) => format!("weekly:{}:{}@{}", weekday_key(day), at.format("%H:%M"), tz.name()),
+        }
+    }
+
+    pub fn from_spec(spec: &str) -> Option<Self> {
+        let (spec, tz) = match spec.split_once('@') {
+            Some((spec, tz)) => (spec, tz.parse().ok()?),
+            None => (spec, Tz::UTC),
+        };
+        let (kind, rest) = spec.split_once(':')?;
+        match kind {
+            "interval" => rest.parse().ok().map(|secs| Recurrence::Interval(Duration::seconds(secs))),
+            "daily" => NaiveTime::parse_from_str(rest, "%H:%M").ok().map(|at| Recurrence::Daily(at, tz)),
+            "weekly" => {
+                let (day, at) = rest.split_once(':')?;
+                let day = day.parse().ok()?;
+                NaiveTime::parse_from_str(at, "%H:%M").ok().map(|at| Recurrence::Weekly(day, at, tz))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn describe(self) -> String {
+        match self {
+            Recurrence::Interval(step) => format!("every {}", format_interval(step)),
+            Recurrence::Daily(at, tz) => format!("every day at {} {}", at.format("%H:%M"), tz.name()),
+            Recurrence::Weekly(day, at, tz) => format!("every {} at {} {}", weekday_name(day), at.format("%H:%M"), tz.name()),
+        }
+    }
+
+    /// When the first reminder goes off if the schedule is set up at `now`.
+    pub fn first(self, now: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Recurrence::Interval(step) => now + step,
+            Recurrence::Daily(at, tz) => next_time_of_day(now, at, None, tz),
+            Recurrence::Weekly(day, at, tz) => next_time_of_day(now, at, Some(day), tz),
+        }
+    }
+
+    /// The next occurrence after `previous` that is still in the future. Occurrences
+    /// missed while the bot was offline are skipped rather than delivered in a burst.
+    pub fn next_after(self, previous: DateTime<Utc>, now: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Recurrence::Interval(step) => {
+                let missed = (now - previous).num_seconds().max(0) / step.num_seconds().max(1);
+                previous + Duration::seconds(step.num_seconds() * (missed + 1))
+            }
+            Recurrence::Daily(at, tz) => next_time_of_day(now.max(previous), at, None, tz),
+            Recurrence::Weekly(day, at, tz) => next_time_of_day(now.max(previous), at, Some(day), tz),
+        }
+    }
+}
+
+fn next_time_of_day(after: DateTime<Utc>, at: NaiveTime, day: Option<Weekday>, tz: Tz) -> DateTime<Utc> {
+    let mut date = after.with_timezone(&tz).date_naive();
+    loop {
+        // On the night the clocks go forward the time may not exist; go off an hour later instead.
+        let candidate = local_to_utc(tz, date, at).or_else(|| local_to_utc(tz, date, at + Duration::hours(1)));
+        if let Some(candidate) = candidate.filter(|c| *c > after && day.is_none_or(|d| date.weekday() == d)) {
+            return candidate;
+        }
+        date = date.succ_opt().unwrap_or(date);
+    }
+}
+
+fn weekday_key(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "mon",
+        Weekday::Tue => "tue",
+        Weekday::Wed => "wed",
+        Weekday::Thu => "thu",
+        Weekday::Fri => "fri",
+        Weekday::Sat => "sat",
+        Weekday::Sun => "sun",
+    }
+}
+
+fn weekday_name(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+fn format_interval(step: Duration) -> String {
+    let secs = step.num_seconds();
+    match secs {
+        s if s % 604_800 == 0 => format!("{}w", s / 604_800),
+        s if s % 86_400 == 0 => format!("{}d", s / 86_400),
+        s if s % 3600 == 0 => format!("{}h", s / 3600),
+        s if s % 60 == 0 => format!("{}m", s / 60),
+        s => format!("{}s", s),
+    }
+}
+
+pub struct RecurringReminder {
+    pub recurrence: Recurrence,
+    pub repeat_until: Option<DateTime<Utc>>,
+    pub repeat_limit: Option<i32>,
+    pub reason: String,
+}
+
+/// Parses what follows `reminder every`: a schedule (`2h`, `hour`, `week`, `day [at] 09:00`,
+/// `monday [at] 9am`), optional `--until YYYY-MM-DD` and `--count N`, then the reason.
+/// Times of day and the end date are read in `tz`.
+pub fn parse_recurring(args: &[&str], tz: Tz) -> Result<RecurringReminder> {
+    let mut rest = args.iter().copied().peekable();
+    let schedule = rest.next().ok_or_else(|| anyhow::anyhow!("Say how often to repeat, e.g. `every 2h` or `every day 09:00`"))?.to_lowercase();
+
+    let day = match schedule.as_str() {
+        "hour" | "hourly" => return finish_recurring(Recurrence::Interval(Duration::hours(1)), rest.collect(), tz),
+        "week" | "weekly" => return finish_recurring(Recurrence::Interval(Duration::weeks(1)), rest.collect(), tz),
+        "day" | "daily" => None,
+        name => match parse_weekday(name) {
+            Some(day) => Some(day),
+            None => {
+                let step = crate::utils::time::parse_duration(name)?;
+                if step < Duration::minutes(MIN_REPEAT_INTERVAL_MINUTES) {
+                    anyhow::bail!("Reminders can repeat at most every {} minutes", MIN_REPEAT_INTERVAL_MINUTES);
+                }
+                return finish_recurring(Recurrence::Interval(step), rest.collect(), tz);
+            }
+        },
+    };
+
+    if rest.peek().is_some_and(|word| word.eq_ignore_ascii_case("at")) {
+        rest.next();
+    }
+    let at = match rest.peek().and_then(|word| parse_time_of_day(word)) {
+        Some(at) => {
+            rest.next();
+            at
+        }
+        None => NaiveTime::from_hms_opt(9, 0, 0).unwrap_or_default(),
+    };
+
+    let recurrence = match day {
+        Some(day) => Recurrence::Weekly(day, at, tz),
+        None => Recurrence::Daily(at, tz),
+    };
+    finish_recurring(recurrence, rest.collect(), tz)
+}
+
+fn finish_recurring(recurrence: Recurrence, args: Vec<&str>, tz: Tz) -> Result<RecurringReminder> {
+    let mut repeat_until = None;
+    let mut repeat_limit = None;
+    let mut reason = Vec::new();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg {
+            "--until" => {
+                let date = args.next().ok_or_else(|| anyhow::anyhow!("`--until` needs a date like 2026-12-31"))?;
+                let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                    .map_err(|_| anyhow::anyhow!("`{}` is not a date like 2026-12-31", date))?;
+                // The end date itself still gets its reminders.
+                let next_day = date.succ_opt().ok_or_else(|| anyhow::anyhow!("`{}` is too far in the future", date))?;
+                repeat_until = Some(start_of_day(tz, next_day)
+                    .ok_or_else(|| anyhow::anyhow!("Couldn't work out when {} starts in {}", next_day, tz.name()))?);
+            }
+            "--count" => {
+                let count = args.next().and_then(|c| c.parse::<i32>().ok()).filter(|c| *c > 0)
+                    .ok_or_else(|| anyhow::anyhow!("`--count` needs a positive number"))?;
+                repeat_limit = Some(count);
+            }
+            word => reason.push(word),
+        }
+    }
+
+    if reason.is_empty() {
+        anyhow::bail!("Add what the reminder is for after the schedule");
+    }
+    if repeat_until.is_some_and(|until| until <= recurrence.first(Utc::now())) {
+        anyhow::bail!("The end date is before the first reminder");
+    }
+
+    Ok(RecurringReminder { recurrence, repeat_until, repeat_limit, reason: reason.join(" ") })
+}
+
+/// The first moment of `date` in `tz`. Where the clocks go forward at midnight
+/// that is the first local time that exists, e.g. 01:00.
+fn start_of_day(tz: Tz, date: NaiveDate) -> Option<DateTime<Utc>> {
+    (0..24 * 4).find_map(|quarter| local_to_utc(tz, date, NaiveTime::MIN + Duration::minutes(15 * quarter)))
+}
+
+/// When a reminder that has just gone off should go off again, or `None` if it
+/// doesn't repeat or has reached its end date or count.
+pub fn next_occurrence(reminder: &Reminder, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let recurrence = Recurrence::from_spec(reminder.recurrence.as_deref()?)?;
+
+    if reminder.repeat_limit.is_some_and(|limit| reminder.occurrences + 1 >= limit) {
+        return None;
+    }
+
+    let next = recurrence.next_after(reminder.remind_at, now);
+    if reminder.repeat_until.is_some_and(|until| next >= until) {
+        return None;
+    }
+
+    Some(next)
+}
+
+/// "Repeats every day at 09:00 UTC, 3 times in total" style summary for embeds.
+pub fn describe_repeat(recurrence: Recurrence, repeat_until: Option<DateTime<Utc>>, repeat_limit: Option<i32>) -> String {
+    let mut text = format!("Repeats {}", recurrence.describe());
+    if let Some(until) = repeat_until {
+        text.push_str(&format!(" until <t:{}:D>", (until - Duration::days(1)).timestamp()));
+    }
+    if let Some(limit) = repeat_limit {
+        text.push_str(&format!(", {} time{} in total", limit, if limit == 1 { "" } else { "s" }));
+    }
+    text
+}
+
+pub const SNOOZE_OPTIONS: [(&str, i64); 2] = [("10m", 10), ("1h", 60)];
+// Leaves room under Discord's 4096-character embed description.
+const LIST_CHAR_BUDGET: usize = 3900;
+
+/// The first block of a reminder's UUID, which is what users type to pick one.
+pub fn short_id(id: Uuid) -> String {
+    id.to_string()[..8].to_string()
+}
+
+/// Finds one of the user's pending reminders by its full or shortened ID.
+pub fn find_reminder<'a>(reminders: &'a [Reminder], key: &str) -> Result<&'a Reminder> {
+    let key = key.trim().to_lowercase();
+    let mut matches = reminders.iter().filter(|r| key.len() >= 4 && r.id.to_string().starts_with(&key));
+
+    match (matches.next(), matches.next()) {
+        (Some(reminder), None) => Ok(reminder),
+        (Some(_), Some(_)) => anyhow::bail!("More than one reminder starts with `{}`, use more of the ID", key),
+        (None, _) => anyhow::bail!("You don't have a pending reminder with ID `{}`. List yours to see their IDs", key),
+    }
+}
+
+/// One line per pending reminder with its ID, time and schedule.
+pub fn format_reminder_list(reminders: &[Reminder]) -> String {
+    if reminders.is_empty() {
+        return "You have no pending reminders.".to_string();
+    }
+
+    let mut list = String::new();
+    for (i, r) in reminders.iter().enumerate() {
+        let reason = if r.reason.chars().count() > 80 {
+            format!("{}...", r.reason.chars().take(77).collect::<String>())
+        } else {
+            r.reason.clone()
+        };
+        let repeat = r.recurrence.as_deref()
+            .and_then(Recurrence::from_spec)
+            .map(|rec| format!("\n  {}", describe_repeat(rec, r.repeat_until, r.repeat_limit)))
+            .unwrap_or_default();
+        let followup = match r.ticket_id {
+            Some(_) if r.cancel_on_reply => format!(" (follow-up in <#{}>, cancels on reply)", r.channel_id),
+            Some(_) => format!(" (follow-up in <#{}>)", r.channel_id),
+            None => String::new(),
+        };
+        let line = format!("`{}` <t:{}:R> - {}{}{}{}", short_id(r.id), r.remind_at.timestamp(), reason, followup, repeat, delivery_note(r));
+        if list.len() + line.len() + 1 > LIST_CHAR_BUDGET {
+            list.push_str(&format!("\n...and {} more", reminders.len() - i));
+            break;
+        }
+        if !list.is_empty() {
+            list.push('\n');
+        }
+        list.push_str(&line);
+    }
+    list
+}
+
+/// How the last delivery of a still-pending reminder went, if it has been tried.
+fn delivery_note(reminder: &Reminder) -> String {
+    let error = short_error(reminder.last_error.as_deref());
+    match (reminder.delivery_status.as_deref(), reminder.last_attempt_at) {
+        (Some("retrying"), _) => format!(
+            "\n  Delivery failed ({} of {} attempts), retrying: {}",
+            reminder.attempts, MAX_DELIVERY_ATTEMPTS, error
+        ),
+        (Some("failed"), Some(at)) => format!("\n  Last delivery <t:{}:R> failed: {}", at.timestamp(), error),
+        (Some("delivered"), Some(at)) => format!("\n  Last delivered <t:{}:R>", at.timestamp()),
+        _ => String::new(),
+    }
+}
+
+/// Outcomes of the user's recently finished reminders, or `None` if there are none.
+pub fn format_recent_deliveries(reminders: &[Reminder]) -> Option<String> {
+    let lines: Vec<String> = reminders.iter().filter_map(|r| {
+        let at = r.last_attempt_at?;
+        let outcome = match r.delivery_status.as_deref() {
+            Some("failed") => format!("**failed**: {}", short_error(r.last_error.as_deref())),
+            Some("skipped") => "skipped, ticket closed".to_string(),
+            _ => "delivered".to_string(),
+        };
+        let reason: String = r.reason.chars().take(50).collect();
+        Some(format!("`{}` <t:{}:R> - {} ({})", short_id(r.id), at.timestamp(), reason, outcome))
+    }).collect();
+
+    if lines.is_empty() { None } else { Some(lines.join("\n")) }
+}
+
+fn short_error(error: Option<&str>) -> String {
+    let error = error.unwrap_or("unknown error");
+    if error.chars().count() > 100 {
+        format!("{}...", error.chars().take(97).collect::<String>())
+    } else {
+        error.to_string()
+    }
+}
+
+/// Snooze and Done buttons attached to a delivered reminder DM.
+pub fn delivery_buttons(reminder_id: Uuid) -> CreateActionRow {
+    let mut buttons: Vec<CreateButton> = SNOOZE_OPTIONS.iter()
+        .map(|(label, minutes)| {
+            CreateButton::new(format!("reminder_snooze:{}:{}", reminder_id, minutes))
+                .label(format!("Snooze {}", label))
+                .style(ButtonStyle::Secondary)
+        })
+        .collect();
+    buttons.push(CreateButton::new(format!("reminder_done:{}", reminder_id)).label("Done").style(ButtonStyle::Success));
+    CreateActionRow::Buttons(buttons)
+}
+
+/// Where a pending reminder moves to when snoozed: `by` after its current time,
+/// or after now if it is already overdue.
+pub fn snoozed_until(reminder: &Reminder, by: Duration, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    reminder.remind_at.max(now)
+        .checked_add_signed(by)
+        .ok_or_else(|| anyhow::anyhow!("Duration too large"))
+}
+
+/// How long an instance holds a claimed reminder before another may pick it up.
+/// The lease is renewed right before each delivery, so it only has to cover one.
+pub const CLAIM_LEASE_SECONDS: i64 = 120;
+pub const CLAIM_BATCH_SIZE: i64 = 20;
+/// Deliveries tried before a reminder is recorded as failed and moved on.
+pub const MAX_DELIVERY_ATTEMPTS: i32 = 5;
+const RETRY_DELAYS_MINUTES: [i64; 4] = [1, 5, 15, 60];
+
+/// Backoff before the next try after `attempts` earlier failures.
+pub fn retry_delay(attempts: i32) -> Duration {
+    let index = (attempts.max(0) as usize).min(RETRY_DELAYS_MINUTES.len() - 1);
+    Duration::minutes(RETRY_DELAYS_MINUTES[index])
+}
+
+pub const DEFAULT_FOLLOWUP_MESSAGE: &str = "Just checking in on this ticket. Is there anything else you need help with?";
+
+/// Who a ticket follow-up pings when it goes off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FollowUpMention {
+    Owner,
+    Claimer,
+    Both,
+}
+
+impl FollowUpMention {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.to_lowercase().as_str() {
+            "owner" | "user" => Some(FollowUpMention::Owner),
+            "claimer" | "staff" => Some(FollowUpMention::Claimer),
+            "both" | "all" => Some(FollowUpMention::Both),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FollowUpMention::Owner => "owner",
+            FollowUpMention::Claimer => "claimer",
+            FollowUpMention::Both => "both",
+        }
+    }
+}
+
+pub struct FollowUp {
+    pub remind_at: DateTime<Utc>,
+    pub mention: FollowUpMention,
+    pub cancel_on_reply: bool,
+    pub message: String,
+}
+
+/// Parses `<when> [--mention owner|claimer|both] [--always] [message]`. Follow-ups
+/// ping the owner and are dropped if the owner replies first unless `--always` is given.
+pub fn parse_followup(args: &[&str], now: DateTime<Utc>, tz: Tz) -> Result<FollowUp> {
+    let (remind_at, used) = crate::utils::time::parse_when(args, now, tz)?;
+    let mut followup = FollowUp {
+        remind_at,
+        mention: FollowUpMention::Owner,
+        cancel_on_reply: true,
+        message: String::new(),
+    };
+
+    let mut message = Vec::new();
+    let mut rest = args[used..].iter().copied();
+    while let Some(arg) = rest.next() {
+        match arg {
+            "--mention" => {
+                followup.mention = rest.next().and_then(FollowUpMention::parse)
+                    .ok_or_else(|| anyhow::anyhow!("`--mention` needs `owner`, `claimer` or `both`"))?;
+            }
+            "--always" => followup.cancel_on_reply = false,
+            word => message.push(word),
+        }
+    }
+
+    followup.message = if message.is_empty() { DEFAULT_FOLLOWUP_MESSAGE.to_string() } else { message.join(" ") };
+    Ok(followup)
+}
+
+/// Posts a ticket follow-up in its ticket channel and returns the delivery status.
+/// Follow-ups for tickets that have since closed are skipped.
+pub async fn deliver_followup(http: &serenity::all::Http, db: &crate::database::Database, reminder: &Reminder, ticket_id: Uuid) -> Result<&'static str> {
+    let Some(ticket) = crate::database::ticket::get_ticket_by_id(&db.pool, ticket_id).await? else {
+        return Ok("skipped");
+    };
+    if ticket.status != "open" {
+        return Ok("skipped");
+    }
+
+    let mention = reminder.mention.as_deref().and_then(FollowUpMention::parse).unwrap_or(FollowUpMention::Owner);
+    // Unclaimed tickets fall back to whoever set the follow-up.
+    let claimer = ticket.claimed_by.unwrap_or(reminder.user_id);
+    let mentions = match mention {
+        FollowUpMention::Owner => format!("<@{}>", ticket.owner_id),
+        FollowUpMention::Claimer => format!("<@{}>", claimer),
+        FollowUpMention::Both => format!("<@{}> <@{}>", ticket.owner_id, claimer),
+    };
+
+    let embed = crate::utils::create_embed(
+        "Follow-up",
+        format!("{}\n\n*Set by <@{}> <t:{}:R>*", reminder.reason, reminder.user_id, reminder.created_at.timestamp()),
+    )
+    .color(0x5865F2);
+
+    serenity::all::ChannelId::new(ticket.channel_id as u64)
+        .send_message(http, serenity::all::CreateMessage::new().content(mentions).embed(embed))
+        .await?;
+    Ok("delivered")
+}
+
+/// Saves a follow-up on `ticket` and returns the confirmation embed.
+pub async fn create_followup(db: &crate::database::Database, ticket: &crate::models::Ticket, set_by: i64, followup: &FollowUp) -> Result<serenity::all::CreateEmbed> {
+    let reminder = crate::database::ticket::create_scheduled_reminder(&db.pool, &crate::models::NewReminder {
+        user_id: set_by,
+        channel_id: ticket.channel_id,
+        guild_id: Some(ticket.guild_id),
+        message_id: None,
+        reason: followup.message.clone(),
+        remind_at: followup.remind_at,
+        recurrence: None,
+        repeat_until: None,
+        repeat_limit: None,
+        ticket_id: Some(ticket.id),
+        mention: Some(followup.mention.as_str().to_string()),
+        cancel_on_reply: followup.cancel_on_reply,
+    })
+    .await?;
+
+    let at = followup.remind_at.timestamp();
+    Ok(crate::utils::create_success_embed(
+        "Follow-up Set",
+        format!(
+            "**Message:** {}\n**When:** <t:{}:F> (<t:{}:R>)\n**Mentions:** {}\n{}",
+            followup.message,
+            at,
+            at,
+            followup.mention.as_str(),
+            if followup.cancel_on_reply { "Cancelled if the owner replies first" } else { "Sent even if the owner replies" }
+        ),
+    )
+    .footer(serenity::all::CreateEmbedFooter::new(format!("Reminder ID: {}", short_id(reminder.id)))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn reminder(recurrence: Recurrence, remind_at: DateTime<Utc>) -> Reminder {
+        Reminder {
+            id: Uuid::nil(),
+            user_id: 1,
+            channel_id: 1,
+            guild_id: None,
+            message_id: None,
+            reason: "test".to_string(),
+            remind_at,
+            created_at: remind_at,
+            completed: false,
+            recurrence: Some(recurrence.to_spec()),
+            repeat_until: None,
+            repeat_limit: None,
+            occurrences: 0,
+            ticket_id: None,
+            mention: None,
+            cancel_on_reply: false,
+            attempts: 0,
+            delivery_status: None,
+            last_error: None,
+            last_attempt_at: None,
+        }
+    }
+
+    #[test]
+    fn parse_recurring_schedules() {
+        let parsed = parse_recurring(&["day", "at", "9:30", "stand-up"], Tz::UTC).unwrap();
+        assert_eq!(parsed.recurrence, Recurrence::Daily(time(9, 30), Tz::UTC));
+        assert_eq!(parsed.reason, "stand-up");
+
+        let parsed = parse_recurring(&["friday", "5pm", "timesheets"], Tz::UTC).unwrap();
+        assert_eq!(parsed.recurrence, Recurrence::Weekly(Weekday::Fri, time(17, 0), Tz::UTC));
+
+        let parsed = parse_recurring(&["2h", "stretch"], Tz::UTC).unwrap();
+        assert_eq!(parsed.recurrence, Recurrence::Interval(Duration::hours(2)));
+
+        assert!(parse_recurring(&["1m", "too", "often"], Tz::UTC).is_err());
+        assert!(parse_recurring(&["day"], Tz::UTC).is_err());
+    }
+
+    #[test]
+    fn parse_recurring_count() {
+        let parsed = parse_recurring(&["day", "--count", "3", "water", "plants"], Tz::UTC).unwrap();
+        assert_eq!(parsed.repeat_limit, Some(3));
+        assert_eq!(parsed.reason, "water plants");
+
+        assert!(parse_recurring(&["day", "--count", "0", "water"], Tz::UTC).is_err());
+        assert!(parse_recurring(&["day", "--count", "water"], Tz::UTC).is_err());
+    }
+
+    #[test]
+    fn parse_recurring_until_includes_the_end_date() {
+        let parsed = parse_recurring(&["day", "--until", "2099-01-31", "water"], Tz::UTC).unwrap();
+        assert_eq!(parsed.repeat_until, Some(utc(2099, 2, 1, 0, 0)));
+
+        let london: Tz = "Europe/London".parse().unwrap();
+        let parsed = parse_recurring(&["day", "--until", "2099-07-31", "water"], london).unwrap();
+        assert_eq!(parsed.repeat_until, Some(utc(2099, 7, 31, 23, 0)));
+
+        assert!(parse_recurring(&["day", "--until", "2000-01-01", "water"], Tz::UTC).is_err());
+        assert!(parse_recurring(&["day", "--until", "soon", "water"], Tz::UTC).is_err());
+    }
+
+    #[test]
+    fn parse_recurring_until_when_midnight_is_skipped() {
+        // Havana moves its clocks from 00:00 to 01:00 on the second Sunday in March.
+        let havana: Tz = "America/Havana".parse().unwrap();
+        assert_eq!(local_to_utc(havana, NaiveDate::from_ymd_opt(2099, 3, 8).unwrap(), NaiveTime::MIN), None);
+        let parsed = parse_recurring(&["day", "--until", "2099-03-07", "water"], havana).unwrap();
+        assert_eq!(parsed.repeat_until, Some(utc(2099, 3, 8, 5, 0)));
+    }
+
+    #[test]
+    fn next_after_interval_skips_missed_occurrences() {
+        let step = Recurrence::Interval(Duration::hours(2));
+        let previous = utc(2026, 10, 14, 12, 0);
+        assert_eq!(step.next_after(previous, previous), utc(2026, 10, 14, 14, 0));
+        assert_eq!(step.next_after(previous, utc(2026, 10, 14, 17, 1)), utc(2026, 10, 14, 18, 0));
+    }
+
+    #[test]
+    fn next_after_keeps_local_time_across_dst() {
+        let london: Tz = "Europe/London".parse().unwrap();
+        let daily = Recurrence::Daily(time(9, 0), london);
+        // 09:00 GMT the day before the clocks go forward, then 09:00 BST.
+        let previous = utc(2026, 3, 28, 9, 0);
+        assert_eq!(daily.next_after(previous, previous), utc(2026, 3, 29, 8, 0));
+        // And back again in October.
+        let previous = utc(2026, 10, 24, 8, 0);
+        assert_eq!(daily.next_after(previous, previous), utc(2026, 10, 25, 9, 0));
+    }
+
+    #[test]
+    fn next_after_moves_a_skipped_time_an_hour_later() {
+        let london: Tz = "Europe/London".parse().unwrap();
+        // 01:30 doesn't exist in London on 29 March 2026.
+        let daily = Recurrence::Daily(time(1, 30), london);
+        let previous = utc(2026, 3, 28, 1, 30);
+        assert_eq!(daily.next_after(previous, previous), utc(2026, 3, 29, 1, 30));
+    }
+
+    #[test]
+    fn next_after_weekly() {
+        let weekly = Recurrence::Weekly(Weekday::Mon, time(9, 0), Tz::UTC);
+        // 14 October 2026 is a Wednesday.
+        assert_eq!(weekly.next_after(utc(2026, 10, 12, 9, 0), utc(2026, 10, 14, 12, 0)), utc(2026, 10, 19, 9, 0));
+    }
+
+    #[test]
+    fn next_occurrence_stops_at_count_and_until() {
+        let daily = Recurrence::Daily(time(9, 0), Tz::UTC);
+        let at = utc(2026, 10, 14, 9, 0);
+
+        let mut r = reminder(daily, at);
+        assert_eq!(next_occurrence(&r, at), Some(utc(2026, 10, 15, 9, 0)));
+
+        r.repeat_limit = Some(3);
+        r.occurrences = 1;
+        assert_eq!(next_occurrence(&r, at), Some(utc(2026, 10, 15, 9, 0)));
+        r.occurrences = 2;
+        assert_eq!(next_occurrence(&r, at), None);
+
+        let mut r = reminder(daily, at);
+        r.repeat_until = Some(utc(2026, 10, 16, 0, 0));
+        assert_eq!(next_occurrence(&r, at), Some(utc(2026, 10, 15, 9, 0)));
+        r.remind_at = utc(2026, 10, 15, 9, 0);
+        assert_eq!(next_occurrence(&r, r.remind_at), None);
+    }
+}
@@ -0,0 +1,534 @@
+use crate::database::stats::{DailyCount, PeriodStats, StaffStats};
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serenity::all::{CreateAttachment, CreateEmbed};
+
+const MAX_CUSTOM_DAYS: i64 = 366;
+
+#[derive(Debug, Clone)]
+pub struct StatsPeriod {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub label: String,
+}
+
+impl StatsPeriod {
+    pub fn last(duration: Duration, label: impl Into<String>) -> Self {
+        let end = Utc::now();
+        Self { start: end - duration, end, label: label.into() }
+    }
+
+    /// The window of the same length immediately before this one.
+    pub fn previous(&self) -> Self {
+        let length = self.end - self.start;
+        Self {
+            start: self.start - length,
+            end: self.start,
+            label: "previous period".to_string(),
+        }
+    }
+}
+
+/// Parses `24h`, `7d`, `30d`, any other duration, or `custom` with a
+/// `YYYY-MM-DD` start and optional inclusive end date.
+pub fn parse_stats_period(period: &str, from: Option<&str>, to: Option<&str>) -> Result<StatsPeriod> {
+    match period.to_lowercase().as_str() {
+        "24h" | "day" => Ok(StatsPeriod::last(Duration::hours(24), "Last 24 hours")),
+        "7d" | "week" => Ok(StatsPeriod::last(Duration::days(7), "Last 7 days")),
+        "30d" | "month" => Ok(StatsPeriod::last(Duration::days(30), "Last 30 days")),
+        "custom" => {
+            let from = from.ok_or_else(|| anyhow::anyhow!("Custom periods need a start date (YYYY-MM-DD)"))?;
+            parse_date_range(from, to)
+        }
+        other => {
+            if other.parse::<NaiveDate>().is_ok() {
+                return parse_date_range(other, to);
+            }
+
+            let duration = crate::utils::time::parse_duration(other)?;
+            if duration.num_days() > MAX_CUSTOM_DAYS {
+                return Err(anyhow::anyhow!("Periods can be at most {} days long", MAX_CUSTOM_DAYS));
+            }
+            Ok(StatsPeriod::last(duration, format!("Last {}", other)))
+        }
+    }
+}
+
+fn parse_date_range(from: &str, to: Option<&str>) -> Result<StatsPeriod> {
+    let from_date: NaiveDate = from.parse()
+        .map_err(|_| anyhow::anyhow!("Invalid start date `{}`. Use YYYY-MM-DD", from))?;
+    let to_date: NaiveDate = match to {
+        Some(to) => to.parse().map_err(|_| anyhow::anyhow!("Invalid end date `{}`. Use YYYY-MM-DD", to))?,
+        None => Utc::now().date_naive(),
+    };
+
+    if to_date < from_date {
+        return Err(anyhow::anyhow!("The end date must not be before the start date"));
+    }
+
+    if (to_date - from_date).num_days() >= MAX_CUSTOM_DAYS {
+        return Err(anyhow::anyhow!("Periods can be at most {} days long", MAX_CUSTOM_DAYS));
+    }
+
+    Ok(StatsPeriod {
+        start: from_date.and_hms_opt(0, 0, 0).unwrap().and_utc(),
+        end: (to_date + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap().and_utc(),
+        label: format!("{} to {}", from_date, to_date),
+    })
+}
+
+pub fn format_duration_seconds(seconds: f64) -> String {
+    let hours = (seconds / 3600.0).floor();
+    let minutes = ((seconds % 3600.0) / 60.0).floor();
+    format!("{}h {}m", hours, minutes)
+}
+
+pub fn format_change(current: i64, previous: i64) -> String {
+    let delta = current - previous;
+    let sign = if delta >= 0 { "+" } else { "" };
+
+    if previous == 0 {
+        format!("{} ({}{} vs previous)", current, sign, delta)
+    } else {
+        let percent = delta as f64 / previous as f64 * 100.0;
+        format!("{} ({}{}, {}{:.0}% vs previous)", current, sign, delta, sign, percent)
+    }
+}
+
+fn format_breakdown(daily: &[DailyCount]) -> String {
+    // Long custom ranges are summarised per week to stay within embed limits.
+    let rows: Vec<(String, i64, i64)> = if daily.len() > 31 {
+        daily
+            .chunks(7)
+            .map(|week| (
+                format!("Week of {}", week[0].day.format("%b %d")),
+                week.iter().map(|d| d.opened).sum(),
+                week.iter().map(|d| d.closed).sum(),
+            ))
+            .collect()
+    } else {
+        daily
+            .iter()
+            .map(|d| (d.day.format("%b %d").to_string(), d.opened, d.closed))
+            .collect()
+    };
+
+    if rows.is_empty() {
+        return "No data".to_string();
+    }
+
+    truncate_field(
+        rows.iter()
+            .map(|(label, opened, closed)| format!("`{}` {} opened, {} closed", label, opened, closed))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+fn format_counts(rows: &[(String, i64)]) -> String {
+    if rows.is_empty() {
+        return "No data".to_string();
+    }
+
+    truncate_field(
+        rows.iter()
+            .map(|(name, count)| format!("{} - {}", name, count))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+fn truncate_field(mut value: String) -> String {
+    if value.len() > 1024 {
+        // Cut on a char boundary; names and emoji make byte 1000 unsafe to slice at.
+        let limit = value.char_indices().map(|(i, _)| i).take_while(|i| *i <= 1000).last().unwrap_or(0);
+        let cut = value[..limit].rfind('\n').unwrap_or(limit);
+        value.truncate(cut);
+        value.push_str("\n...");
+    }
+    value
+}
+
+pub fn build_period_stats_embed(period: &StatsPeriod, stats: &PeriodStats, previous: (i64, i64)) -> CreateEmbed {
+    let busiest = if stats.busiest_hours.is_empty() {
+        "No data".to_string()
+    } else {
+        stats.busiest_hours
+            .iter()
+            .map(|(hour, count)| format!("{:02}:00-{:02}:00 - {} tickets", hour, (hour + 1) % 24, count))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    CreateEmbed::new()
+        .title("Ticket Statistics")
+        .description(format!(
+            "**Period:** {}\n<t:{}:f> - <t:{}:f>",
+            period.label,
+            period.start.timestamp(),
+            period.end.timestamp()
+        ))
+        .color(serenity::all::Colour::from_rgb(88, 101, 242))
+        .field("Opened", format_change(stats.opened, previous.0), true)
+        .field("Closed", format_change(stats.closed, previous.1), true)
+        .field(
+            "Avg. Resolution Time",
+            stats.avg_resolution_seconds.map(format_duration_seconds).unwrap_or_else(|| "N/A".to_string()),
+            true,
+        )
+        .field("Daily Breakdown", format_breakdown(&stats.daily), false)
+        .field("By Category", format_counts(&stats.by_category), true)
+        .field("By Priority", format_counts(&stats.by_priority), true)
+        .field("Busiest Hours (UTC)", busiest, false)
+}
+
+pub async fn period_stats_embed(
+    db: &crate::database::Database,
+    guild_id: i64,
+    period: &StatsPeriod,
+) -> Result<CreateEmbed> {
+    let stats = crate::database::stats::get_period_stats(&db.pool, guild_id, period.start, period.end).await?;
+    let previous = period.previous();
+    let previous_counts = crate::database::stats::count_opened_closed(&db.pool, guild_id, previous.start, previous.end).await?;
+
+    Ok(build_period_stats_embed(period, &stats, previous_counts))
+}
+
+/// Same as [`period_stats_embed`] with the volume chart attached as the embed image.
+pub async fn period_stats_chart(
+    db: &crate::database::Database,
+    guild_id: i64,
+    period: &StatsPeriod,
+) -> Result<(CreateEmbed, CreateAttachment)> {
+    let stats = crate::database::stats::get_period_stats(&db.pool, guild_id, period.start, period.end).await?;
+    let previous = period.previous();
+    let previous_counts = crate::database::stats::count_opened_closed(&db.pool, guild_id, previous.start, previous.end).await?;
+
+    let embed = build_period_stats_embed(period, &stats, previous_counts)
+        .image("attachment://ticket-stats.png");
+
+    // Rasterising takes long enough that it shouldn't sit on an async worker.
+    let chart_period = period.clone();
+    let png = tokio::task::spawn_blocking(move || super::chart::render_stats_chart(&chart_period, &stats)).await??;
+
+    Ok((embed, CreateAttachment::bytes(png, "ticket-stats.png")))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StaffSort {
+    Claimed,
+    Assigned,
+    Closed,
+    Messages,
+    Notes,
+    Response,
+}
+
+impl StaffSort {
+    pub const ALL: [StaffSort; 6] = [
+        StaffSort::Claimed,
+        StaffSort::Assigned,
+        StaffSort::Closed,
+        StaffSort::Messages,
+        StaffSort::Notes,
+        StaffSort::Response,
+    ];
+
+    pub fn parse(input: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|s| s.key() == input.to_lowercase())
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            StaffSort::Claimed => "claimed",
+            StaffSort::Assigned => "assigned",
+            StaffSort::Closed => "closed",
+            StaffSort::Messages => "messages",
+            StaffSort::Notes => "notes",
+            StaffSort::Response => "response",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            StaffSort::Claimed => "Tickets Claimed",
+            StaffSort::Assigned => "Tickets Assigned",
+            StaffSort::Closed => "Tickets Closed",
+            StaffSort::Messages => "Messages Sent",
+            StaffSort::Notes => "Notes Written",
+            StaffSort::Response => "Avg. First Response",
+        }
+    }
+
+    fn format_value(&self, stats: &StaffStats) -> String {
+        match self {
+            StaffSort::Claimed => stats.claimed.to_string(),
+            StaffSort::Assigned => stats.assigned.to_string(),
+            StaffSort::Closed => stats.closed.to_string(),
+            StaffSort::Messages => stats.messages.to_string(),
+            StaffSort::Notes => stats.notes.to_string(),
+            StaffSort::Response => stats.avg_first_response_seconds
+                .map(format_duration_seconds)
+                .unwrap_or_else(|| "N/A".to_string()),
+        }
+    }
+
+    /// Sorts best-first. Faster responses rank higher, and staff with no data for
+    /// the chosen metric sink to the bottom.
+    pub fn sort(&self, rows: &mut [StaffStats]) {
+        match self {
+            StaffSort::Response => rows.sort_by(|a, b| {
+                let a = a.avg_first_response_seconds.unwrap_or(f64::MAX);
+                let b = b.avg_first_response_seconds.unwrap_or(f64::MAX);
+                a.total_cmp(&b)
+            }),
+            _ => rows.sort_by_key(|s| std::cmp::Reverse(match self {
+                StaffSort::Claimed => s.claimed,
+                StaffSort::Assigned => s.assigned,
+                StaffSort::Closed => s.closed,
+                StaffSort::Messages => s.messages,
+                _ => s.notes,
+            })),
+        }
+    }
+}
+
+pub fn build_staff_stats_embed(period: &StatsPeriod, user_id: u64, stats: Option<&StaffStats>) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .title("Staff Performance")
+        .description(format!(
+            "<@{}>\n**Period:** {}\n<t:{}:f> - <t:{}:f>",
+            user_id,
+            period.label,
+            period.start.timestamp(),
+            period.end.timestamp()
+        ))
+        .color(serenity::all::Colour::from_rgb(88, 101, 242));
+
+    match stats {
+        Some(stats) => {
+            for sort in StaffSort::ALL {
+                embed = embed.field(sort.label(), sort.format_value(stats), true);
+            }
+            embed
+        }
+        None => embed.field("No Activity", "No ticket activity recorded in this period", false),
+    }
+}
+
+pub fn build_staff_leaderboard_embed(period: &StatsPeriod, rows: &[StaffStats], sort: StaffSort) -> CreateEmbed {
+    const MAX_ROWS: usize = 15;
+
+    let description = if rows.is_empty() {
+        "No staff activity recorded in this period".to_string()
+    } else {
+        rows.iter()
+            .take(MAX_ROWS)
+            .enumerate()
+            .map(|(i, s)| format!(
+                "**{}.** <@{}> - **{}** ({} claimed, {} closed, {} messages)",
+                i + 1,
+                s.user_id,
+                sort.format_value(s),
+                s.claimed,
+                s.closed,
+                s.messages
+            ))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    CreateEmbed::new()
+        .title(format!("Staff Leaderboard - {}", sort.label()))
+        .description(format!(
+            "**Period:** {}\n\n{}\n\nSort by: {}",
+            period.label,
+            description,
+            StaffSort::ALL.iter().map(|s| format!("`{}`", s.key())).collect::<Vec<_>>().join(", ")
+        ))
+        .color(serenity::all::Colour::from_rgb(88, 101, 242))
+}
+
+/// Builds either a single member's report or, without `member`, the leaderboard
+/// restricted to current support staff.
+pub async fn staff_stats_embed(
+    ctx: &serenity::all::Context,
+    db: &crate::database::Database,
+    guild_id: i64,
+    period: &StatsPeriod,
+    member: Option<u64>,
+    sort: StaffSort,
+) -> Result<CreateEmbed> {
+    let mut rows = crate::database::stats::get_staff_stats(&db.pool, guild_id, period.start, period.end).await?;
+
+    if let Some(user_id) = member {
+        let stats = rows.iter().find(|s| s.user_id == user_id as i64);
+        return Ok(build_staff_stats_embed(period, user_id, stats));
+    }
+
+    let support_roles = crate::database::ticket::get_support_roles(&db.pool, guild_id).await?;
+    let role_ids: Vec<serenity::all::RoleId> = support_roles
+        .iter()
+        .map(|r| serenity::all::RoleId::new(r.role_id as u64))
+        .collect();
+    let staff: std::collections::HashSet<i64> = serenity::all::GuildId::new(guild_id as u64)
+        .members(&ctx.http, None, None)
+        .await?
+        .into_iter()
+        .filter(|m| !m.user.bot && m.roles.iter().any(|r| role_ids.contains(r)))
+        .map(|m| m.user.id.get() as i64)
+        .collect();
+
+    rows.retain(|s| staff.contains(&s.user_id));
+    sort.sort(&mut rows);
+
+    Ok(build_staff_leaderboard_embed(period, &rows, sort))
+}
+
+const OWNER_STATS_PAGE_SIZE: usize = 10;
+const INACTIVE_DAYS: i64 = 30;
+
+fn growth_label(current: i64, previous: i64) -> String {
+    if previous == 0 {
+        if current == 0 { "-".to_string() } else { "new".to_string() }
+    } else {
+        let change = (current - previous) as f64 / previous as f64 * 100.0;
+        format!("{:+.0}%", change)
+    }
+}
+
+/// Splits `lines` into embeds of at most [`OWNER_STATS_PAGE_SIZE`] lines.
+fn paged_section(title: &str, lines: Vec<String>, empty: &str) -> Vec<CreateEmbed> {
+    if lines.is_empty() {
+        return vec![CreateEmbed::new().title(title).description(empty).color(0x5865F2)];
+    }
+
+    let chunks = lines.chunks(OWNER_STATS_PAGE_SIZE).count();
+    lines
+        .chunks(OWNER_STATS_PAGE_SIZE)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let title = if chunks > 1 { format!("{} ({}/{})", title, i + 1, chunks) } else { title.to_string() };
+            CreateEmbed::new().title(title).description(chunk.join("\n")).color(0x5865F2)
+        })
+        .collect()
+}
+
+/// Builds every page of the owner's cross-guild report. Only guilds the bot is
+/// currently in are listed.
+pub async fn owner_stats_pages(ctx: &serenity::all::Context, db: &crate::database::Database) -> Result<Vec<CreateEmbed>> {
+    let totals = crate::database::stats::get_global_totals(&db.pool).await?;
+    let weekly = crate::database::stats::get_weekly_ticket_counts(&db.pool, 8).await?;
+
+    let current: std::collections::HashSet<i64> = ctx.cache.guilds().iter().map(|g| g.get() as i64).collect();
+    let guilds: Vec<_> = crate::database::stats::get_guild_activity(&db.pool)
+        .await?
+        .into_iter()
+        .filter(|g| current.contains(&g.guild_id))
+        .collect();
+
+    let guild_name = |guild_id: i64| {
+        ctx.cache
+            .guild(serenity::all::GuildId::new(guild_id as u64))
+            .map(|g| g.name.clone())
+            .unwrap_or_else(|| "Unknown".to_string())
+    };
+
+    let (premium, free): (Vec<_>, Vec<_>) = guilds.iter().partition(|g| g.premium);
+    let usage = |group: &[&crate::database::stats::GuildActivity]| {
+        format!(
+            "{} guilds, {} open, {} opened in 30d",
+            group.len(),
+            group.iter().map(|g| g.open).sum::<i64>(),
+            group.iter().map(|g| g.last_30d).sum::<i64>()
+        )
+    };
+
+    let max_week = weekly.iter().map(|(_, count)| *count).max().unwrap_or(0).max(1);
+    let growth = weekly
+        .iter()
+        .map(|(week, count)| {
+            let bar = "█".repeat(((*count as f64 / max_week as f64) * 20.0).round() as usize);
+            format!("`{}` {} {}", week.format("%b %d"), bar, count)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut pages = vec![CreateEmbed::new()
+        .title("Global Ticket Statistics")
+        .color(0x5865F2)
+        .field("Open Tickets", totals.open.to_string(), true)
+        .field("Closed Tickets", totals.closed.to_string(), true)
+        .field("Total Tickets", (totals.open + totals.closed).to_string(), true)
+        .field("Total Messages", totals.messages.to_string(), true)
+        .field(
+            "Avg. Resolution Time",
+            totals.avg_resolution_seconds.map(format_duration_seconds).unwrap_or_else(|| "N/A".to_string()),
+            true,
+        )
+        .field("Guilds", current.len().to_string(), true)
+        .field("Premium", usage(&premium), false)
+        .field("Free", usage(&free), false)
+        .field("Tickets Opened per Week", growth, false)];
+
+    let top = guilds
+        .iter()
+        .filter(|g| g.total > 0)
+        .enumerate()
+        .map(|(i, g)| {
+            format!(
+                "{}. **{}**{} (`{}`) - {} total, {} in 30d ({})",
+                i + 1,
+                guild_name(g.guild_id),
+                if g.premium { " ⭐" } else { "" },
+                g.guild_id,
+                g.total,
+                g.last_30d,
+                growth_label(g.last_30d, g.prev_30d)
+            )
+        })
+        .collect();
+    pages.extend(paged_section("Top Guilds by Ticket Volume", top, "No tickets have been created yet."));
+
+    let misconfigured = guilds
+        .iter()
+        .filter(|g| !g.has_category || !g.has_support_role)
+        .map(|g| {
+            let mut problems = Vec::new();
+            if !g.has_category {
+                problems.push("no ticket category");
+            }
+            if !g.has_support_role {
+                problems.push("no support role");
+            }
+            format!("**{}** (`{}`) - {}", guild_name(g.guild_id), g.guild_id, problems.join(", "))
+        })
+        .collect();
+    pages.extend(paged_section("Configuration Issues", misconfigured, "Every guild is fully set up."));
+
+    let cutoff = Utc::now() - Duration::days(INACTIVE_DAYS);
+    let inactive = guilds
+        .iter()
+        .filter(|g| g.last_ticket_at.is_none_or(|t| t < cutoff))
+        .map(|g| {
+            format!(
+                "**{}** (`{}`) - last ticket {}",
+                guild_name(g.guild_id),
+                g.guild_id,
+                g.last_ticket_at.map(|t| format!("<t:{}:R>", t.timestamp())).unwrap_or_else(|| "never".to_string())
+            )
+        })
+        .collect();
+    pages.extend(paged_section(
+        &format!("Inactive Guilds (no tickets in {} days)", INACTIVE_DAYS),
+        inactive,
+        "Every guild has had a ticket recently.",
+    ));
+
+    let page_count = pages.len();
+    Ok(pages
+        .into_iter()
+        .enumerate()
+        .map(|(i, page)| page.footer(serenity::all::CreateEmbedFooter::new(format!("Page {} of {}", i + 1, page_count))))
+        .collect())
+}
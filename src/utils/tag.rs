@@ -0,0 +1,831 @@
+use anyhow::Result;
+use serenity::all::{
+    ButtonStyle, ChannelId, Colour, Context, CreateActionRow, CreateAttachment, CreateButton, CreateEmbed, CreateEmbedFooter,
+    CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, GuildId, Member, Permissions, RoleId, User, UserId,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::models::{Tag, TagEmbed, TagFolder, TagLibraryEntry, TagRevision};
+
+// Prefix `tag` subcommands; a tag or alias with one of these names could never be used.
+const RESERVED_TAG_NAMES: &[&str] = &[
+    "create", "edit", "delete", "remove", "info", "list", "search", "raw", "rename", "popular", "alias",
+    "history", "rollback", "permissions", "export", "import", "library", "folder", "folders",
+];
+
+pub fn is_reserved_tag_name(name: &str) -> bool {
+    RESERVED_TAG_NAMES.iter().any(|r| r.eq_ignore_ascii_case(name))
+}
+
+/// Returns why `alias` can't be used, or `None` if it is free in this guild.
+pub async fn check_tag_alias(db: &crate::database::Database, guild_id: i64, alias: &str) -> Result<Option<String>> {
+    if alias.len() > 100 {
+        return Ok(Some("Aliases must be 100 characters or less".to_string()));
+    }
+
+    if is_reserved_tag_name(alias) {
+        return Ok(Some(format!("`{}` is reserved for a tag subcommand", alias)));
+    }
+
+    if let Some(existing) = crate::database::tag::get_tag(&db.pool, guild_id, alias).await? {
+        return Ok(Some(if existing.name.eq_ignore_ascii_case(alias) {
+            format!("A tag named `{}` already exists", existing.name)
+        } else {
+            format!("`{}` is already an alias of tag `{}`", alias, existing.name)
+        }));
+    }
+
+    Ok(None)
+}
+
+pub fn format_tag_aliases(aliases: &[String]) -> String {
+    if aliases.is_empty() {
+        "None".to_string()
+    } else {
+        aliases.iter().map(|a| format!("`{}`", a)).collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// Variables available to tag content. Ticket variables are only set when the tag
+/// is used inside a ticket channel.
+pub async fn tag_variables(
+    ctx: &Context,
+    db: &crate::database::Database,
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+    user: &User,
+) -> Result<HashMap<&'static str, String>> {
+    let mut vars = HashMap::new();
+    vars.insert("user", user.name.clone());
+    vars.insert("user.mention", format!("<@{}>", user.id.get()));
+    vars.insert("user.id", user.id.get().to_string());
+    vars.insert("channel", format!("<#{}>", channel_id.get()));
+
+    if let Some(guild_id) = guild_id {
+        if let Some(guild) = ctx.cache.guild(guild_id) {
+            vars.insert("guild", guild.name.clone());
+        }
+    }
+
+    if let Some(ticket) = crate::database::ticket::get_ticket_by_channel(&db.pool, channel_id.get() as i64).await? {
+        let category = match ticket.category_id {
+            Some(category_id) => crate::database::ticket::get_category_name(&db.pool, category_id).await?,
+            None => None,
+        };
+
+        vars.insert("ticket.number", ticket.ticket_number.to_string());
+        vars.insert("ticket.owner", format!("<@{}>", ticket.owner_id));
+        vars.insert(
+            "claimer",
+            ticket.claimed_by.map(|id| format!("<@{}>", id)).unwrap_or_else(|| "Unclaimed".to_string()),
+        );
+        vars.insert("category", category.unwrap_or_else(|| "None".to_string()));
+    }
+
+    Ok(vars)
+}
+
+/// Replaces `{name}` placeholders from `vars` and `{1}`, `{2}`, ... from `args`.
+/// Unknown placeholders and missing arguments are left as written.
+pub fn render_tag(content: &str, vars: &HashMap<&'static str, String>, args: &[&str]) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        let Some(end) = after.find('}') else {
+            output.push_str(&rest[start..]);
+            return output;
+        };
+
+        let key = &after[..end];
+        let value = match key.parse::<usize>() {
+            Ok(index) if index >= 1 => args.get(index - 1).map(|a| a.to_string()),
+            _ => vars.get(key).cloned(),
+        };
+
+        match value {
+            Some(value) => output.push_str(&value),
+            None => output.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &after[end + 1..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Builds the message for a tag, rendering placeholders in text and embed tags alike.
+pub fn tag_message(
+    tag: &Tag,
+    vars: &HashMap<&'static str, String>,
+    args: &[&str],
+) -> (CreateEmbed, Vec<CreateActionRow>) {
+    match &tag.embed {
+        Some(embed) => embed_tag_message(embed, |text| render_tag(text, vars, args)),
+        None => (super::create_embed(&tag.name, render_tag(&tag.content, vars, args)), Vec::new()),
+    }
+}
+
+pub const MAX_EMBED_FIELDS: usize = 25;
+pub const MAX_LINK_BUTTONS: usize = 5;
+// Leaves room under Discord's 4096-character embed description.
+const LIBRARY_LIST_BUDGET: usize = 3900;
+// Discord rejects an embed whose title, description, field names and values and
+// footer add up to more than this.
+const MAX_EMBED_TOTAL_CHARS: usize = 6000;
+
+pub fn is_http_url(url: &str) -> bool {
+    url.starts_with("https://") || url.starts_with("http://")
+}
+
+/// Checks an embed tag against Discord's limits so it can't fail on every use.
+/// Returns the first problem found.
+pub fn validate_tag_embed(embed: &TagEmbed) -> std::result::Result<(), String> {
+    let len = |text: &Option<String>| text.as_deref().map_or(0, |t| t.chars().count());
+
+    if embed.title.is_none() && embed.description.is_none() && embed.fields.is_empty() {
+        return Err("Add a title, description or field".to_string());
+    }
+    if embed.fields.len() > MAX_EMBED_FIELDS {
+        return Err(format!("Embeds can have at most {} fields", MAX_EMBED_FIELDS));
+    }
+    if embed.buttons.len() > MAX_LINK_BUTTONS {
+        return Err(format!("Tags can have at most {} buttons", MAX_LINK_BUTTONS));
+    }
+    if len(&embed.title) > 256 {
+        return Err("The title must be 256 characters or less".to_string());
+    }
+    if len(&embed.description) > 4096 {
+        return Err("The description must be 4096 characters or less".to_string());
+    }
+    if len(&embed.footer) > 2048 {
+        return Err("The footer must be 2048 characters or less".to_string());
+    }
+    if embed.image.as_deref().is_some_and(|url| !is_http_url(url)) {
+        return Err("The image must be an http(s) URL".to_string());
+    }
+    for field in &embed.fields {
+        if field.name.is_empty() || field.value.is_empty() {
+            return Err("Fields need both a name and a value".to_string());
+        }
+        if field.name.chars().count() > 256 || field.value.chars().count() > 1024 {
+            return Err("Field names are limited to 256 characters and values to 1024".to_string());
+        }
+    }
+    for button in &embed.buttons {
+        if button.label.is_empty() || button.label.chars().count() > 80 {
+            return Err("Button labels must be 1-80 characters".to_string());
+        }
+        if !is_http_url(&button.url) {
+            return Err("Button links must be http(s) URLs".to_string());
+        }
+    }
+
+    let total = len(&embed.title)
+        + len(&embed.description)
+        + len(&embed.footer)
+        + embed.fields.iter().map(|f| f.name.chars().count() + f.value.chars().count()).sum::<usize>();
+    if total > MAX_EMBED_TOTAL_CHARS {
+        return Err(format!(
+            "The embed is {} characters long; Discord allows at most {} across the title, description, fields and footer",
+            total, MAX_EMBED_TOTAL_CHARS
+        ));
+    }
+
+    Ok(())
+}
+
+/// Turns a stored embed into an embed plus a row of link buttons. `render` is
+/// applied to every piece of text; the builder preview passes it through as-is.
+pub fn embed_tag_message(embed: &TagEmbed, render: impl Fn(&str) -> String) -> (CreateEmbed, Vec<CreateActionRow>) {
+    let mut builder = CreateEmbed::new().color(Colour::new(embed.color.unwrap_or(0x5865F2)));
+
+    if let Some(title) = &embed.title {
+        builder = builder.title(render(title));
+    }
+    if let Some(description) = &embed.description {
+        builder = builder.description(render(description));
+    }
+    for field in &embed.fields {
+        builder = builder.field(render(&field.name), render(&field.value), field.inline);
+    }
+    if let Some(image) = &embed.image {
+        builder = builder.image(image);
+    }
+    if let Some(footer) = &embed.footer {
+        builder = builder.footer(CreateEmbedFooter::new(render(footer)));
+    }
+
+    let buttons: Vec<CreateButton> = embed.buttons.iter()
+        .map(|button| CreateButton::new_link(&button.url).label(render(&button.label)))
+        .collect();
+
+    let rows = if buttons.is_empty() { Vec::new() } else { vec![CreateActionRow::Buttons(buttons)] };
+    (builder, rows)
+}
+
+/// Parses `#5865F2` or `5865F2`.
+pub fn parse_hex_color(input: &str) -> Option<u32> {
+    let hex = input.trim().trim_start_matches('#');
+    if hex.len() != 6 {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok()
+}
+
+/// Exports an embed tag as JSON, attaching it as a file when it doesn't fit in a message.
+pub fn export_tag_embed(name: &str, embed: &TagEmbed) -> Result<(String, Option<CreateAttachment>)> {
+    let json = serde_json::to_string_pretty(embed)?;
+    let block = format!("```json\n{}\n```", json.replace("```", "\\`\\`\\`"));
+
+    if block.len() <= 2000 {
+        Ok((block, None))
+    } else {
+        Ok((
+            format!("Tag `{}` is too long to show here, so its JSON is attached.", name),
+            Some(CreateAttachment::bytes(json.into_bytes(), format!("{}.json", name))),
+        ))
+    }
+}
+
+const BROWSER_PAGE_SIZE: i64 = 15;
+// A select menu holds 25 options: All Tags, Unfiled and "More folders" plus the folders.
+const BROWSER_FOLDERS_PER_MENU: usize = 22;
+
+/// Which tags the tag browser shows. Stored in the browser's component custom ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BrowserFolder {
+    All,
+    Unfiled,
+    Folder(uuid::Uuid),
+}
+
+impl BrowserFolder {
+    pub fn parse(input: &str) -> Self {
+        match input {
+            "none" => BrowserFolder::Unfiled,
+            id => uuid::Uuid::parse_str(id).map(BrowserFolder::Folder).unwrap_or(BrowserFolder::All),
+        }
+    }
+
+    fn as_id(self) -> String {
+        match self {
+            BrowserFolder::All => "all".to_string(),
+            BrowserFolder::Unfiled => "none".to_string(),
+            BrowserFolder::Folder(id) => id.to_string(),
+        }
+    }
+}
+
+/// One page of the tag browser: a folder picker, a picker that previews a tag
+/// from the page, and Previous/Next buttons when there is more than one page.
+/// With more folders than fit in the picker, `folder_window` picks which slice of
+/// them is listed; `None` shows the slice holding the current folder.
+pub async fn tag_browser(
+    db: &crate::database::Database,
+    guild_id: i64,
+    folder: BrowserFolder,
+    page: i64,
+    folder_window: Option<usize>,
+) -> Result<(CreateEmbed, Vec<CreateActionRow>)> {
+    let folders = crate::database::tag::get_tag_folders(&db.pool, guild_id).await?;
+
+    // Buttons on an old message can point at a folder that has since been deleted.
+    let (folder, label) = match folder {
+        BrowserFolder::Folder(id) => match folders.iter().find(|f| f.id == id) {
+            Some(f) => (folder, f.name.clone()),
+            None => (BrowserFolder::All, "All Tags".to_string()),
+        },
+        BrowserFolder::Unfiled => (folder, "Unfiled".to_string()),
+        BrowserFolder::All => (folder, "All Tags".to_string()),
+    };
+    let (folder_id, unfiled) = match folder {
+        BrowserFolder::Folder(id) => (Some(id), false),
+        BrowserFolder::Unfiled => (None, true),
+        BrowserFolder::All => (None, false),
+    };
+
+    let (mut tags, total) = crate::database::tag::list_tags_page(&db.pool, guild_id, folder_id, unfiled, page.max(0) * BROWSER_PAGE_SIZE, BROWSER_PAGE_SIZE).await?;
+    let page_count = ((total + BROWSER_PAGE_SIZE - 1) / BROWSER_PAGE_SIZE).max(1);
+    let page = page.clamp(0, page_count - 1);
+    if tags.is_empty() && total > 0 {
+        tags = crate::database::tag::list_tags_page(&db.pool, guild_id, folder_id, unfiled, page * BROWSER_PAGE_SIZE, BROWSER_PAGE_SIZE).await?.0;
+    }
+
+    if total == 0 && folder == BrowserFolder::All {
+        return Ok((super::create_error_embed("No Tags", "There are no tags in this server"), Vec::new()));
+    }
+
+    let description = if tags.is_empty() {
+        "No tags in this folder".to_string()
+    } else {
+        tags.iter()
+            .map(|t| format!("`{}` (uses: {})", t.name, t.uses))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let embed = super::create_embed(format!("{} ({})", label, total), description)
+        .footer(CreateEmbedFooter::new(format!("Page {}/{} • Pick a tag below to preview it", page + 1, page_count)));
+
+    let mut components = Vec::new();
+
+    if !folders.is_empty() {
+        let window_count = folders.len().div_ceil(BROWSER_FOLDERS_PER_MENU);
+        let window = folder_window
+            .unwrap_or_else(|| folders.iter().position(|f| folder == BrowserFolder::Folder(f.id)).unwrap_or(0) / BROWSER_FOLDERS_PER_MENU)
+            % window_count;
+        let shown = &folders[window * BROWSER_FOLDERS_PER_MENU..folders.len().min((window + 1) * BROWSER_FOLDERS_PER_MENU)];
+
+        let mut options = vec![
+            CreateSelectMenuOption::new("All Tags", "all").default_selection(folder == BrowserFolder::All),
+            CreateSelectMenuOption::new("Unfiled", "none").default_selection(folder == BrowserFolder::Unfiled),
+        ];
+        options.extend(shown.iter().map(|f| {
+            CreateSelectMenuOption::new(&f.name, f.id.to_string())
+                .description(format!("{} tags", f.tag_count))
+                .default_selection(folder == BrowserFolder::Folder(f.id))
+        }));
+        if window_count > 1 {
+            // Keeps the current folder and page so only the listed folders change.
+            options.push(
+                CreateSelectMenuOption::new("More folders...", format!("more:{}:{}:{}", (window + 1) % window_count, folder.as_id(), page))
+                    .description(format!("Showing {}-{} of {} folders", window * BROWSER_FOLDERS_PER_MENU + 1, window * BROWSER_FOLDERS_PER_MENU + shown.len(), folders.len())),
+            );
+        }
+        components.push(CreateActionRow::SelectMenu(
+            CreateSelectMenu::new("tag_browser_folder", CreateSelectMenuKind::String { options })
+                .placeholder("Choose a folder"),
+        ));
+    }
+
+    if !tags.is_empty() {
+        let options = tags.iter()
+            .map(|t| CreateSelectMenuOption::new(&t.name, &t.name))
+            .collect();
+        components.push(CreateActionRow::SelectMenu(
+            CreateSelectMenu::new("tag_browser_preview", CreateSelectMenuKind::String { options })
+                .placeholder("Preview a tag"),
+        ));
+    }
+
+    if page_count > 1 {
+        components.push(CreateActionRow::Buttons(vec![
+            CreateButton::new(format!("tag_browser:{}:{}", folder.as_id(), page.saturating_sub(1)))
+                .label("Previous")
+                .style(ButtonStyle::Secondary)
+                .disabled(page == 0),
+            CreateButton::new(format!("tag_browser:{}:{}", folder.as_id(), (page + 1).min(page_count - 1)))
+                .label("Next")
+                .style(ButtonStyle::Secondary)
+                .disabled(page + 1 >= page_count),
+        ]));
+    }
+
+    Ok((embed, components))
+}
+
+/// Folder names are single words so prefix commands can take them; "none" means no folder.
+pub fn valid_folder_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().count() <= 50 && !name.contains(char::is_whitespace) && !name.eq_ignore_ascii_case("none")
+}
+
+/// Moves a tag into the named folder, or out of any folder for `None` or "none".
+pub async fn move_tag_to_folder(db: &crate::database::Database, guild_id: i64, tag: &Tag, folder: Option<&str>) -> Result<CreateEmbed> {
+    let Some(folder) = folder.filter(|f| !f.eq_ignore_ascii_case("none")) else {
+        crate::database::tag::set_tag_folder(&db.pool, tag.id, None).await?;
+        return Ok(super::create_success_embed("Tag Moved", format!("Tag `{}` is no longer in a folder", tag.name)));
+    };
+
+    Ok(match crate::database::tag::get_tag_folder(&db.pool, guild_id, folder).await? {
+        Some(found) => {
+            crate::database::tag::set_tag_folder(&db.pool, tag.id, Some(found.id)).await?;
+            super::create_success_embed("Tag Moved", format!("Tag `{}` is now in folder `{}`", tag.name, found.name))
+        }
+        None => super::create_error_embed("Folder Not Found", format!("Folder `{}` does not exist", folder)),
+    })
+}
+
+pub fn format_tag_folders(folders: &[TagFolder]) -> String {
+    if folders.is_empty() {
+        return "No folders yet".to_string();
+    }
+
+    folders.iter()
+        .map(|f| format!("`{}` ({} tags)", f.name, f.tag_count))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+const TAG_EXPORT_VERSION: u32 = 1;
+pub const MAX_IMPORT_BYTES: u32 = 1024 * 1024;
+const MAX_IMPORT_TAGS: usize = 500;
+
+/// The file written by `tag export` and read by `tag import`.
+#[derive(Serialize, Deserialize)]
+struct TagExportFile {
+    version: u32,
+    #[serde(default)]
+    guild_id: Option<String>,
+    tags: Vec<ExportedTag>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ExportedTag {
+    name: String,
+    #[serde(default)]
+    content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    embed: Option<TagEmbed>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    aliases: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    folder: Option<String>,
+}
+
+/// What to do when an imported tag's name is already taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictStrategy {
+    Skip,
+    Overwrite,
+    Rename,
+}
+
+impl ConflictStrategy {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.to_lowercase().as_str() {
+            "skip" => Some(ConflictStrategy::Skip),
+            "overwrite" => Some(ConflictStrategy::Overwrite),
+            "rename" => Some(ConflictStrategy::Rename),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ImportSummary {
+    pub created: Vec<String>,
+    pub overwritten: Vec<String>,
+    pub renamed: Vec<(String, String)>,
+    pub skipped: Vec<String>,
+    pub invalid: Vec<String>,
+}
+
+/// Every tag in the guild, with aliases, as a JSON attachment. Returns the tag count too.
+pub async fn export_tags(db: &crate::database::Database, guild_id: i64) -> Result<(usize, CreateAttachment)> {
+    let tags = crate::database::tag::list_tags(&db.pool, guild_id).await?;
+    let mut aliases: HashMap<uuid::Uuid, Vec<String>> = HashMap::new();
+    for (tag_id, alias) in crate::database::tag::get_guild_tag_aliases(&db.pool, guild_id).await? {
+        aliases.entry(tag_id).or_default().push(alias);
+    }
+    let mut folders: HashMap<uuid::Uuid, String> = crate::database::tag::get_tag_folder_names(&db.pool, guild_id).await?
+        .into_iter()
+        .collect();
+
+    let file = TagExportFile {
+        version: TAG_EXPORT_VERSION,
+        guild_id: Some(guild_id.to_string()),
+        tags: tags.iter()
+            .map(|tag| ExportedTag {
+                name: tag.name.clone(),
+                content: tag.content.clone(),
+                embed: tag.embed.as_ref().map(|embed| embed.0.clone()),
+                aliases: aliases.remove(&tag.id).unwrap_or_default(),
+                folder: folders.remove(&tag.id),
+            })
+            .collect(),
+    };
+
+    let json = serde_json::to_vec_pretty(&file)?;
+    Ok((tags.len(), CreateAttachment::bytes(json, format!("tags-{}.json", guild_id))))
+}
+
+/// Imports tags from an export file in a single transaction. Entries whose embed
+/// would fail the builder's checks are reported as invalid; aliases are only added
+/// where they are free.
+pub async fn import_tags(
+    db: &crate::database::Database,
+    guild_id: i64,
+    data: &[u8],
+    strategy: ConflictStrategy,
+    user_id: i64,
+) -> Result<ImportSummary> {
+    let file: TagExportFile = serde_json::from_slice(data)
+        .map_err(|e| anyhow::anyhow!("This is not a tag export file ({})", e))?;
+
+    if file.version > TAG_EXPORT_VERSION {
+        anyhow::bail!("This file was exported by a newer version of the bot");
+    }
+    if file.tags.len() > MAX_IMPORT_TAGS {
+        anyhow::bail!("A file can hold at most {} tags, this one has {}", MAX_IMPORT_TAGS, file.tags.len());
+    }
+
+    let mut summary = ImportSummary::default();
+    let mut tx = db.pool.begin().await?;
+
+    for imported in file.tags {
+        let name = imported.name.trim();
+        let valid = !name.is_empty()
+            && name.len() <= 100
+            && !name.contains(char::is_whitespace)
+            && !is_reserved_tag_name(name)
+            && (!imported.content.trim().is_empty() || imported.embed.is_some())
+            && imported.embed.as_ref().is_none_or(|embed| validate_tag_embed(embed).is_ok());
+        if !valid {
+            summary.invalid.push(imported.name.clone());
+            continue;
+        }
+
+        let existing = crate::database::tag::get_tag(&mut *tx, guild_id, name).await?;
+        let target = match (existing, strategy) {
+            (None, _) => {
+                summary.created.push(name.to_string());
+                name.to_string()
+            }
+            (Some(_), ConflictStrategy::Skip) => {
+                summary.skipped.push(name.to_string());
+                continue;
+            }
+            (Some(tag), ConflictStrategy::Overwrite) if tag.name.eq_ignore_ascii_case(name) => {
+                crate::database::tag::overwrite_tag_in(&mut tx, guild_id, &tag.name, &imported.content, imported.embed.as_ref(), user_id).await?;
+                summary.overwritten.push(tag.name.clone());
+                add_imported_extras(&mut tx, guild_id, tag.id, &imported, user_id).await?;
+                continue;
+            }
+            // The name is an alias of another tag, which an import shouldn't take over.
+            (Some(_), ConflictStrategy::Overwrite) => {
+                summary.skipped.push(name.to_string());
+                continue;
+            }
+            (Some(_), ConflictStrategy::Rename) => match free_tag_name(&mut tx, guild_id, name).await? {
+                Some(free) => {
+                    summary.renamed.push((name.to_string(), free.clone()));
+                    free
+                }
+                None => {
+                    summary.skipped.push(name.to_string());
+                    continue;
+                }
+            },
+        };
+
+        let tag = match &imported.embed {
+            Some(embed) => crate::database::tag::create_embed_tag(&mut *tx, guild_id, &target, embed, user_id).await?,
+            None => crate::database::tag::create_tag(&mut *tx, guild_id, &target, &imported.content, user_id).await?,
+        };
+        add_imported_extras(&mut tx, guild_id, tag.id, &imported, user_id).await?;
+    }
+
+    tx.commit().await?;
+    Ok(summary)
+}
+
+/// Files an imported tag into its folder, creating the folder if needed, and adds
+/// whichever of its aliases are free.
+async fn add_imported_extras(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, guild_id: i64, tag_id: uuid::Uuid, imported: &ExportedTag, user_id: i64) -> Result<()> {
+    if let Some(folder) = imported.folder.as_deref().map(str::trim).filter(|f| valid_folder_name(f)) {
+        let folder_id = match crate::database::tag::get_tag_folder(&mut **tx, guild_id, folder).await? {
+            Some(existing) => Some(existing.id),
+            None => crate::database::tag::create_tag_folder(&mut **tx, guild_id, folder, user_id).await?,
+        };
+        if folder_id.is_some() {
+            crate::database::tag::set_tag_folder(&mut **tx, tag_id, folder_id).await?;
+        }
+    }
+
+    for alias in &imported.aliases {
+        let alias = alias.trim();
+        let usable = !alias.is_empty()
+            && alias.len() <= 100
+            && !alias.contains(char::is_whitespace)
+            && !is_reserved_tag_name(alias);
+        if usable && crate::database::tag::get_tag(&mut **tx, guild_id, alias).await?.is_none() {
+            crate::database::tag::add_tag_alias(&mut **tx, guild_id, tag_id, alias, user_id).await?;
+        }
+    }
+    Ok(())
+}
+
+/// First of `name-2`, `name-3`, ... that isn't a tag or alias yet.
+async fn free_tag_name(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, guild_id: i64, name: &str) -> Result<Option<String>> {
+    for n in 2..100 {
+        let candidate = format!("{}-{}", name, n);
+        if candidate.len() <= 100 && crate::database::tag::get_tag(&mut **tx, guild_id, &candidate).await?.is_none() {
+            return Ok(Some(candidate));
+        }
+    }
+    Ok(None)
+}
+
+pub fn format_import_summary(summary: &ImportSummary) -> String {
+    fn names(names: &[String]) -> String {
+        let mut shown: Vec<String> = names.iter().take(15).map(|n| format!("`{}`", n)).collect();
+        if names.len() > 15 {
+            shown.push(format!("and {} more", names.len() - 15));
+        }
+        shown.join(", ")
+    }
+
+    let renamed: Vec<String> = summary.renamed.iter().map(|(from, to)| format!("{} → {}", from, to)).collect();
+    let sections = [
+        ("Created", &summary.created),
+        ("Overwritten", &summary.overwritten),
+        ("Renamed", &renamed),
+        ("Skipped (name taken)", &summary.skipped),
+        ("Invalid", &summary.invalid),
+    ];
+
+    let lines: Vec<String> = sections.iter()
+        .filter(|(_, list)| !list.is_empty())
+        .map(|(label, list)| format!("**{} ({}):** {}", label, list.len(), names(list)))
+        .collect();
+
+    if lines.is_empty() {
+        "The file didn't contain any tags".to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+pub fn library_subscribed_message(added: u64, skipped: u64) -> String {
+    let mut message = format!("Added {} library tags. They update when the library does, until you edit them.", added);
+    if skipped > 0 {
+        message.push_str(&format!(" {} were skipped because this server already has a tag or alias with the same name.", skipped));
+    }
+    message
+}
+
+pub fn format_library_tags(entries: &[TagLibraryEntry]) -> String {
+    if entries.is_empty() {
+        return "The tag library is empty".to_string();
+    }
+
+    let mut list = String::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let line = format!("`{}` - {}", entry.name, revision_preview(&entry.content, entry.embed.as_deref()));
+        if list.len() + line.len() + 1 > LIBRARY_LIST_BUDGET {
+            list.push_str(&format!("\n...and {} more", entries.len() - i));
+            break;
+        }
+        if !list.is_empty() {
+            list.push('\n');
+        }
+        list.push_str(&line);
+    }
+    list
+}
+
+/// "Tag not found" embed with the closest names in this guild, if any.
+pub async fn tag_not_found_embed(db: &crate::database::Database, guild_id: i64, name: &str) -> Result<CreateEmbed> {
+    let suggestions = crate::database::tag::fuzzy_tag_names(&db.pool, guild_id, name, 3).await?;
+
+    let mut description = format!("Tag `{}` does not exist", name);
+    if !suggestions.is_empty() {
+        let names: Vec<String> = suggestions.iter().map(|s| format!("`{}`", s)).collect();
+        description.push_str(&format!("\n\nDid you mean {}?", names.join(", ")));
+    }
+
+    Ok(super::create_error_embed("Tag Not Found", description))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagAction {
+    Create,
+    Edit,
+    Delete,
+}
+
+impl TagAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TagAction::Create => "create",
+            TagAction::Edit => "edit",
+            TagAction::Delete => "delete",
+        }
+    }
+
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.to_lowercase().as_str() {
+            "create" => Some(TagAction::Create),
+            "edit" => Some(TagAction::Edit),
+            "delete" => Some(TagAction::Delete),
+            _ => None,
+        }
+    }
+}
+
+/// Creators can always edit and delete their own tags and administrators can do
+/// anything. Everyone else needs a role configured for the action; with none
+/// configured, anyone may create tags and nobody else may edit or delete them.
+pub async fn can_manage_tag(
+    ctx: &Context,
+    db: &crate::database::Database,
+    guild_id: GuildId,
+    user_id: UserId,
+    action: TagAction,
+    creator_id: Option<i64>,
+) -> Result<bool> {
+    if action != TagAction::Create && creator_id == Some(user_id.get() as i64) {
+        return Ok(true);
+    }
+
+    let member = guild_id.member(&ctx.http, user_id).await?;
+    if is_guild_admin(ctx, &member) {
+        return Ok(true);
+    }
+
+    let roles: Vec<i64> = crate::database::tag::get_tag_permission_roles(&db.pool, guild_id.get() as i64)
+        .await?
+        .into_iter()
+        .filter(|(a, _)| a == action.as_str())
+        .map(|(_, role_id)| role_id)
+        .collect();
+
+    if roles.is_empty() {
+        return Ok(action == TagAction::Create);
+    }
+
+    Ok(member.roles.iter().any(|role| roles.contains(&(role.get() as i64))))
+}
+
+/// Guild-wide administrator check from the member's roles, ignoring channel overwrites.
+pub fn is_guild_admin(ctx: &Context, member: &Member) -> bool {
+    let Some(guild) = ctx.cache.guild(member.guild_id) else {
+        return false;
+    };
+
+    guild.owner_id == member.user.id
+        || std::iter::once(RoleId::new(guild.id.get()))
+            .chain(member.roles.iter().copied())
+            .filter_map(|role_id| guild.roles.get(&role_id))
+            .any(|role| role.permissions.contains(Permissions::ADMINISTRATOR))
+}
+
+pub fn permission_denied_message(action: TagAction) -> String {
+    match action {
+        TagAction::Create => "You don't have a role that can create tags".to_string(),
+        TagAction::Edit => "You can only edit tags you created unless you have a tag editor role".to_string(),
+        TagAction::Delete => "You can only delete tags you created unless you have a tag deleter role".to_string(),
+    }
+}
+
+pub fn format_tag_permissions(roles: &[(String, i64)]) -> String {
+    [TagAction::Create, TagAction::Edit, TagAction::Delete]
+        .iter()
+        .map(|action| {
+            let mentions: Vec<String> = roles.iter()
+                .filter(|(a, _)| a == action.as_str())
+                .map(|(_, role_id)| format!("<@&{}>", role_id))
+                .collect();
+            let who = match (action, mentions.is_empty()) {
+                (TagAction::Create, true) => "Everyone".to_string(),
+                (TagAction::Create, false) => mentions.join(", "),
+                (_, true) => "Tag creators only".to_string(),
+                (_, false) => format!("Tag creators and {}", mentions.join(", ")),
+            };
+            format!("**{}:** {}", action.as_str(), who)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n\nAdministrators can always manage every tag."
+}
+
+fn revision_preview(content: &str, embed: Option<&TagEmbed>) -> String {
+    let text = match embed {
+        Some(embed) => format!("[embed] {}", embed.title.as_deref().or(embed.description.as_deref()).unwrap_or_default()),
+        None => content.to_string(),
+    };
+    let text = text.replace('\n', " ").replace('`', "'");
+    if text.trim().is_empty() {
+        "(empty)".to_string()
+    } else if text.chars().count() > 80 {
+        format!("{}...", text.chars().take(77).collect::<String>())
+    } else {
+        text
+    }
+}
+
+pub fn format_tag_history(tag: &Tag, revisions: &[TagRevision]) -> String {
+    let mut lines = vec![format!(
+        "**Current** - updated <t:{}:R>\n`{}`",
+        tag.updated_at.timestamp(),
+        revision_preview(&tag.content, tag.embed.as_deref())
+    )];
+
+    lines.extend(revisions.iter().map(|r| format!(
+        "**#{}** - replaced by <@{}> <t:{}:R>\n`{}`",
+        r.revision,
+        r.replaced_by,
+        r.created_at.timestamp(),
+        revision_preview(&r.content, r.embed.as_deref())
+    )));
+
+    if revisions.is_empty() {
+        lines.push("No earlier versions yet.".to_string());
+    }
+
+    lines.join("\n\n")
+}
@@ -0,0 +1,312 @@
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+
+const DEFAULT_TIME_OF_DAY: (u32, u32) = (9, 0);
+
+/// Parses a duration such as `30m`, `2h`, `1h30m` or `1w2d`.
+pub fn parse_duration(input: &str) -> Result<Duration> {
+    let input = input.trim().to_lowercase();
+
+    if input.is_empty() {
+        return Err(anyhow::anyhow!("Time string is empty"));
+    }
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let value: i64 = number.parse().map_err(|_| anyhow::anyhow!("Invalid number in `{}`", input))?;
+        number.clear();
+
+        let part = match c {
+            's' => Duration::try_seconds(value),
+            'm' => Duration::try_minutes(value),
+            'h' => Duration::try_hours(value),
+            'd' => Duration::try_days(value),
+            'w' => Duration::try_weeks(value),
+            _ => return Err(anyhow::anyhow!("Invalid time unit '{}'. Use s (seconds), m (minutes), h (hours), d (days), or w (weeks)", c)),
+        };
+        total = part.and_then(|part| total.checked_add(&part)).ok_or_else(|| anyhow::anyhow!("Duration too large"))?;
+    }
+
+    if !number.is_empty() {
+        return Err(anyhow::anyhow!("`{}` is missing a unit at the end, e.g. `{}m`", input, input));
+    }
+    if total <= Duration::zero() {
+        return Err(anyhow::anyhow!("Time value must be positive"));
+    }
+
+    Ok(total)
+}
+
+/// Parses an IANA timezone name such as `Europe/London`, ignoring case.
+pub fn parse_timezone(input: &str) -> Option<Tz> {
+    input.parse::<Tz>().ok()
+        .or_else(|| chrono_tz::TZ_VARIANTS.iter().find(|tz| tz.name().eq_ignore_ascii_case(input)).copied())
+}
+
+/// Timezone names containing `query`, for autocomplete.
+pub fn search_timezones(query: &str, limit: usize) -> Vec<&'static str> {
+    let query = query.to_lowercase();
+    chrono_tz::TZ_VARIANTS.iter()
+        .map(|tz| tz.name())
+        .filter(|name| name.to_lowercase().contains(&query))
+        .take(limit)
+        .collect()
+}
+
+/// Parses `09:00`, `9:30`, `9am` or `9pm`.
+pub fn parse_time_of_day(input: &str) -> Option<NaiveTime> {
+    let input = input.trim().to_lowercase();
+
+    let (clock, pm) = if let Some(clock) = input.strip_suffix("am") {
+        (clock, Some(false))
+    } else if let Some(clock) = input.strip_suffix("pm") {
+        (clock, Some(true))
+    } else {
+        (input.as_str(), None)
+    };
+
+    let (hour, minute) = match clock.split_once(':') {
+        Some((h, m)) => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?),
+        None if pm.is_some() => (clock.parse::<u32>().ok()?, 0),
+        None => return None,
+    };
+
+    let hour = match pm {
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some(true) => hour % 12 + 12,
+        Some(false) => hour % 12,
+        None => hour,
+    };
+
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// Parses a weekday name, abbreviation or plural (`mon`, `monday`, `mondays`).
+pub fn parse_weekday(input: &str) -> Option<Weekday> {
+    let input = input.to_lowercase();
+    input.parse().ok().or_else(|| input.strip_suffix('s')?.parse().ok())
+}
+
+/// The UTC instant of a local date and time, or `None` if the clocks skip over it.
+pub fn local_to_utc(tz: Tz, date: NaiveDate, time: NaiveTime) -> Option<DateTime<Utc>> {
+    tz.from_local_datetime(&date.and_time(time)).earliest().map(|t| t.with_timezone(&Utc))
+}
+
+/// Reads a point in time from the start of `args` and returns it with the number
+/// of words used. Accepts a duration (`1h30m`, `in 2d`), a time (`14:00`, `9am`),
+/// a day (`today`, `tomorrow`, `friday`, `2026-11-01`) optionally followed by
+/// `[at] <time>`, or `2026-11-01T14:00`. Days without a time mean 09:00, and times
+/// are read in `tz`.
+pub fn parse_when(args: &[&str], now: DateTime<Utc>, tz: Tz) -> Result<(DateTime<Utc>, usize)> {
+    let first = args.first().ok_or_else(|| anyhow::anyhow!("Say when, e.g. `30m`, `tomorrow 9am` or `2026-11-01 14:00`"))?;
+
+    if first.eq_ignore_ascii_case("in") {
+        let duration = args.get(1).ok_or_else(|| anyhow::anyhow!("Expected a duration after `in`, e.g. `in 2h`"))?;
+        return Ok((after(now, parse_duration(duration)?)?, 2));
+    }
+    if let Ok(duration) = parse_duration(first) {
+        return Ok((after(now, duration)?, 1));
+    }
+    if let Ok(local) = NaiveDateTime::parse_from_str(first, "%Y-%m-%dT%H:%M") {
+        let at = local_to_utc(tz, local.date(), local.time())
+            .ok_or_else(|| anyhow::anyhow!("`{}` doesn't exist in {} because of a clock change", first, tz.name()))?;
+        return future(at, now, 1);
+    }
+
+    let today = now.with_timezone(&tz).date_naive();
+    let lower = first.to_lowercase();
+    let day = match lower.as_str() {
+        "today" => Some(Day::Date(today)),
+        "tomorrow" => today.succ_opt().map(Day::Date),
+        _ => match (parse_weekday(&lower), NaiveDate::parse_from_str(&lower, "%Y-%m-%d")) {
+            (Some(weekday), _) => Some(Day::Weekday(weekday)),
+            (None, Ok(date)) => Some(Day::Date(date)),
+            (None, Err(_)) => None,
+        },
+    };
+
+    let mut used = usize::from(day.is_some());
+    if day.is_some() && args.get(used).is_some_and(|w| w.eq_ignore_ascii_case("at")) && args.get(used + 1).and_then(|w| parse_time_of_day(w)).is_some() {
+        used += 1;
+    }
+    let time = args.get(used).and_then(|w| parse_time_of_day(w));
+    if time.is_some() {
+        used += 1;
+    }
+
+    let (day, time) = match (day, time) {
+        // Something like `1h30` was meant as a duration, so its error is the helpful one.
+        (None, None) if first.starts_with(|c: char| c.is_ascii_digit()) && !first.contains([':', '-']) => {
+            return Err(parse_duration(first).unwrap_err());
+        }
+        (None, None) => anyhow::bail!(
+            "Couldn't read a time from `{}`. Try `30m`, `1h30m`, `14:00`, `tomorrow 9am`, `friday` or `2026-11-01 14:00`",
+            first
+        ),
+        (day, time) => (
+            day,
+            time.unwrap_or_else(|| NaiveTime::from_hms_opt(DEFAULT_TIME_OF_DAY.0, DEFAULT_TIME_OF_DAY.1, 0).unwrap_or_default()),
+        ),
+    };
+
+    let at = match day {
+        Some(Day::Date(date)) => local_to_utc(tz, date, time),
+        // A bare time or weekday means the next time it comes round.
+        Some(Day::Weekday(weekday)) => (0..=7)
+            .filter_map(|offset| today.checked_add_signed(Duration::days(offset)))
+            .filter(|date| date.weekday() == weekday)
+            .filter_map(|date| local_to_utc(tz, date, time))
+            .find(|at| *at > now),
+        None => (0..=1)
+            .filter_map(|offset| today.checked_add_signed(Duration::days(offset)))
+            .filter_map(|date| local_to_utc(tz, date, time))
+            .find(|at| *at > now),
+    }
+    .ok_or_else(|| anyhow::anyhow!("That time doesn't exist in {} because of a clock change", tz.name()))?;
+
+    future(at, now, used)
+}
+
+enum Day {
+    Date(NaiveDate),
+    Weekday(Weekday),
+}
+
+fn after(now: DateTime<Utc>, duration: Duration) -> Result<DateTime<Utc>> {
+    now.checked_add_signed(duration).ok_or_else(|| anyhow::anyhow!("Duration too large"))
+}
+
+fn future(at: DateTime<Utc>, now: DateTime<Utc>, used: usize) -> Result<(DateTime<Utc>, usize)> {
+    if at <= now {
+        anyhow::bail!("That time has already passed");
+    }
+    Ok((at, used))
+}
+
+/// The user's saved timezone, or UTC if they haven't set one.
+pub async fn user_timezone(db: &crate::database::Database, user_id: i64) -> Result<Tz> {
+    Ok(crate::database::ticket::get_user_timezone(&db.pool, user_id)
+        .await?
+        .and_then(|tz| parse_timezone(&tz))
+        .unwrap_or(Tz::UTC))
+}
+
+/// "Your timezone is **Europe/London** (currently 14:05)" style line for replies.
+pub fn describe_timezone(tz: Tz, now: DateTime<Utc>) -> String {
+    format!("**{}** (currently {})", tz.name(), now.with_timezone(&tz).format("%H:%M, %a %d %b"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        // A Wednesday.
+        Utc.with_ymd_and_hms(2026, 10, 14, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn parse_duration_units() {
+        assert_eq!(parse_duration("30m").unwrap(), Duration::minutes(30));
+        assert_eq!(parse_duration("1h30m").unwrap(), Duration::minutes(90));
+        assert_eq!(parse_duration("1w2d").unwrap(), Duration::days(9));
+        assert_eq!(parse_duration(" 45S ").unwrap(), Duration::seconds(45));
+    }
+
+    #[test]
+    fn parse_duration_rejects_bad_input() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("0m").is_err());
+        assert!(parse_duration("90").is_err());
+        assert!(parse_duration("5y").is_err());
+        assert!(parse_duration("h").is_err());
+    }
+
+    #[test]
+    fn parse_duration_overflow_is_an_error() {
+        let err = parse_duration("9999999999999999w").unwrap_err();
+        assert_eq!(err.to_string(), "Duration too large");
+        assert!(parse_duration("9223372036854775807s9223372036854775807s").is_err());
+    }
+
+    #[test]
+    fn parse_time_of_day_formats() {
+        assert_eq!(parse_time_of_day("09:00"), NaiveTime::from_hms_opt(9, 0, 0));
+        assert_eq!(parse_time_of_day("9:30"), NaiveTime::from_hms_opt(9, 30, 0));
+        assert_eq!(parse_time_of_day("9am"), NaiveTime::from_hms_opt(9, 0, 0));
+        assert_eq!(parse_time_of_day("9PM"), NaiveTime::from_hms_opt(21, 0, 0));
+        assert_eq!(parse_time_of_day("12am"), NaiveTime::from_hms_opt(0, 0, 0));
+        assert_eq!(parse_time_of_day("12:15pm"), NaiveTime::from_hms_opt(12, 15, 0));
+    }
+
+    #[test]
+    fn parse_time_of_day_rejects_bad_input() {
+        assert_eq!(parse_time_of_day("9"), None);
+        assert_eq!(parse_time_of_day("13pm"), None);
+        assert_eq!(parse_time_of_day("24:00"), None);
+        assert_eq!(parse_time_of_day("9:60"), None);
+        assert_eq!(parse_time_of_day("noon"), None);
+    }
+
+    #[test]
+    fn parse_when_durations() {
+        assert_eq!(parse_when(&["30m", "tea"], now(), Tz::UTC).unwrap(), (now() + Duration::minutes(30), 1));
+        assert_eq!(parse_when(&["in", "2h"], now(), Tz::UTC).unwrap(), (now() + Duration::hours(2), 2));
+        assert!(parse_when(&["in"], now(), Tz::UTC).is_err());
+    }
+
+    #[test]
+    fn parse_when_duration_past_the_calendar_is_an_error() {
+        let err = parse_when(&["in", "15250000w"], now(), Tz::UTC).unwrap_err();
+        assert_eq!(err.to_string(), "Duration too large");
+        assert!(parse_when(&["15250000w"], now(), Tz::UTC).is_err());
+    }
+
+    #[test]
+    fn parse_when_days_and_times() {
+        let tomorrow_nine = Utc.with_ymd_and_hms(2026, 10, 15, 9, 0, 0).unwrap();
+        assert_eq!(parse_when(&["tomorrow"], now(), Tz::UTC).unwrap(), (tomorrow_nine, 1));
+        assert_eq!(parse_when(&["tomorrow", "at", "9am", "x"], now(), Tz::UTC).unwrap(), (tomorrow_nine, 3));
+
+        // A time that has passed today means tomorrow.
+        assert_eq!(parse_when(&["9:00"], now(), Tz::UTC).unwrap(), (tomorrow_nine, 1));
+        assert_eq!(
+            parse_when(&["14:00"], now(), Tz::UTC).unwrap(),
+            (Utc.with_ymd_and_hms(2026, 10, 14, 14, 0, 0).unwrap(), 1)
+        );
+
+        assert_eq!(
+            parse_when(&["friday", "5pm"], now(), Tz::UTC).unwrap(),
+            (Utc.with_ymd_and_hms(2026, 10, 16, 17, 0, 0).unwrap(), 2)
+        );
+        assert_eq!(
+            parse_when(&["2026-11-01T14:00"], now(), Tz::UTC).unwrap(),
+            (Utc.with_ymd_and_hms(2026, 11, 1, 14, 0, 0).unwrap(), 1)
+        );
+    }
+
+    #[test]
+    fn parse_when_reads_times_in_the_timezone() {
+        let tz = parse_timezone("europe/london").unwrap();
+        // London is on BST (UTC+1) until 25 October 2026.
+        assert_eq!(
+            parse_when(&["tomorrow", "9am"], now(), tz).unwrap(),
+            (Utc.with_ymd_and_hms(2026, 10, 15, 8, 0, 0).unwrap(), 2)
+        );
+    }
+
+    #[test]
+    fn parse_when_rejects_the_past_and_nonsense() {
+        assert_eq!(parse_when(&["2026-01-01T09:00"], now(), Tz::UTC).unwrap_err().to_string(), "That time has already passed");
+        assert!(parse_when(&["whenever"], now(), Tz::UTC).is_err());
+        assert!(parse_when(&[], now(), Tz::UTC).is_err());
+    }
+}